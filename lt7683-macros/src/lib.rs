@@ -0,0 +1,231 @@
+//! Build-time image decoding for the `lt7683` crate, used by its `include_image!` macro with
+//! the `png` feature. Not meant to be used directly.
+
+use proc_macro::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::path::{Path, PathBuf};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// `include_image!($crate, "path", color_depth)`: the image file at `path`, relative to the
+/// file of the call, as an `Image` at `color_depth`. PNG files are decoded here into 8-bit RGB,
+/// dropping any alpha channel; other files are handed to the PPM conversion of the driver.
+/// Either way the conversion to the color depth runs at compile time in the driver.
+#[doc(hidden)]
+#[proc_macro]
+pub fn include_image(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err((message, span)) => compile_error(&message, span),
+    }
+}
+
+type Error = (String, Span);
+
+fn expand(input: TokenStream) -> Result<TokenStream, Error> {
+    let args = split_args(input);
+    let [krate, path, color_depth] = args.as_slice() else {
+        return Err(("expected a path and a color depth".into(), Span::call_site()));
+    };
+    let (path, path_span) = string_literal(path)?;
+    let file = resolve(&path, path_span);
+    let bytes = std::fs::read(&file).map_err(|error| (format!("cannot read {}: {error}", file.display()), path_span))?;
+    let file_literal = TokenTree::Literal(Literal::string(&file.to_string_lossy()));
+    if !bytes.starts_with(&PNG_SIGNATURE) {
+        let args = [file_literal.into(), punct(','), color_depth.clone()];
+        return Ok(macro_call(krate, "__include_ppm", args.into_iter().collect()));
+    }
+    let (width, height, rgb) = decode_png(&bytes).map_err(|message| (format!("cannot decode {}: {message}", file.display()), path_span))?;
+    let mut body = TokenStream::new();
+    // Rebuild when the file changes
+    body.extend(tokens("const _: &[u8] = include_bytes!"));
+    body.extend([group(Delimiter::Parenthesis, file_literal.into()), punct(';')]);
+    body.extend(tokens("const RGB: &[u8] ="));
+    body.extend([TokenTree::Literal(Literal::byte_string(&rgb)).into(), punct(';')]);
+    // As in the PPM conversion, the color depth is not given a name
+    let mut len = tokens(&format!("{width}, {height},"));
+    len.extend([color_depth.clone()]);
+    let mut data_type = tokens("u8;");
+    data_type.extend([path_to(krate, "Image::raw_len"), group(Delimiter::Parenthesis, len)]);
+    body.extend(tokens("#[allow(long_running_const_eval)] static DATA:"));
+    body.extend([group(Delimiter::Bracket, data_type), punct('=')]);
+    let mut args = tokens("RGB,");
+    args.extend([color_depth.clone()]);
+    body.extend([path_to(krate, "Image::convert_rgb"), group(Delimiter::Parenthesis, args), punct(';')]);
+    let mut fields = tokens(&format!("width: {width}, height: {height}, color_depth:"));
+    fields.extend([color_depth.clone(), tokens(", data: &DATA")]);
+    body.extend([path_to(krate, "Image"), group(Delimiter::Brace, fields)]);
+    Ok(group(Delimiter::Brace, body))
+}
+
+/// Arguments separated by top-level commas.
+fn split_args(input: TokenStream) -> Vec<TokenStream> {
+    let mut args = vec![TokenStream::new()];
+    for token in input {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => args.push(TokenStream::new()),
+            token => args.last_mut().unwrap().extend([token]),
+        }
+    }
+    args
+}
+
+/// Value and span of the string literal in `tokens`, which macro_rules may have wrapped in
+/// invisible groups.
+fn string_literal(tokens: &TokenStream) -> Result<(String, Span), Error> {
+    let mut tokens = tokens.clone().into_iter();
+    let (Some(token), None) = (tokens.next(), tokens.next()) else {
+        return Err(("expected a string literal".into(), Span::call_site()));
+    };
+    match token {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => string_literal(&group.stream()),
+        TokenTree::Literal(literal) => {
+            let text = literal.to_string();
+            let value = match text.strip_prefix('"').and_then(|text| text.strip_suffix('"')) {
+                Some(value) => value.replace("\\\\", "\\").replace("\\\"", "\""),
+                None => return Err(("expected a string literal".into(), literal.span())),
+            };
+            Ok((value, literal.span()))
+        }
+        token => Err(("expected a string literal".into(), token.span())),
+    }
+}
+
+/// Absolute path of `path` taken relative to the file of the call like `include_bytes!`, or
+/// to the package when the file is not known.
+fn resolve(path: &str, span: Span) -> PathBuf {
+    let base = match span.local_file() {
+        Some(file) => file.parent().map(Path::to_path_buf).unwrap_or_default(),
+        None => PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default()),
+    };
+    let path = base.join(path);
+    std::path::absolute(&path).unwrap_or(path)
+}
+
+/// Width, height and 8-bit RGB pixels of a PNG file.
+fn decode_png(bytes: &[u8]) -> Result<(u16, u16, Vec<u8>), String> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(|error| error.to_string())?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer).map_err(|error| error.to_string())?;
+    let (Ok(width), Ok(height)) = (u16::try_from(frame.width), u16::try_from(frame.height)) else {
+        return Err("image is larger than 65535 pixels".into());
+    };
+    let pixels = &buffer[..frame.buffer_size()];
+    let rgb = match frame.color_type {
+        png::ColorType::Rgb => pixels.to_vec(),
+        png::ColorType::Rgba => pixels.chunks_exact(4).flat_map(|pixel| [pixel[0], pixel[1], pixel[2]]).collect(),
+        png::ColorType::Grayscale => pixels.iter().flat_map(|&gray| [gray; 3]).collect(),
+        png::ColorType::GrayscaleAlpha => pixels.chunks_exact(2).flat_map(|pixel| [pixel[0]; 3]).collect(),
+        png::ColorType::Indexed => return Err("palette was not expanded".into()),
+    };
+    Ok((width, height, rgb))
+}
+
+/// `krate::name!(args)`.
+fn macro_call(krate: &TokenStream, name: &str, args: TokenStream) -> TokenStream {
+    [path_to(krate, name), punct('!'), group(Delimiter::Parenthesis, args)].into_iter().collect()
+}
+
+/// `krate::path`, with `krate` as passed in, usually `$crate`.
+fn path_to(krate: &TokenStream, path: &str) -> TokenStream {
+    let mut tokens = krate.clone();
+    tokens.extend(self::tokens(&format!("::{path}")));
+    tokens
+}
+
+/// Tokens of the expansion, spanned so its constants do not clash with the caller's.
+fn tokens(source: &str) -> TokenStream {
+    respan(source.parse().unwrap(), Span::mixed_site())
+}
+
+fn punct(ch: char) -> TokenStream {
+    let mut punct = Punct::new(ch, Spacing::Alone);
+    punct.set_span(Span::mixed_site());
+    TokenTree::Punct(punct).into()
+}
+
+fn group(delimiter: Delimiter, stream: TokenStream) -> TokenStream {
+    let mut group = Group::new(delimiter, stream);
+    group.set_span(Span::mixed_site());
+    TokenTree::Group(group).into()
+}
+
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(inner) => {
+                let mut group = Group::new(inner.delimiter(), respan(inner.stream(), span));
+                group.set_span(span);
+                TokenTree::Group(group)
+            }
+            mut token => {
+                token.set_span(span);
+                token
+            }
+        })
+        .collect()
+}
+
+fn compile_error(message: &str, span: Span) -> TokenStream {
+    respan(format!("compile_error!({message:?})").parse().unwrap(), span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(width: u32, height: u32, color_type: png::ColorType, bit_depth: png::BitDepth, setup: impl FnOnce(&mut png::Encoder<&mut Vec<u8>>), data: &[u8]) -> Vec<u8> {
+        let mut file = Vec::new();
+        let mut encoder = png::Encoder::new(&mut file, width, height);
+        encoder.set_color(color_type);
+        encoder.set_depth(bit_depth);
+        setup(&mut encoder);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(data).unwrap();
+        writer.finish().unwrap();
+        file
+    }
+
+    #[test]
+    fn rgb_is_kept() {
+        let file = encode(2, 1, png::ColorType::Rgb, png::BitDepth::Eight, |_| {}, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(decode_png(&file), Ok((2, 1, vec![1, 2, 3, 4, 5, 6])));
+    }
+
+    #[test]
+    fn alpha_is_dropped() {
+        let file = encode(2, 1, png::ColorType::Rgba, png::BitDepth::Eight, |_| {}, &[1, 2, 3, 0, 4, 5, 6, 255]);
+        assert_eq!(decode_png(&file), Ok((2, 1, vec![1, 2, 3, 4, 5, 6])));
+        let file = encode(1, 1, png::ColorType::GrayscaleAlpha, png::BitDepth::Eight, |_| {}, &[7, 128]);
+        assert_eq!(decode_png(&file), Ok((1, 1, vec![7, 7, 7])));
+    }
+
+    #[test]
+    fn palettes_are_expanded() {
+        let palette = |encoder: &mut png::Encoder<&mut Vec<u8>>| encoder.set_palette(vec![10, 20, 30, 40, 50, 60]);
+        // Two bits per index, three pixels in one byte
+        let file = encode(3, 1, png::ColorType::Indexed, png::BitDepth::Two, palette, &[0b0001_0000]);
+        assert_eq!(decode_png(&file), Ok((3, 1, vec![10, 20, 30, 40, 50, 60, 10, 20, 30])));
+    }
+
+    #[test]
+    fn sixteen_bit_channels_keep_their_high_byte() {
+        let file = encode(1, 1, png::ColorType::Rgb, png::BitDepth::Sixteen, |_| {}, &[0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC]);
+        assert_eq!(decode_png(&file), Ok((1, 1, vec![0x12, 0x56, 0x9A])));
+        let file = encode(2, 1, png::ColorType::Grayscale, png::BitDepth::Sixteen, |_| {}, &[0xAB, 0xCD, 0x01, 0x02]);
+        assert_eq!(decode_png(&file), Ok((2, 1, vec![0xAB, 0xAB, 0xAB, 0x01, 0x01, 0x01])));
+    }
+
+    #[test]
+    fn corrupt_files_are_errors() {
+        let file = encode(2, 2, png::ColorType::Rgb, png::BitDepth::Eight, |_| {}, &[9; 12]);
+        assert!(decode_png(&PNG_SIGNATURE).is_err());
+        assert!(decode_png(&file[..file.len() - 20]).is_err());
+        let mut header = file.clone();
+        // Header chunk data no longer matches its CRC
+        header[16] ^= 0xFF;
+        assert!(decode_png(&header).is_err());
+    }
+}
@@ -0,0 +1,50 @@
+//! Backlight following an ambient light sensor. Readings are mapped to a duty by the
+//! application, small changes are ignored so noise on the sensor does not make the backlight
+//! shimmer, and the level moves towards the target in steps rather than jumping.
+
+use crate::{LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Change of the target duty, out of 255, below which new readings are ignored.
+const HYSTERESIS: u8 = 8;
+/// Part of the remaining distance to the target covered per reading, as a shift.
+const SMOOTHING_SHIFT: u32 = 2;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AutoBacklight {
+    lux_to_duty: fn(u16) -> u8,
+    /// Level the backlight is moving towards.
+    target: Option<u8>,
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Let [`update_ambient`](Self::update_ambient) drive the backlight, with `lux_to_duty`
+    /// mapping a sensor reading to a backlight level, 0-255. `None` stops following the
+    /// sensor and leaves the backlight where it is.
+    pub fn auto_backlight(&mut self, lux_to_duty: Option<fn(u16) -> u8>) {
+        self.auto_backlight = lux_to_duty.map(|lux_to_duty| AutoBacklight { lux_to_duty, target: None });
+    }
+
+    /// Feed an ambient light reading, e.g. every 100 ms. The backlight moves a quarter of the
+    /// way to the level for `lux` per call, so a steady reading is reached over a handful of
+    /// calls. Does nothing unless [`auto_backlight`](Self::auto_backlight) is set.
+    pub fn update_ambient(&mut self, lux: u16) -> Result<(), I::Error> {
+        let Some(auto) = &mut self.auto_backlight else {
+            return Ok(());
+        };
+        let duty = (auto.lux_to_duty)(lux);
+        let target = match auto.target {
+            // Full off and full on are always reached, however close the last target was
+            Some(target) if target.abs_diff(duty) < HYSTERESIS && duty != 0 && duty != u8::MAX => target,
+            _ => duty,
+        };
+        auto.target = Some(target);
+        let level = self.backlight_level;
+        if level == target {
+            return Ok(());
+        }
+        let step = (level.abs_diff(target) >> SMOOTHING_SHIFT).max(1);
+        let next = if target > level { level + step } else { level - step };
+        self.set_backlight(next)
+    }
+}
@@ -0,0 +1,38 @@
+//! Frame-by-frame animations from any embedded-graphics image source; GIFs are played with
+//! `play_gif` with the `gif` feature. Enabled with the `graphics` feature.
+//!
+//! ```ignore
+//! const MAP: MemoryMap = MemoryMap::new(&CONFIG).with_image(RegionKind::Canvas(0), 32, 32, CONFIG.color_depth);
+//! let frames = [(eyes_open, 3000), (eyes_closed, 150)];
+//! display.play_animation(frames, Rect::new(100, 100, 32, 32), MAP.address(RegionKind::Canvas(0)), &mut delay)?;
+//! ```
+
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_graphics_core::image::ImageDrawable;
+use embedded_graphics_core::pixelcolor::Rgb888;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Play `frames` in `rect`, each shown for its delay in milliseconds. Every frame is drawn
+    /// over the previous ones into an off-screen image at `address` and then copied into `rect`
+    /// with a single block transfer, so partial frames compose and no frame is seen half drawn.
+    /// The image takes `rect.width` by `rect.height` pixels of display memory, which must not
+    /// overlap the canvas or other images.
+    pub fn play_animation<F, D>(&mut self, frames: impl IntoIterator<Item = (F, u32)>, rect: Rect, address: u32, delay: &mut D) -> Result<(), CheckedError<I::Error>>
+    where
+        F: ImageDrawable<Color = Rgb888>,
+        D: DelayNs,
+    {
+        if rect.is_empty() {
+            return Ok(());
+        }
+        let stride = rect.width.next_multiple_of(4);
+        for (frame, delay_ms) in frames {
+            self.draw_offscreen(address, stride, rect.height, |display| frame.draw(display))?;
+            self.bte_copy((address, stride), 0, 0, rect.x, rect.y, rect.width, rect.height, None)?;
+            delay.delay_ms(delay_ms);
+        }
+        Ok(())
+    }
+}
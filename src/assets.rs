@@ -0,0 +1,347 @@
+//! Images, fonts and animations in the serial flash, found by id through a table of contents
+//! written with them, so application code does not carry flash offsets of its own.
+//!
+//! The table of contents is an 8-byte header followed by one 16-byte entry per asset, all
+//! numbers little-endian:
+//!
+//! | Offset | Content |
+//! |--------|---------|
+//! | 0 | `LTA1` |
+//! | 4 | Number of entries, u16 |
+//! | 6 | Reserved, 0 |
+//! | 8 | Entries |
+//!
+//! | Entry offset | Content |
+//! |--------------|---------|
+//! | 0 | Id, u16 |
+//! | 2 | Kind: 0 = image, 1 = font, 2 = animation |
+//! | 3 | Color depth of images and animations, as [`ColorDepth`]; 0 for fonts |
+//! | 4 | Width in pixels, u16 |
+//! | 6 | Height in pixels of an image or one animation frame, u16 |
+//! | 8 | Start of the data, relative to the table of contents, u32 |
+//! | 12 | Length of the data in bytes, u32 |
+//!
+//! Images are stored row by row without padding, animations as their frames one after another,
+//! fonts in the packed format of [`load_flash_font`](LT7683::load_flash_font).
+
+use crate::{CheckedError, ColorDepth, LT7683, LT7683Interface, Rect, UserFont, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Start of a table of contents.
+const TOC_MAGIC: [u8; 4] = *b"LTA1";
+const TOC_HEADER_LEN: u16 = 8;
+const TOC_ENTRY_LEN: u16 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    Image,
+    Font,
+    /// Frames of equal size, stored one after another.
+    Animation,
+}
+
+/// An asset listed in the table of contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Asset {
+    pub id: u16,
+    pub kind: AssetKind,
+    /// Color depth the pixels are stored at; `None` for fonts.
+    pub color_depth: Option<ColorDepth>,
+    pub width: u16,
+    /// Height of the image or of one animation frame.
+    pub height: u16,
+    /// Flash address of the data.
+    pub address: u32,
+    pub bytes: u32,
+}
+
+impl Asset {
+    /// Bytes of one image or animation frame.
+    const fn frame_bytes(&self) -> u32 {
+        let bytes_per_pixel = match self.color_depth {
+            Some(color_depth) => color_depth.bytes_per_pixel() as u32,
+            None => 0,
+        };
+        self.width as u32 * self.height as u32 * bytes_per_pixel
+    }
+
+    /// Number of frames of an animation, 1 for images and 0 for fonts.
+    pub const fn frames(&self) -> u32 {
+        match self.bytes.checked_div(self.frame_bytes()) {
+            Some(frames) => frames,
+            None => 0,
+        }
+    }
+}
+
+/// Up to `N` assets read from the table of contents in the serial flash on
+/// `config.flash_target`.
+#[derive(Debug, Clone)]
+pub struct AssetStore<const N: usize> {
+    assets: [Option<Asset>; N],
+    len: usize,
+}
+
+impl<const N: usize> AssetStore<N> {
+    /// Read the table of contents at flash `address`. Entries pass through 16 bytes of display
+    /// memory at `scratch_address`, which are overwritten. Entries past the `N`th, entries of
+    /// unknown kind or color depth and entries reaching past the end of the flash address
+    /// space are left out. Returns `None` if there is no valid header
+    /// at `address`.
+    pub fn load<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        display: &mut LT7683<I, RESET, W>,
+        address: u32,
+        scratch_address: u32,
+    ) -> Result<Option<Self>, CheckedError<I::Error>> {
+        Self::read(address, |address, buf| read_flash(display, address, scratch_address, buf))
+    }
+
+    /// Parse the table of contents at flash `address`, with `read` filling a buffer from a
+    /// flash address.
+    fn read<E>(address: u32, mut read: impl FnMut(u32, &mut [u8]) -> Result<(), E>) -> Result<Option<Self>, E> {
+        let mut header = [0; TOC_HEADER_LEN as usize];
+        read(address, &mut header)?;
+        if header[..4] != TOC_MAGIC {
+            return Ok(None);
+        }
+        let count = u16::from_le_bytes([header[4], header[5]]);
+        let mut store = Self { assets: [None; N], len: 0 };
+        for index in 0..count {
+            if store.len == N {
+                break;
+            }
+            let mut entry = [0; TOC_ENTRY_LEN as usize];
+            let Some(entry_address) = address.checked_add(TOC_HEADER_LEN as u32 + index as u32 * TOC_ENTRY_LEN as u32) else {
+                break;
+            };
+            read(entry_address, &mut entry)?;
+            if let Some(asset) = parse_entry(&entry, address) {
+                store.assets[store.len] = Some(asset);
+                store.len += 1;
+            }
+        }
+        Ok(Some(store))
+    }
+
+    pub fn get(&self, id: u16) -> Option<Asset> {
+        self.iter().find(|asset| asset.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Asset> + '_ {
+        self.assets[..self.len].iter().flatten().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copy image `id` to the canvas with its upper-left corner at `x`, `y`. Returns whether
+    /// it was drawn: nothing is drawn for unknown ids, other kinds of asset, or images stored
+    /// at another color depth than the canvas.
+    pub fn draw_image<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &self,
+        display: &mut LT7683<I, RESET, W>,
+        id: u16,
+        x: u16,
+        y: u16,
+    ) -> Result<bool, CheckedError<I::Error>> {
+        self.draw_frame_of(display, id, AssetKind::Image, 0, x, y)
+    }
+
+    /// Copy frame `frame` of animation `id` to the canvas with its upper-left corner at `x`,
+    /// `y`. Returns whether it was drawn, as [`draw_image`](Self::draw_image) does; frames
+    /// past the last are not drawn either.
+    pub fn draw_frame<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &self,
+        display: &mut LT7683<I, RESET, W>,
+        id: u16,
+        frame: u32,
+        x: u16,
+        y: u16,
+    ) -> Result<bool, CheckedError<I::Error>> {
+        self.draw_frame_of(display, id, AssetKind::Animation, frame, x, y)
+    }
+
+    fn draw_frame_of<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &self,
+        display: &mut LT7683<I, RESET, W>,
+        id: u16,
+        kind: AssetKind,
+        frame: u32,
+        x: u16,
+        y: u16,
+    ) -> Result<bool, CheckedError<I::Error>> {
+        let Some(asset) = self.get(id).filter(|asset| asset.kind == kind) else {
+            return Ok(false);
+        };
+        if asset.color_depth != Some(display.config.color_depth) || frame >= asset.frames() {
+            return Ok(false);
+        }
+        let address = asset.address + frame * asset.frame_bytes();
+        display.copy_from_flash(address, Rect::new(x, y, asset.width, asset.height), asset.width)?;
+        Ok(true)
+    }
+
+    /// Load font `id` into display memory at `cgram_address`, as
+    /// [`load_flash_font`](LT7683::load_flash_font) does. Returns `None` for unknown ids,
+    /// other kinds of asset and invalid fonts.
+    pub fn load_font<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &self,
+        display: &mut LT7683<I, RESET, W>,
+        id: u16,
+        cgram_address: u32,
+    ) -> Result<Option<UserFont>, CheckedError<I::Error>> {
+        match self.get(id).filter(|asset| asset.kind == AssetKind::Font) {
+            Some(asset) => display.load_flash_font(asset.address, cgram_address),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Read `buf.len()` bytes, at most 16, from flash `address` through display memory at
+/// `scratch_address`.
+fn read_flash<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+    display: &mut LT7683<I, RESET, W>,
+    address: u32,
+    scratch_address: u32,
+    buf: &mut [u8],
+) -> Result<(), CheckedError<I::Error>> {
+    let width = buf.len() as u16;
+    display.draw_offscreen_bytes(scratch_address, TOC_ENTRY_LEN, 1, |display| {
+        display.copy_from_flash(address, Rect::new(0, 0, width, 1), width)?;
+        Ok(display.read_canvas_bytes(buf)?)
+    })
+}
+
+/// Asset described by `entry` of the table of contents at `toc_address`.
+fn parse_entry(entry: &[u8; TOC_ENTRY_LEN as usize], toc_address: u32) -> Option<Asset> {
+    let kind = match entry[2] {
+        0 => AssetKind::Image,
+        1 => AssetKind::Font,
+        2 => AssetKind::Animation,
+        _ => return None,
+    };
+    let color_depth = match (kind, entry[3]) {
+        (AssetKind::Font, _) => None,
+        (_, 0) => Some(ColorDepth::Bpp8),
+        (_, 1) => Some(ColorDepth::Bpp16),
+        (_, 2) => Some(ColorDepth::Bpp24),
+        _ => return None,
+    };
+    let offset = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]);
+    let bytes = u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]);
+    // Entries reaching past the end of the address space are corrupt
+    let address = toc_address.checked_add(offset)?;
+    address.checked_add(bytes)?;
+    Some(Asset {
+        id: u16::from_le_bytes([entry[0], entry[1]]),
+        kind,
+        color_depth,
+        width: u16::from_le_bytes([entry[4], entry[5]]),
+        height: u16::from_le_bytes([entry[6], entry[7]]),
+        address,
+        bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    /// Flash address of the table of contents.
+    const TOC: u32 = 0x1000;
+
+    fn entry(id: u16, kind: u8, color_depth: u8, size: (u16, u16), offset: u32, bytes: u32) -> [u8; 16] {
+        let mut entry = [0; 16];
+        entry[..2].copy_from_slice(&id.to_le_bytes());
+        entry[2] = kind;
+        entry[3] = color_depth;
+        entry[4..6].copy_from_slice(&size.0.to_le_bytes());
+        entry[6..8].copy_from_slice(&size.1.to_le_bytes());
+        entry[8..12].copy_from_slice(&offset.to_le_bytes());
+        entry[12..].copy_from_slice(&bytes.to_le_bytes());
+        entry
+    }
+
+    fn table(count: u16, entries: &[[u8; 16]]) -> Vec<u8> {
+        let mut table = Vec::from(TOC_MAGIC);
+        table.extend_from_slice(&count.to_le_bytes());
+        table.extend_from_slice(&[0, 0]);
+        entries.iter().for_each(|entry| table.extend_from_slice(entry));
+        table
+    }
+
+    /// Read `table` as if it were stored at [`TOC`] in erased flash.
+    fn read<const N: usize>(table: &[u8]) -> Option<AssetStore<N>> {
+        AssetStore::read(TOC, |address, buf: &mut [u8]| {
+            let start = (address - TOC) as usize;
+            for (index, byte) in buf.iter_mut().enumerate() {
+                *byte = table.get(start + index).copied().unwrap_or(0xFF);
+            }
+            Ok::<_, ()>(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn entries_are_found_by_id() {
+        let store = read::<4>(&table(3, &[
+            entry(1, 0, 1, (32, 16), 0x100, 32 * 16 * 2),
+            entry(7, 1, 0, (0, 0), 0x500, 1234),
+            entry(9, 2, 2, (8, 8), 0x900, 8 * 8 * 3 * 5),
+        ]));
+        let store = store.unwrap();
+        assert_eq!(store.len(), 3);
+        let image = store.get(1).unwrap();
+        assert_eq!((image.kind, image.color_depth, image.width, image.height), (AssetKind::Image, Some(ColorDepth::Bpp16), 32, 16));
+        assert_eq!((image.address, image.frames()), (TOC + 0x100, 1));
+        let font = store.get(7).unwrap();
+        assert_eq!((font.kind, font.color_depth, font.address, font.frames()), (AssetKind::Font, None, TOC + 0x500, 0));
+        assert_eq!(store.get(9).unwrap().frames(), 5);
+        // An id missing from the table
+        assert_eq!(store.get(2), None);
+    }
+
+    #[test]
+    fn truncated_tables_keep_the_entries_present() {
+        // The header promises three entries, erased flash follows the first
+        let store = read::<4>(&table(3, &[entry(1, 0, 0, (4, 4), 0x100, 16)])).unwrap();
+        assert_eq!(store.iter().map(|asset| asset.id).collect::<Vec<_>>(), [1]);
+        // Cut in the middle of the second entry
+        let mut cut = table(2, &[entry(1, 0, 0, (4, 4), 0x100, 16), entry(2, 0, 0, (4, 4), 0x200, 16)]);
+        cut.truncate(cut.len() - 10);
+        assert_eq!(read::<4>(&cut).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn invalid_entries_are_left_out() {
+        let store = read::<4>(&table(4, &[
+            entry(1, 3, 0, (4, 4), 0x100, 16),
+            entry(2, 0, 3, (4, 4), 0x100, 16),
+            entry(3, 0, 0, (4, 4), u32::MAX, 16),
+            entry(4, 0, 0, (4, 4), 0x100, 16),
+        ]));
+        assert_eq!(store.unwrap().iter().map(|asset| asset.id).collect::<Vec<_>>(), [4]);
+    }
+
+    #[test]
+    fn stores_keep_the_first_entries_that_fit() {
+        let entries: Vec<_> = (0..5).map(|id| entry(id, 0, 0, (4, 4), 0x100, 16)).collect();
+        let store = read::<2>(&table(5, &entries)).unwrap();
+        assert_eq!(store.iter().map(|asset| asset.id).collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn tables_need_the_header() {
+        assert!(read::<4>(&[]).is_none());
+        assert!(read::<4>(b"LTA0\0\0\0\0").is_none());
+        assert!(read::<4>(&table(0, &[])).unwrap().is_empty());
+    }
+}
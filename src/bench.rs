@@ -0,0 +1,106 @@
+//! Timing of the major operations, enabled with the `bench` feature, so performance changes
+//! can be measured on the target. Without the feature the operations run untimed at no cost.
+
+#[cfg(feature = "bench")]
+use crate::WaitStrategy;
+use crate::{LT7683, LT7683Interface};
+#[cfg(feature = "bench")]
+use embedded_hal::digital::OutputPin;
+
+/// Samples kept; older ones are overwritten.
+#[cfg(feature = "bench")]
+const BENCH_SAMPLES: usize = 16;
+
+/// Free-running clock read before and after each timed operation, e.g. a microsecond timer or
+/// the cycle counter. Differences are taken with wrapping arithmetic.
+#[cfg(feature = "bench")]
+pub trait BenchClock {
+    fn now(&self) -> u32;
+}
+
+/// Kind of operation a sample was taken of.
+#[cfg(feature = "bench")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BenchOp {
+    /// [`LT7683::clear_screen`].
+    Clear,
+    /// [`LT7683::clear_area`], including the fill inside a clear.
+    Fill,
+    /// Writing pixels through the memory port, by [`LT7683::write_image`] or embedded-graphics.
+    ImageUpload,
+    /// Feeding one string to the text engine.
+    Text,
+}
+
+#[cfg(feature = "bench")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchSample {
+    pub op: BenchOp,
+    /// Clock ticks the operation took.
+    pub ticks: u32,
+}
+
+/// Clock and the most recent samples.
+#[cfg(feature = "bench")]
+#[derive(Default)]
+pub(crate) struct Bench {
+    clock: Option<&'static dyn BenchClock>,
+    samples: [Option<BenchSample>; BENCH_SAMPLES],
+    next: usize,
+}
+
+#[cfg(feature = "bench")]
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Time the following operations with `clock`, or stop timing with `None`. Samples taken
+    /// so far are kept.
+    pub fn set_bench_clock(&mut self, clock: Option<&'static dyn BenchClock>) {
+        self.bench.clock = clock;
+    }
+
+    /// The most recent samples, oldest first.
+    pub fn bench_samples(&self) -> impl Iterator<Item = BenchSample> + '_ {
+        let (newer, older) = self.bench.samples.split_at(self.bench.next);
+        older.iter().chain(newer).flatten().copied()
+    }
+
+    pub fn clear_bench_samples(&mut self) {
+        self.bench = Bench { clock: self.bench.clock, ..Bench::default() };
+    }
+}
+
+impl<I: LT7683Interface, RESET, W> LT7683<I, RESET, W> {
+    /// Run `operation`, recording how long it took as `op` when a bench clock is set.
+    #[cfg(feature = "bench")]
+    pub(crate) fn timed<R, E, F>(&mut self, op: BenchOp, operation: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Self) -> Result<R, E>,
+    {
+        let Some(clock) = self.bench.clock else {
+            return operation(self);
+        };
+        let start = clock.now();
+        let result = operation(self);
+        let ticks = clock.now().wrapping_sub(start);
+        self.bench.samples[self.bench.next] = Some(BenchSample { op, ticks });
+        self.bench.next = (self.bench.next + 1) % BENCH_SAMPLES;
+        result
+    }
+
+    #[cfg(not(feature = "bench"))]
+    pub(crate) fn timed<R, E, F>(&mut self, _op: BenchOp, operation: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut Self) -> Result<R, E>,
+    {
+        operation(self)
+    }
+}
+
+/// Stand-in for the operation kinds when timing is compiled out.
+#[cfg(not(feature = "bench"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum BenchOp {
+    Clear,
+    Fill,
+    ImageUpload,
+    Text,
+}
@@ -0,0 +1,159 @@
+//! Bounds checks on the areas handed to the chip. Areas past the canvas make the engines wrap
+//! into the next rows or into other images in display memory, so in debug builds, or with the
+//! `checked` feature, drawing calls refuse them with [`CheckedError::OutOfBounds`]. Release
+//! builds without the feature compile the checks out and leave hot drawing paths as they are.
+//!
+//! Areas are checked after clipping, so shapes the clip rectangle cuts down to the canvas are
+//! drawn. While drawing into an off-screen image, areas are checked against that image.
+//!
+//! The drawing engine primitives, lines, rectangles, ellipses, triangles and text, keep
+//! returning interface errors only and instead assert in debug builds that their area lies on
+//! the canvas. Text is checked where it starts, as the text engine wraps it within the active
+//! window and keeps its glyphs upright on the panel whatever the rotation.
+
+use crate::{LT7683, LT7683Interface, Quadrant, Rect, WaitStrategy};
+use core::fmt;
+use embedded_hal::digital::OutputPin;
+
+/// An area reaching past the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds {
+    /// The area as given, in logical coordinates.
+    pub rect: Rect,
+    /// Logical canvas size the area was checked against.
+    pub size: (u16, u16),
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Rect { x, y, width, height } = self.rect;
+        write!(f, "{width}x{height} area at {x},{y} reaches past the {}x{} canvas", self.size.0, self.size.1)
+    }
+}
+
+impl core::error::Error for OutOfBounds {}
+
+/// Error of the drawing calls whose area is checked against the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckedError<E> {
+    /// The interface failed.
+    Bus(E),
+    /// The area reached past the canvas and nothing was drawn. Only reported in debug builds
+    /// or with the `checked` feature.
+    OutOfBounds(OutOfBounds),
+    /// The memory write FIFO did not drain during a burst with
+    /// [`fifo_flow_control`](crate::DisplayConfig::fifo_flow_control), e.g. because the chip
+    /// stopped taking data.
+    FifoTimeout,
+}
+
+impl<E> From<E> for CheckedError<E> {
+    fn from(error: E) -> Self {
+        CheckedError::Bus(error)
+    }
+}
+
+impl<E> CheckedError<E> {
+    /// The interface error, if the failure was one.
+    pub fn bus_error(&self) -> Option<&E> {
+        match self {
+            CheckedError::Bus(error) => Some(error),
+            CheckedError::OutOfBounds(_) | CheckedError::FifoTimeout => None,
+        }
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for CheckedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckedError::Bus(error) => write!(f, "interface error: {error:?}"),
+            CheckedError::OutOfBounds(error) => fmt::Display::fmt(error, f),
+            CheckedError::FifoTimeout => f.write_str("memory write FIFO did not drain"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for CheckedError<E> {}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Check that the logical `rect`, moved by the origin, lies on the canvas, or on the
+    /// off-screen image being drawn into. Always available, whether or not drawing calls
+    /// check their areas.
+    pub fn check_rect(&self, rect: Rect) -> Result<(), OutOfBounds> {
+        if rect.is_empty() {
+            return Ok(());
+        }
+        let size = self.canvas_size();
+        let right = rect.x as u32 + self.origin.0 as u32 + rect.width as u32;
+        let bottom = rect.y as u32 + self.origin.1 as u32 + rect.height as u32;
+        if right > size.0 as u32 || bottom > size.1 as u32 {
+            return Err(OutOfBounds { rect, size });
+        }
+        Ok(())
+    }
+
+    /// Logical size of the image at the canvas address: the canvas, or the off-screen image
+    /// the driver switched to.
+    fn canvas_size(&self) -> (u16, u16) {
+        if self.canvas_address == self.config.framebuffer_address {
+            return self.logical_size();
+        }
+        let (width, height) = (self.canvas_image_width, self.canvas_image_height);
+        if self.rotation.is_quarter_turn() { (height, width) } else { (width, height) }
+    }
+
+    /// Refuse a drawing call on `rect`, the area left after clipping, if it reaches past the
+    /// canvas.
+    #[cfg(any(debug_assertions, feature = "checked"))]
+    pub(crate) fn check_bounds(&self, rect: Rect) -> Result<(), CheckedError<I::Error>> {
+        self.check_rect(rect).map_err(CheckedError::OutOfBounds)
+    }
+
+    #[cfg(not(any(debug_assertions, feature = "checked")))]
+    #[inline(always)]
+    pub(crate) fn check_bounds(&self, _rect: Rect) -> Result<(), CheckedError<I::Error>> {
+        Ok(())
+    }
+
+    /// Assert in debug builds that `area`, left after clipping, lies on the canvas, for the
+    /// primitives whose errors are the interface's. `None` stands for an area reaching past
+    /// the top or left edge.
+    #[track_caller]
+    #[inline(always)]
+    pub(crate) fn debug_check_bounds(&self, area: Option<Rect>) {
+        debug_assert!(
+            area.is_some_and(|area| self.check_rect(area).is_ok()),
+            "drawing area {area:?} reaches past the {}x{} canvas",
+            self.canvas_size().0,
+            self.canvas_size().1
+        );
+    }
+}
+
+/// Smallest area covering all `points`.
+pub(crate) fn span(points: &[(u16, u16)]) -> Rect {
+    let (mut left, mut top, mut right, mut bottom) = (u16::MAX, u16::MAX, 0, 0);
+    for &(x, y) in points {
+        (left, top) = (left.min(x), top.min(y));
+        (right, bottom) = (right.max(x), bottom.max(y));
+    }
+    let width = (right as u32 + 1).saturating_sub(left as u32).min(u16::MAX as u32);
+    let height = (bottom as u32 + 1).saturating_sub(top as u32).min(u16::MAX as u32);
+    Rect::new(left, top, width as u16, height as u16)
+}
+
+/// Area of the ellipse around `center` with `radii`, or of its `quadrant` only. `None` if it
+/// reaches past the top or left edge.
+pub(crate) fn ellipse_area(center: (u16, u16), radii: (u16, u16), quadrant: Option<Quadrant>) -> Option<Rect> {
+    let (left, right) = match quadrant {
+        Some(Quadrant::UpperLeft | Quadrant::BottomLeft) => (center.0.checked_sub(radii.0)?, center.0),
+        Some(Quadrant::UpperRight | Quadrant::BottomRight) => (center.0, center.0.saturating_add(radii.0)),
+        None => (center.0.checked_sub(radii.0)?, center.0.saturating_add(radii.0)),
+    };
+    let (top, bottom) = match quadrant {
+        Some(Quadrant::UpperLeft | Quadrant::UpperRight) => (center.1.checked_sub(radii.1)?, center.1),
+        Some(Quadrant::BottomLeft | Quadrant::BottomRight) => (center.1, center.1.saturating_add(radii.1)),
+        None => (center.1.checked_sub(radii.1)?, center.1.saturating_add(radii.1)),
+    };
+    Some(span(&[(left, top), (right, bottom)]))
+}
@@ -0,0 +1,161 @@
+//! Software clipping of lines, rectangles and text to a clip rectangle, so shapes reaching past
+//! it are cut off instead of wrapping around or being drawn over neighbouring content.
+
+use crate::{LT7683, LT7683Interface, Rect, TextStyle, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Clip drawing to `clip`, or stop clipping with `None`. Parts of shapes, fills, copies,
+    /// images and bitmaps outside it are left out before anything reaches the chip; text is
+    /// cut at whole characters.
+    pub fn set_clip_rect(&mut self, clip: Option<Rect>) {
+        self.clip = clip;
+    }
+
+    /// Clip to the current active window. Does nothing while the driver does not know the
+    /// active window, e.g. after `invalidate_cache`.
+    pub fn clip_to_active_window(&mut self) {
+        if let Some(window) = self.active_window {
+            self.clip = Some(window);
+        }
+    }
+
+    pub fn clip_rect(&self) -> Option<Rect> {
+        self.clip
+    }
+
+    /// The part of the logical `rect` inside the clip rectangle, `None` if nothing of it is
+    /// visible.
+    pub(crate) fn clip_area(&self, rect: Rect) -> Option<Rect> {
+        match self.clip {
+            Some(clip) => rect.intersection(&clip),
+            None => (!rect.is_empty()).then_some(rect),
+        }
+    }
+
+    /// The characters of `text` whose cells lie within the clip rectangle, and the x position
+    /// of the first. `None` if no character is visible.
+    pub(crate) fn clip_text<'t>(&self, text: &'t str, x: u16, y: u16, style: &TextStyle) -> Option<(&'t str, u16)> {
+        match self.clip {
+            Some(clip) => clip_cells(&clip, text, (x, y), (style.char_width(), style.cell_height())),
+            None => Some((text, x)),
+        }
+    }
+}
+
+/// The characters of `text`, in cells of `cell` size from `position` on, that lie entirely
+/// within `clip`, and the x position of the first.
+fn clip_cells<'t>(clip: &Rect, text: &'t str, (x, y): (u16, u16), cell: (u16, u16)) -> Option<(&'t str, u16)> {
+    let (width, height) = (cell.0 as u32, cell.1 as u32);
+    let (clip_right, clip_bottom) = (clip.x as u32 + clip.width as u32, clip.y as u32 + clip.height as u32);
+    if y < clip.y || y as u32 + height > clip_bottom || width == 0 {
+        return None;
+    }
+    let skipped = (clip.x.saturating_sub(x) as u32).div_ceil(width);
+    let start_x = x as u32 + skipped * width;
+    let visible = clip_right.saturating_sub(start_x) / width;
+    let mut chars = text.char_indices().map(|(i, _)| i).chain([text.len()]).skip(skipped as usize);
+    let start = chars.next()?;
+    let end = chars.nth((visible as usize).checked_sub(1)?).unwrap_or(text.len());
+    (end > start).then(|| (&text[start..end], start_x as u16))
+}
+
+/// Cut the line from `start` to `end` to the part inside `clip` (Cohen-Sutherland), `None` if
+/// it lies entirely outside.
+pub(crate) fn clip_line(clip: &Rect, start: (u16, u16), end: (u16, u16)) -> Option<((u16, u16), (u16, u16))> {
+    if clip.is_empty() {
+        return None;
+    }
+    let (left, top) = (clip.x as i64, clip.y as i64);
+    let (right, bottom) = (left + clip.width as i64 - 1, top + clip.height as i64 - 1);
+    let outcode = |(x, y): (i64, i64)| {
+        (x < left) as u8 | ((x > right) as u8) << 1 | ((y < top) as u8) << 2 | ((y > bottom) as u8) << 3
+    };
+    let (mut p0, mut p1) = ((start.0 as i64, start.1 as i64), (end.0 as i64, end.1 as i64));
+    loop {
+        let (code0, code1) = (outcode(p0), outcode(p1));
+        if code0 | code1 == 0 {
+            return Some(((p0.0 as u16, p0.1 as u16), (p1.0 as u16, p1.1 as u16)));
+        }
+        if code0 & code1 != 0 {
+            return None;
+        }
+        // Move the endpoint outside the clip rectangle onto the edge it crosses
+        let code = if code0 != 0 { code0 } else { code1 };
+        let ((x0, y0), (x1, y1)) = (p0, p1);
+        let point = if code & 0b1000 != 0 {
+            (x0 + (x1 - x0) * (bottom - y0) / (y1 - y0), bottom)
+        } else if code & 0b0100 != 0 {
+            (x0 + (x1 - x0) * (top - y0) / (y1 - y0), top)
+        } else if code & 0b0010 != 0 {
+            (right, y0 + (y1 - y0) * (right - x0) / (x1 - x0))
+        } else {
+            (left, y0 + (y1 - y0) * (left - x0) / (x1 - x0))
+        };
+        if code == code0 { p0 = point } else { p1 = point }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Columns and rows 10 to 20.
+    const CLIP: Rect = Rect::new(10, 10, 11, 11);
+
+    #[test]
+    fn lines_inside_are_kept() {
+        assert_eq!(clip_line(&CLIP, (12, 12), (18, 15)), Some(((12, 12), (18, 15))));
+        assert_eq!(clip_line(&CLIP, (10, 10), (20, 20)), Some(((10, 10), (20, 20))));
+    }
+
+    #[test]
+    fn lines_outside_are_dropped() {
+        assert_eq!(clip_line(&CLIP, (0, 0), (5, 30)), None);
+        assert_eq!(clip_line(&CLIP, (21, 0), (30, 30)), None);
+        // Passes the upper-left corner on the outside
+        assert_eq!(clip_line(&CLIP, (0, 15), (15, 0)), None);
+        assert_eq!(clip_line(&Rect::new(10, 10, 0, 5), (10, 10), (10, 12)), None);
+    }
+
+    #[test]
+    fn lines_crossing_one_edge_end_on_it() {
+        assert_eq!(clip_line(&CLIP, (15, 15), (30, 15)), Some(((15, 15), (20, 15))));
+        assert_eq!(clip_line(&CLIP, (0, 15), (15, 15)), Some(((10, 15), (15, 15))));
+        assert_eq!(clip_line(&CLIP, (15, 12), (15, 40)), Some(((15, 12), (15, 20))));
+        assert_eq!(clip_line(&CLIP, (12, 12), (24, 16)), Some(((12, 12), (20, 14))));
+    }
+
+    #[test]
+    fn lines_crossing_corners_are_cut_at_both_ends() {
+        assert_eq!(clip_line(&CLIP, (5, 5), (25, 25)), Some(((10, 10), (20, 20))));
+        assert_eq!(clip_line(&CLIP, (5, 16), (16, 5)), Some(((10, 11), (11, 10))));
+    }
+
+    #[test]
+    fn horizontal_and_vertical_lines_keep_their_axis() {
+        assert_eq!(clip_line(&CLIP, (0, 20), (40, 20)), Some(((10, 20), (20, 20))));
+        assert_eq!(clip_line(&CLIP, (40, 10), (0, 10)), Some(((20, 10), (10, 10))));
+        assert_eq!(clip_line(&CLIP, (12, 0), (12, 30)), Some(((12, 10), (12, 20))));
+        assert_eq!(clip_line(&CLIP, (0, 21), (40, 21)), None);
+    }
+
+    #[test]
+    fn text_loses_partial_cells_at_both_edges() {
+        let clip = Rect::new(10, 0, 25, 16);
+        // Cells start at 4, 12, 20, 28: the first and last reach past the clip rectangle
+        assert_eq!(clip_cells(&clip, "abcd", (4, 0), (8, 16)), Some(("bc", 12)));
+        assert_eq!(clip_cells(&clip, "\u{e4}\u{f6}\u{fc}\u{df}", (4, 0), (8, 16)), Some(("\u{f6}\u{fc}", 12)));
+        assert_eq!(clip_cells(&clip, "abc", (10, 0), (8, 16)), Some(("abc", 10)));
+        assert_eq!(clip_cells(&clip, "abcd", (10, 0), (8, 16)), Some(("abc", 10)));
+    }
+
+    #[test]
+    fn text_outside_is_dropped() {
+        let clip = Rect::new(10, 0, 25, 16);
+        assert_eq!(clip_cells(&clip, "abc", (30, 0), (8, 16)), None);
+        assert_eq!(clip_cells(&clip, "abc", (0, 0), (20, 16)), None);
+        assert_eq!(clip_cells(&clip, "abc", (10, 1), (8, 16)), None);
+        assert_eq!(clip_cells(&clip, "", (10, 0), (8, 16)), None);
+    }
+}
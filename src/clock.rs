@@ -0,0 +1,71 @@
+//! Changing the panel refresh rate while the display runs, e.g. to drop to a low rate while
+//! idle and save power, and back when the user interacts.
+//!
+//! The chip only takes new PLL settings by restarting all three PLLs (core, memory and pixel
+//! clock) together, so every change stops the panel output and memory for about a millisecond
+//! and may show as a flicker. Display memory keeps its contents across the pause.
+//!
+//! The driver does not know the refresh range of the panel: rates are only checked against
+//! what the PLL and the core clock can do, and keeping within the panel's datasheet limits is
+//! up to the caller.
+
+use crate::math::{PllConfig, sdram_refresh_interval, split_u16};
+use crate::registers::Register;
+use crate::{LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Reprogram the pixel clock PLL to the setting closest to `hz`, keeping the panel timing,
+    /// memory and core clocks. Returns the pixel clock reached, which is also stored in
+    /// `config`. Clocks the PLL cannot produce, and clocks above the core clock, are refused
+    /// with `None` and leave the chip untouched. `None` is also returned if the PLLs do not
+    /// lock on the new setting, with `config` left at the previous clock.
+    pub fn set_pixel_clock<D: DelayNs>(&mut self, hz: u32, delay: &mut D) -> Result<Option<u32>, I::Error> {
+        let Some(pll) = PllConfig::compute(self.config.oscillator_hz, hz) else {
+            return Ok(None);
+        };
+        let reached = pll.output_hz(self.config.oscillator_hz) as u32;
+        if reached == 0 || reached > self.config.core_clock_hz {
+            return Ok(None);
+        }
+        self.write_register(Register::Ppllc1, pll.control1())?;
+        self.write_register(Register::Ppllc2, pll.n)?;
+        self.restart_plls(delay)?;
+        if !self.wait_pll_lock(delay)? {
+            return Ok(None);
+        }
+        self.config.pixel_clock_hz = reached;
+        Ok(Some(reached))
+    }
+
+    /// Set the pixel clock for a refresh rate of `hz` frames per second with the configured
+    /// panel timing, see [`set_pixel_clock`](Self::set_pixel_clock). Returns the refresh rate
+    /// reached in millihertz.
+    pub fn set_refresh_rate<D: DelayNs>(&mut self, hz: u32, delay: &mut D) -> Result<Option<u32>, I::Error> {
+        let pixel_clock = (hz as u64 * self.config.frame_pixels()).min(u32::MAX as u64) as u32;
+        Ok(self.set_pixel_clock(pixel_clock, delay)?.map(|_| self.config.refresh_rate_millihz()))
+    }
+
+    /// Reprogram the memory clock PLL to the setting closest to `hz` and the SDRAM refresh
+    /// interval to match. Returns the memory clock reached, or `None` if the PLL cannot
+    /// produce it or the PLLs do not lock, leaving the refresh interval and `config` as they
+    /// were.
+    pub(crate) fn set_memory_clock<D: DelayNs>(&mut self, hz: u32, delay: &mut D) -> Result<Option<u32>, I::Error> {
+        let Some(pll) = PllConfig::compute(self.config.oscillator_hz, hz) else {
+            return Ok(None);
+        };
+        let reached = pll.output_hz(self.config.oscillator_hz) as u32;
+        self.write_register(Register::Mpllc1, pll.control1())?;
+        self.write_register(Register::Mpllc2, pll.n)?;
+        self.restart_plls(delay)?;
+        if !self.wait_pll_lock(delay)? {
+            return Ok(None);
+        }
+        let (interval_low, interval_high) = split_u16(sdram_refresh_interval(reached));
+        self.write_register(Register::SdrRef1, interval_low)?;
+        self.write_register(Register::SdrRef2, interval_high)?;
+        self.config.memory_clock_hz = reached;
+        Ok(Some(reached))
+    }
+}
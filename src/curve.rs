@@ -0,0 +1,92 @@
+//! Bezier curves drawn as hardware lines, subdivided until each piece is flat enough to pass for
+//! a straight line.
+
+use crate::{LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Fractional bits of the coordinates used while subdividing.
+const FRACTION_BITS: u32 = 4;
+/// Largest allowed distance between a piece and its chord, in 1/16 pixels.
+const TOLERANCE: i32 = 8;
+/// Subdivision limit, at most 2^10 lines per curve.
+const MAX_DEPTH: u8 = 10;
+
+/// Cubic curve in fixed point: start, two control points, end.
+type Cubic = [(i32, i32); 4];
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Draw a quadratic Bezier curve from `start` to `end`, pulled towards `control`.
+    pub fn draw_quadratic_bezier(&mut self, start: (u16, u16), control: (u16, u16), end: (u16, u16), color: u32) -> Result<(), I::Error> {
+        let [p0, q, p3] = [start, control, end].map(fixed);
+        // The same curve as a cubic: both control points two thirds of the way to `control`
+        let c1 = (p0.0 + 2 * (q.0 - p0.0) / 3, p0.1 + 2 * (q.1 - p0.1) / 3);
+        let c2 = (p3.0 + 2 * (q.0 - p3.0) / 3, p3.1 + 2 * (q.1 - p3.1) / 3);
+        self.draw_cubic([p0, c1, c2, p3], color)
+    }
+
+    /// Draw a cubic Bezier curve from `start` to `end` with control points `control1` and
+    /// `control2`.
+    pub fn draw_cubic_bezier(
+        &mut self, start: (u16, u16), control1: (u16, u16), control2: (u16, u16), end: (u16, u16), color: u32
+    ) -> Result<(), I::Error> {
+        self.draw_cubic([start, control1, control2, end].map(fixed), color)
+    }
+
+    fn draw_cubic(&mut self, curve: Cubic, color: u32) -> Result<(), I::Error> {
+        // Pieces still to draw, the next one on top; splitting pushes the second half first
+        let mut stack = [([(0, 0); 4], 0); MAX_DEPTH as usize + 1];
+        stack[0] = (curve, 0);
+        let mut len = 1;
+        let mut previous = pixel(curve[0]);
+        let mut lines = 0;
+        while len > 0 {
+            len -= 1;
+            let (piece, depth) = stack[len];
+            if depth < MAX_DEPTH && !is_flat(&piece) {
+                let (first, second) = split(&piece);
+                stack[len] = (second, depth + 1);
+                stack[len + 1] = (first, depth + 1);
+                len += 2;
+                continue;
+            }
+            let next = pixel(piece[3]);
+            if next != previous {
+                // Alternate directions so the shared point's registers are left as they are,
+                // like draw_lines
+                let (from, to) = if lines % 2 == 0 { (previous, next) } else { (next, previous) };
+                self.draw_line(from.0, from.1, to.0, to.1, color)?;
+                lines += 1;
+                previous = next;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn fixed((x, y): (u16, u16)) -> (i32, i32) {
+    ((x as i32) << FRACTION_BITS, (y as i32) << FRACTION_BITS)
+}
+
+fn pixel((x, y): (i32, i32)) -> (u16, u16) {
+    let round = |v: i32| ((v + (1 << (FRACTION_BITS - 1))) >> FRACTION_BITS).clamp(0, u16::MAX as i32) as u16;
+    (round(x), round(y))
+}
+
+/// Whether the control points are close enough to the chord for a line to stand in for the
+/// piece (the bound of Roger Willcocks' flatness test).
+fn is_flat(&[p0, c1, c2, p3]: &Cubic) -> bool {
+    let ux = (3 * c1.0 - 2 * p0.0 - p3.0) as i64;
+    let uy = (3 * c1.1 - 2 * p0.1 - p3.1) as i64;
+    let vx = (3 * c2.0 - p0.0 - 2 * p3.0) as i64;
+    let vy = (3 * c2.1 - p0.1 - 2 * p3.1) as i64;
+    (ux * ux).max(vx * vx) + (uy * uy).max(vy * vy) <= 16 * (TOLERANCE as i64).pow(2)
+}
+
+/// Split a piece in half by de Casteljau's construction.
+fn split(&[p0, c1, c2, p3]: &Cubic) -> (Cubic, Cubic) {
+    let mid = |a: (i32, i32), b: (i32, i32)| ((a.0 + b.0) / 2, (a.1 + b.1) / 2);
+    let (a, b, c) = (mid(p0, c1), mid(c1, c2), mid(c2, p3));
+    let (d, e) = (mid(a, b), mid(b, c));
+    let f = mid(d, e);
+    ([p0, a, d, f], [f, e, c, p3])
+}
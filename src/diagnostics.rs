@@ -0,0 +1,111 @@
+//! Production-line checks and register dumps of a mounted display.
+
+use crate::registers::Register;
+use crate::{CheckedError, ColorDepth, LT7683, LT7683Interface, Rect, SDRAM_BYTES, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Bytes of display memory written and read back, at the very end of the SDRAM.
+const MEMORY_TEST_LEN: u16 = 64;
+const MEMORY_TEST_ADDRESS: u32 = SDRAM_BYTES - 2 * MEMORY_TEST_LEN as u32;
+/// Colors of the test screen bars, left to right.
+const TEST_SCREEN_COLORS: [u32; 8] =
+    [0x00FF_FFFF, 0x00FF_FF00, 0x0000_FFFF, 0x0000_FF00, 0x00FF_00FF, 0x00FF_0000, 0x0000_00FF, 0x0000_0000];
+
+/// Registers whose reads have side effects: the memory port advances the graphic cursor and the
+/// SPI data register pops the receive FIFO.
+const DUMP_SKIPPED: [Register; 2] = [Register::Mrwdp, Register::Spidr];
+
+/// Outcome of [`LT7683::self_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SelfTestReport {
+    /// Bit patterns written to a register read back unchanged.
+    pub registers_ok: bool,
+    /// The SDRAM reports ready.
+    pub sdram_ready: bool,
+    /// A test pattern written to display memory read back unchanged.
+    pub memory_ok: bool,
+    /// A rectangle filled by the drawing engine read back with the expected color.
+    pub draw_engine_ok: bool,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.registers_ok && self.sdram_ready && self.memory_ok && self.draw_engine_ok
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Check register access, display memory and the drawing engine of an initialized display,
+    /// then with `render_test_screen` draw vertical color bars over the canvas for a visual
+    /// check. The last 128 bytes of display memory are overwritten. A drawing engine that never
+    /// finishes blocks in the wait strategy rather than being reported.
+    pub fn self_test(&mut self, render_test_screen: bool) -> Result<SelfTestReport, CheckedError<I::Error>> {
+        let mut report = SelfTestReport {
+            registers_ok: self.test_registers()?,
+            sdram_ready: self.is_sdram_ready()?,
+            ..SelfTestReport::default()
+        };
+        // The expected values are raw bytes, so colors must reach the chip uncorrected
+        let correction = self.color_correction.take();
+        let pattern: [u8; MEMORY_TEST_LEN as usize] = core::array::from_fn(|i| (i as u8).wrapping_mul(37) ^ 0xA5);
+        let mut readback = [0; 2 * MEMORY_TEST_LEN as usize];
+        let result = self.draw_offscreen_bytes(MEMORY_TEST_ADDRESS, MEMORY_TEST_LEN, 2, |display| {
+            display.write_image(Rect::new(0, 0, MEMORY_TEST_LEN, 1), &pattern)?;
+            display.draw_rectangle(0, 1, MEMORY_TEST_LEN - 1, 1, 0x00FF_0000, true)?;
+            Ok(display.read_canvas_bytes(&mut readback)?)
+        });
+        self.set_color_correction(correction);
+        result?;
+        let (written, filled) = readback.split_at(MEMORY_TEST_LEN as usize);
+        let (red, _) = ColorDepth::Bpp8.pixel_bytes(0x00FF_0000);
+        report.memory_ok = written == pattern;
+        report.draw_engine_ok = filled.iter().all(|&byte| byte == red[0]);
+        if render_test_screen {
+            self.draw_test_screen()?;
+        }
+        Ok(report)
+    }
+
+    /// Read back every documented register and pass its address and value to `report`, in
+    /// address order, e.g. to compare a misbehaving unit against a known-good one. Registers
+    /// whose reads have side effects (the memory port and the SPI master data register) are
+    /// skipped.
+    pub fn dump_registers(&mut self, report: &mut impl FnMut(u8, u8)) -> Result<(), I::Error> {
+        for register in Register::ALL {
+            if DUMP_SKIPPED.contains(&register) {
+                continue;
+            }
+            report(register as u8, self.read_register(register)?);
+        }
+        Ok(())
+    }
+
+    /// Write alternating bit patterns to the flash DMA start address, which has no effect until
+    /// a transfer is started, and read them back.
+    fn test_registers(&mut self) -> Result<bool, I::Error> {
+        let registers = [Register::DmaSstr0, Register::DmaSstr1, Register::DmaSstr2, Register::DmaSstr3];
+        let mut ok = true;
+        for pattern in [0x55, 0xAA, 0x00, 0xFF] {
+            for register in registers {
+                self.write_register(register, pattern)?;
+            }
+            for register in registers {
+                ok &= self.read_register(register)? == pattern;
+            }
+        }
+        Ok(ok)
+    }
+
+    fn draw_test_screen(&mut self) -> Result<(), I::Error> {
+        let (width, height) = (self.config.width, self.config.height);
+        let bars = TEST_SCREEN_COLORS.len() as u16;
+        for (i, &color) in TEST_SCREEN_COLORS.iter().enumerate() {
+            let i = i as u16;
+            let (left, right) = (width * i / bars, width * (i + 1) / bars);
+            if right > left {
+                self.draw_rectangle(left, 0, right - 1, height - 1, color, true)?;
+            }
+        }
+        Ok(())
+    }
+}
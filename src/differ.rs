@@ -0,0 +1,104 @@
+//! Uploads of frames rendered in host memory that send only what changed since the last
+//! upload, for applications that draw into a local framebuffer and flush it to the chip.
+
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Copy of the last flushed frame, compared against each new frame so only the changed spans
+/// of each row go over the bus.
+///
+/// Frames are raw pixels in memory port byte order at the configured color depth, row by row,
+/// `width` pixels per row, as [`write_image`](LT7683::write_image) takes them.
+#[derive(Debug)]
+pub struct FrameDiffer<'a> {
+    previous: &'a mut [u8],
+    width: u16,
+    height: u16,
+    /// Unchanged pixels between two changed spans of a row up to which the spans are sent as
+    /// one, saving the setup of a second transfer.
+    pub merge_gap: u16,
+    /// The screen does not match `previous`, so the next flush sends everything.
+    stale: bool,
+}
+
+impl<'a> FrameDiffer<'a> {
+    /// Differ for `width` by `height` pixel frames, keeping the last flushed frame in
+    /// `previous`, which takes as many bytes as a frame. Rows `previous` has no room for are
+    /// sent whole on every flush. The first flush sends the whole frame.
+    pub fn new(previous: &'a mut [u8], width: u16, height: u16) -> Self {
+        Self { previous, width, height, merge_gap: 8, stale: true }
+    }
+
+    /// Send the whole frame on the next flush, e.g. after drawing over it on the chip.
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    /// Upload the parts of `frame` that differ from the last flushed frame, with its upper-left
+    /// corner at `x`, `y`. Returns the number of bytes sent.
+    pub fn flush<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &mut self,
+        display: &mut LT7683<I, RESET, W>,
+        x: u16,
+        y: u16,
+        frame: &[u8],
+    ) -> Result<usize, CheckedError<I::Error>> {
+        let bytes_per_pixel = display.config.color_depth.bytes_per_pixel() as usize;
+        let row_bytes = self.width as usize * bytes_per_pixel;
+        if row_bytes == 0 {
+            return Ok(0);
+        }
+        // Until the flush completes the screen matches neither frame
+        let stale = core::mem::replace(&mut self.stale, true);
+        let mut sent = 0;
+        for (row, new) in frame.chunks_exact(row_bytes).take(self.height as usize).enumerate() {
+            let row_y = y + row as u16;
+            let Some(old) = self.previous.get_mut(row * row_bytes..(row + 1) * row_bytes) else {
+                display.write_image(Rect::new(x, row_y, self.width, 1), new)?;
+                sent += row_bytes;
+                continue;
+            };
+            if stale {
+                display.write_image(Rect::new(x, row_y, self.width, 1), new)?;
+                old.copy_from_slice(new);
+                sent += row_bytes;
+                continue;
+            }
+            if old == new {
+                continue;
+            }
+            let differs = |pixel: usize| {
+                let range = pixel * bytes_per_pixel..(pixel + 1) * bytes_per_pixel;
+                new[range.clone()] != old[range]
+            };
+            let width = self.width as usize;
+            let mut pixel = 0;
+            loop {
+                // Start of the next changed span
+                while pixel < width && !differs(pixel) {
+                    pixel += 1;
+                }
+                if pixel == width {
+                    break;
+                }
+                let start = pixel;
+                let mut end = pixel + 1;
+                // Extend over unchanged runs shorter than the merge gap
+                pixel = end;
+                while pixel < width && pixel - end < self.merge_gap as usize {
+                    if differs(pixel) {
+                        end = pixel + 1;
+                    }
+                    pixel += 1;
+                }
+                let bytes = &new[start * bytes_per_pixel..end * bytes_per_pixel];
+                display.write_image(Rect::new(x + start as u16, row_y, (end - start) as u16, 1), bytes)?;
+                sent += bytes.len();
+                pixel = end;
+            }
+            old.copy_from_slice(new);
+        }
+        self.stale = false;
+        Ok(sent)
+    }
+}
@@ -0,0 +1,50 @@
+//! Cross-fades between full-canvas images by BTE alpha blending, so screen changes are
+//! smoothed without the MCU touching pixels.
+
+use crate::registers::Register;
+use crate::{CheckedError, LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Alpha level showing only source 0; levels are in 1/32 steps.
+const ALPHA_OPAQUE: u8 = 32;
+/// BTE_CTRL1 operation: memory copy with opacity.
+const BTE_BLEND: u8 = 0x0A;
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Fade the canvas from the image at `from` to the image at `to` in `steps` steps, one
+    /// per vertical sync, ending with `to` shown in full. Both images are canvas-sized at the
+    /// configured color depth and must not be the canvas itself, e.g. regions of a
+    /// [`MemoryMap`](crate::MemoryMap). The blending engine has 32 alpha levels, so more
+    /// steps than that repeat levels.
+    pub fn crossfade(&mut self, from: u32, to: u32, steps: u16) -> Result<(), CheckedError<I::Error>> {
+        let steps = steps.max(1) as u32;
+        for step in 1..=steps {
+            let alpha = (ALPHA_OPAQUE as u32 * step / steps) as u8;
+            self.wait_vsync()?;
+            self.blend_images(to, from, alpha)?;
+        }
+        Ok(())
+    }
+
+    /// Write the canvas-sized image at `source0` at `alpha`/32 opacity over the one at
+    /// `source1` into the canvas.
+    fn blend_images(&mut self, source0: u32, source1: u32, alpha: u8) -> Result<(), CheckedError<I::Error>> {
+        let width = self.config.canvas_width();
+        let height = self.config.canvas_height();
+        self.write_register(Register::S1Str0, source1 as u8)?;
+        self.write_register(Register::S1Str1, (source1 >> 8) as u8)?;
+        self.write_register(Register::S1Str2, (source1 >> 16) as u8)?;
+        self.write_register(Register::S1Str3, (source1 >> 24) as u8)?;
+        self.write_register(Register::S1Wth0, width as u8)?;
+        self.write_register(Register::S1Wth1, (width >> 8) as u8)?;
+        self.write_register(Register::S1X0, 0)?;
+        self.write_register(Register::S1X1, 0)?;
+        self.write_register(Register::S1Y0, 0)?;
+        self.write_register(Register::S1Y1, 0)?;
+        self.write_register(Register::Apbctrl, alpha.min(ALPHA_OPAQUE))?;
+        // Whole images are blended, wherever the origin and rotation point
+        self.with_physical_coordinates(|display| {
+            display.bte_from_source((source0, width), 0, 0, 0, 0, width, height, None, (BTE_BLEND, BTE_BLEND))
+        })
+    }
+}
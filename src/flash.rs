@@ -0,0 +1,197 @@
+//! DMA transfers from the serial flash on the chip's SPI master interface into display memory,
+//! without the data passing through the host bus.
+
+use crate::raw::{dma_ctrl, sfl_ctrl};
+use crate::registers::Register;
+use crate::{CheckedError, ColorDepth, LT7683, LT7683Interface, Rect, WaitEvent, WaitStrategy};
+use crate::text::WIDTH_TABLE_CHARS;
+use crate::{FontSize, FontWidths, UserFont, WidthTable};
+use embedded_hal::digital::OutputPin;
+
+/// Highest serial clock used for normal reads (0x03), within the limit of common flash chips.
+const SERIAL_FLASH_HZ: u32 = 25_000_000;
+/// Highest serial clock used for reads with dummy cycles, which flash chips take faster.
+const FAST_READ_FLASH_HZ: u32 = 50_000_000;
+
+/// Read command the SPI master uses for flash DMA. Multi-line reads move two bits per clock
+/// over the data lines and only work with flash chips that support them, wired with both
+/// data lines to the chip.
+///
+/// There is no quad mode: the SPI master only drives one or two data lines, so quad read
+/// commands (0x6B, 0xEB) cannot be used even with flash chips that support them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlashReadMode {
+    /// Normal read, command 0x03, understood by every SPI flash.
+    #[default]
+    Normal = 0x00,
+    /// Fast read, command 0x0B with 8 dummy cycles.
+    Fast = 0x04,
+    /// Fast read, command 0x1B with 16 dummy cycles.
+    FastExtraDummy = 0x08,
+    /// Dual output read, command 0x3B: address on one line, data on two.
+    Dual = 0x02,
+    /// Dual I/O read, command 0xBB: address and data on two lines.
+    DualIo = 0x03,
+}
+
+impl FlashReadMode {
+    /// Highest serial clock for the mode.
+    const fn max_clock_hz(self) -> u32 {
+        match self {
+            FlashReadMode::Normal => SERIAL_FLASH_HZ,
+            _ => FAST_READ_FLASH_HZ,
+        }
+    }
+}
+
+/// Serial device on one of the two chip selects of the SPI master interface, e.g. an image
+/// flash on SCS0 and a font ROM on SCS1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlashTarget {
+    #[default]
+    Cs0,
+    Cs1,
+}
+
+/// Start of a packed font header.
+const FONT_MAGIC: [u8; 4] = *b"LTF1";
+const FONT_HEADER_LEN: u16 = 8;
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Copy a block of pixels from the serial flash on `config.flash_target` into `rect` of the
+    /// canvas. The image is stored row by row at the configured color depth starting at
+    /// `address`, `source_width` pixels per row; `rect` selects its upper-left `rect.width` by
+    /// `rect.height` pixels.
+    pub fn copy_from_flash(&mut self, address: u32, rect: Rect, source_width: u16) -> Result<(), CheckedError<I::Error>> {
+        self.copy_from_flash_on(self.config.flash_target, address, rect, source_width)
+    }
+
+    /// Like [`copy_from_flash`](Self::copy_from_flash), from the device on `target`.
+    pub fn copy_from_flash_on(&mut self, target: FlashTarget, address: u32, rect: Rect, source_width: u16) -> Result<(), CheckedError<I::Error>> {
+        let Some(visible) = self.clip_area(rect) else {
+            return Ok(());
+        };
+        self.check_bounds(visible)?;
+        // The image is copied as stored, into the canvas area under the logical `rect`, so
+        // clipping moves the start of the copy by the clipped-off part on the canvas
+        let full = self.rect_to_physical(rect);
+        let Rect { x, y, width, height } = self.rect_to_physical(visible);
+        let skipped = y.saturating_sub(full.y) as u32 * source_width as u32 + x.saturating_sub(full.x) as u32;
+        let address = address.wrapping_add(skipped * self.config.color_depth.bytes_per_pixel() as u32);
+        // DMA mode and the configured read command, with 32-bit addresses only where 24 bits do
+        // not reach
+        let read_mode = self.config.flash_read_mode;
+        let address_bits = if address > 0x00FF_FFFF { sfl_ctrl::ADDRESS_32_BIT } else { 0x00 };
+        self.select_flash(target, sfl_ctrl::DMA_MODE | address_bits, read_mode)?;
+        self.write_register(Register::DmaSstr0, address as u8)?;
+        self.write_register(Register::DmaSstr1, (address >> 8) as u8)?;
+        self.write_register(Register::DmaSstr2, (address >> 16) as u8)?;
+        self.write_register(Register::DmaSstr3, (address >> 24) as u8)?;
+        self.write_register(Register::DmaDx0, x as u8)?;
+        self.write_register(Register::DmaDx1, (x >> 8) as u8)?;
+        self.write_register(Register::DmaDy0, y as u8)?;
+        self.write_register(Register::DmaDy1, (y >> 8) as u8)?;
+        self.write_register(Register::DmaDwr0, width as u8)?;
+        self.write_register(Register::DmaDwr1, (width >> 8) as u8)?;
+        self.write_register(Register::DmaDhr0, height as u8)?;
+        self.write_register(Register::DmaDhr1, (height >> 8) as u8)?;
+        self.write_register(Register::DmaSwr0, source_width as u8)?;
+        self.write_register(Register::DmaSwr1, (source_width >> 8) as u8)?;
+        self.write_register(Register::DmaCtrl, dma_ctrl::START)?;
+        Ok(self.wait_strategy.wait(&mut self.ll.interface, WaitEvent::Dma)?)
+    }
+
+    /// Load a packed font from the serial flash into display memory at `cgram_address`, for
+    /// use with [`Charset::User`](crate::Charset::User). The font at `address` is an 8-byte
+    /// header followed by the glyphs:
+    ///
+    /// | Offset | Content |
+    /// |--------|---------|
+    /// | 0 | `LTF1` |
+    /// | 4 | Cell size: 0 = 8x16, 1 = 12x24, 2 = 16x32 |
+    /// | 5 | Character code of the first glyph |
+    /// | 6 | Number of glyphs, little-endian u16 |
+    /// | 8 | Glyphs in code order, rows MSB first and padded to whole bytes |
+    ///
+    /// Glyphs are indexed by character code, so up to 256 glyphs' worth of display memory from
+    /// `cgram_address` is used. Returns `None` if there is no valid header at `address`.
+    pub fn load_flash_font(&mut self, address: u32, cgram_address: u32) -> Result<Option<UserFont>, CheckedError<I::Error>> {
+        let mut header = [0; FONT_HEADER_LEN as usize];
+        self.draw_offscreen_bytes(cgram_address, FONT_HEADER_LEN, 1, |display| {
+            display.copy_from_flash(address, Rect::new(0, 0, FONT_HEADER_LEN, 1), FONT_HEADER_LEN)?;
+            Ok(display.read_canvas_bytes(&mut header)?)
+        })?;
+        if header[..4] != FONT_MAGIC {
+            return Ok(None);
+        }
+        let font_size = match header[4] {
+            0 => FontSize::Size8x16,
+            1 => FontSize::Size12x24,
+            2 => FontSize::Size16x32,
+            _ => return Ok(None),
+        };
+        let first_code = header[5];
+        let glyph_count = u16::from_le_bytes([header[6], header[7]]).min(256 - first_code as u16);
+        // One glyph per row of an 8 bpp image, so row n holds the glyph for code n
+        let glyph_bytes = font_size.glyph_bytes();
+        let glyphs = Rect::new(0, first_code as u16, glyph_bytes, glyph_count);
+        self.draw_offscreen_bytes(cgram_address, glyph_bytes, 256, |display| {
+            display.copy_from_flash(address + FONT_HEADER_LEN as u32, glyphs, glyph_bytes)
+        })?;
+        Ok(Some(UserFont { font_size, address: cgram_address, first_code, glyph_count }))
+    }
+
+    /// Read the widths of a proportional font ROM's ASCII glyphs from `table`, for
+    /// [`set_font_widths`](Self::set_font_widths). The bytes pass through 380 bytes of display
+    /// memory at `scratch_address`, which are overwritten.
+    pub fn read_font_widths(&mut self, table: WidthTable, scratch_address: u32) -> Result<FontWidths, CheckedError<I::Error>> {
+        // One width per row of a 4 pixel wide 8 bpp image, the narrowest image there is
+        const ROW: usize = 4;
+        let mut rows = [0; ROW * WIDTH_TABLE_CHARS];
+        let widths = Rect::new(0, 0, 1, WIDTH_TABLE_CHARS as u16);
+        self.draw_offscreen_bytes(scratch_address, ROW as u16, WIDTH_TABLE_CHARS as u16, |display| {
+            display.copy_from_flash_on(table.chip_select, table.address, widths, table.stride)?;
+            Ok(display.read_canvas_bytes(&mut rows)?)
+        })?;
+        Ok(FontWidths { widths: core::array::from_fn(|index| rows[index * ROW]) })
+    }
+
+    /// Point the serial flash interface at `target` for DMA or, without
+    /// [`sfl_ctrl::DMA_MODE`] in `mode_bits`, for font ROM access by the text engine, and set
+    /// the serial clock for `read_mode`.
+    pub(crate) fn select_flash(&mut self, target: FlashTarget, mode_bits: u8, read_mode: FlashReadMode) -> Result<(), I::Error> {
+        let chip_select = match target {
+            FlashTarget::Cs0 => 0x00,
+            FlashTarget::Cs1 => sfl_ctrl::CHIP_SELECT_1,
+        };
+        self.write_register(Register::SflCtrl, chip_select | mode_bits | read_mode as u8)?;
+        // Serial clock = core clock / (2 * (divisor + 1))
+        let divisor = self.config.core_clock_hz.div_ceil(2 * read_mode.max_clock_hz()).clamp(1, 256) - 1;
+        self.write_register(Register::SpiDivsor, divisor as u8)
+    }
+
+    /// Like `draw_offscreen`, with the off-screen image at 8 bpp so pixels are plain bytes.
+    pub(crate) fn draw_offscreen_bytes<F>(&mut self, address: u32, width: u16, height: u16, draw: F) -> Result<(), CheckedError<I::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), CheckedError<I::Error>>,
+    {
+        self.write_register(Register::AwColor, ColorDepth::Bpp8 as u8)?;
+        let result = self.draw_offscreen(address, width, height, draw);
+        self.write_register(Register::AwColor, self.config.color_depth as u8)?;
+        result
+    }
+
+    /// Read `buf.len()` bytes of an 8 bpp canvas from its upper-left corner on.
+    pub(crate) fn read_canvas_bytes(&mut self, buf: &mut [u8]) -> Result<(), I::Error> {
+        self.enter_graphic_mode()?;
+        self.set_graphic_cursor(0, 0)?;
+        self.ll.write_command(Register::Mrwdp)?;
+        // The first read after moving the cursor returns stale data
+        self.ll.read_data()?;
+        for byte in buf {
+            *byte = self.ll.read_data()?;
+        }
+        self.graphic_cursor = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+//! Software rendering of user-supplied bitmap fonts, for glyphs the text engine cannot provide.
+
+use crate::{CheckedError, ColorDepth, LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Monospaced bitmap font, laid out like u8g2/BDF glyph tables: glyphs are stored back to back
+/// in character order, each row MSB first and padded to whole bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct BitmapFont<'a> {
+    /// Glyph width in pixels, which is also the advance between characters.
+    pub width: u16,
+    pub height: u16,
+    /// Character of the first glyph in `data`.
+    pub first_char: char,
+    pub data: &'a [u8],
+}
+
+impl<'a> BitmapFont<'a> {
+    pub const fn new(width: u16, height: u16, first_char: char, data: &'a [u8]) -> Self {
+        Self { width, height, first_char, data }
+    }
+
+    pub const fn bytes_per_row(&self) -> usize {
+        (self.width as usize).div_ceil(8)
+    }
+
+    pub const fn glyph_size(&self) -> usize {
+        self.bytes_per_row() * self.height as usize
+    }
+
+    /// Bitmap of `c`, if the font contains it.
+    pub fn glyph(&self, c: char) -> Option<&'a [u8]> {
+        let index = (c as u32).checked_sub(self.first_char as u32)? as usize;
+        let start = index.checked_mul(self.glyph_size())?;
+        self.data.get(start..start + self.glyph_size())
+    }
+}
+
+/// Off-screen display memory holding up to `N` rendered glyphs of one bitmap font, so repeated
+/// characters are drawn with a single BTE copy instead of streaming their bitmap again. Slots
+/// are reused round robin; changing the colors or the font empties the cache.
+#[derive(Debug, Clone)]
+pub struct GlyphCache<const N: usize> {
+    address: u32,
+    slots: [Option<char>; N],
+    next: usize,
+    /// Glyph size and (foreground, background or chroma key) colors of the cached glyphs.
+    rendered: Option<((u16, u16), u32, u32)>,
+}
+
+impl<const N: usize> GlyphCache<N> {
+    /// Cache at `address` in display memory, which must not overlap the canvas or other
+    /// images; it takes [`buffer_bytes`](Self::buffer_bytes).
+    pub const fn new(address: u32) -> Self {
+        Self { address, slots: [None; N], next: 0, rendered: None }
+    }
+
+    /// Display memory needed for the glyphs of `font` at `color_depth`.
+    pub const fn buffer_bytes(font: &BitmapFont, color_depth: ColorDepth) -> u32 {
+        Self::image_width(font) as u32 * font.height as u32 * N as u32 * color_depth.bytes_per_pixel() as u32
+    }
+
+    pub fn clear(&mut self) {
+        self.slots = [None; N];
+        self.next = 0;
+        self.rendered = None;
+    }
+
+    /// Glyphs are stacked in an image one glyph wide, rounded up as images must be.
+    const fn image_width(font: &BitmapFont) -> u16 {
+        font.width.next_multiple_of(4)
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Draw `text` with a bitmap font, streaming each glyph through BTE color expansion.
+    /// Without a background color only the glyph pixels are drawn. Characters missing from the
+    /// font are drawn as `?` if the font has it, and skipped otherwise.
+    pub fn draw_bitmap_text(&mut self, text: &str, x: u16, y: u16, font: &BitmapFont, fg_color: u32, bg_color: Option<u32>) -> Result<(), CheckedError<I::Error>> {
+        let mut x = x;
+        for c in text.chars() {
+            let Some(glyph) = font.glyph(c).or_else(|| font.glyph('?')) else {
+                continue;
+            };
+            self.draw_mono_bitmap(x, y, font.width, font.height, glyph, fg_color, bg_color)?;
+            x = x.saturating_add(font.width);
+        }
+        Ok(())
+    }
+
+    /// Like [`draw_bitmap_text`](Self::draw_bitmap_text), rendering each glyph once into
+    /// `cache` and copying it from there. Without a background color, glyphs are rendered on a
+    /// chroma key color and copied with chroma keying.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_bitmap_text_cached<const N: usize>(
+        &mut self, text: &str, x: u16, y: u16, font: &BitmapFont,
+        fg_color: u32, bg_color: Option<u32>, cache: &mut GlyphCache<N>
+    ) -> Result<(), CheckedError<I::Error>> {
+        if N == 0 {
+            return self.draw_bitmap_text(text, x, y, font, fg_color, bg_color);
+        }
+        let mask = self.config.color_depth.color_mask();
+        // Any color other than the foreground works as key
+        let background = bg_color.unwrap_or(!fg_color) & mask;
+        let rendered = Some(((font.width, font.height), fg_color & mask, background));
+        if cache.rendered != rendered {
+            cache.clear();
+            cache.rendered = rendered;
+        }
+        let chroma_key = if bg_color.is_none() { Some(background) } else { None };
+        let image_width = GlyphCache::<N>::image_width(font);
+        let mut x = x;
+        for c in text.chars() {
+            let c = if font.glyph(c).is_some() { c } else { '?' };
+            let Some(glyph) = font.glyph(c) else {
+                continue;
+            };
+            let slot = match cache.slots.iter().position(|&slot| slot == Some(c)) {
+                Some(slot) => slot,
+                None => {
+                    let slot = cache.next;
+                    cache.next = (slot + 1) % N;
+                    let slot_y = slot as u16 * font.height;
+                    self.draw_offscreen(cache.address, image_width, N as u16 * font.height, |display| {
+                        display.begin_bte_color_expansion(0, slot_y, font.width, font.height, fg_color, Some(background))?;
+                        for &byte in glyph {
+                            display.ll.write_data(byte)?;
+                        }
+                        Ok(display.wait_bte_complete()?)
+                    })?;
+                    cache.slots[slot] = Some(c);
+                    slot
+                }
+            };
+            let source = (cache.address, image_width);
+            self.bte_copy(source, 0, slot as u16 * font.height, x, y, font.width, font.height, chroma_key)?;
+            x = x.saturating_add(font.width);
+        }
+        Ok(())
+    }
+}
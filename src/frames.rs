@@ -0,0 +1,62 @@
+//! Counters of vertical syncs and presented frames, to measure how drawing strategies affect
+//! the frame rate on real hardware.
+
+use crate::raw::interrupt;
+use crate::registers::Register;
+use crate::{LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Vertical syncs and frames counted since [`LT7683::enable_frame_counters`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FrameCounters {
+    /// Vertical syncs seen by [`LT7683::wait_vsync`] and [`LT7683::poll_vsync`].
+    pub vsyncs: u32,
+    /// Frames marked with [`LT7683::frame_presented`].
+    pub frames: u32,
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Start counting from zero, or stop counting with `enable` false.
+    pub fn enable_frame_counters(&mut self, enable: bool) {
+        self.frame_counters = enable.then(FrameCounters::default);
+    }
+
+    /// Counts so far, or `None` while counting is off.
+    pub fn frame_counters(&self) -> Option<FrameCounters> {
+        self.frame_counters
+    }
+
+    /// Mark a frame as finished, e.g. after the last drawing call of a screen update.
+    pub fn frame_presented(&mut self) {
+        if let Some(counters) = &mut self.frame_counters {
+            counters.frames = counters.frames.wrapping_add(1);
+        }
+    }
+
+    /// Check for a vertical sync since the last check without waiting, counting it if there
+    /// was one. Only one sync is flagged at a time, so for accurate counts call this at least
+    /// once per frame, e.g. from the INT pin's interrupt handler.
+    pub fn poll_vsync(&mut self) -> Result<bool, I::Error> {
+        if self.read_register(Register::Intf)? & interrupt::VSYNC == 0 {
+            return Ok(false);
+        }
+        self.write_register(Register::Intf, interrupt::VSYNC)?;
+        self.count_vsync();
+        Ok(true)
+    }
+
+    pub(crate) fn count_vsync(&mut self) {
+        if let Some(counters) = &mut self.frame_counters {
+            counters.vsyncs = counters.vsyncs.wrapping_add(1);
+        }
+    }
+
+    /// Presented frames per second, from the frames per counted vertical sync and the
+    /// refresh rate of the configured panel timing. `None` until a vertical sync has been
+    /// counted.
+    pub fn fps_estimate(&self) -> Option<f32> {
+        let counters = self.frame_counters.filter(|counters| counters.vsyncs > 0)?;
+        let refresh_hz = self.config.refresh_rate_millihz() as f32 / 1000.0;
+        Some(counters.frames as f32 * refresh_hz / counters.vsyncs as f32)
+    }
+}
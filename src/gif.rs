@@ -0,0 +1,629 @@
+//! GIF decoding for [`play_animation`](LT7683::play_animation). Enabled with the `gif` feature.
+//!
+//! Frames are decoded from the file while they are drawn, so there is no heap and no frame
+//! buffer on the host; the decoder tables take about 12 KiB of stack while a frame is drawn.
+//! Each decoded string is drawn from its last pixel back to its first, which saves the stack
+//! other decoders reverse strings on.
+//!
+//! Frames that ask to be replaced by the background color once shown are; files with frames
+//! that ask to be replaced by what was there before are refused, as that would take a copy of
+//! the animation's image in display memory.
+//!
+//! ```ignore
+//! const MAP: MemoryMap = MemoryMap::new(&CONFIG).with_image(RegionKind::Canvas(0), 64, 64, CONFIG.color_depth);
+//! let gif = Gif::from_slice(include_bytes!("spinner.gif")).unwrap();
+//! display.play_gif(&gif, 100, 100, MAP.address(RegionKind::Canvas(0)), &mut delay)?;
+//! ```
+
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{OriginDimensions, Point, Size};
+use embedded_graphics_core::image::ImageDrawable;
+use embedded_graphics_core::pixelcolor::Rgb888;
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_graphics_core::Pixel;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+const HEADER_LEN: usize = 13;
+const EXTENSION: u8 = 0x21;
+const IMAGE_DESCRIPTOR: u8 = 0x2C;
+const GRAPHIC_CONTROL: u8 = 0xF9;
+/// Disposal methods of the graphic control extension.
+const DISPOSE_BACKGROUND: u8 = 2;
+const DISPOSE_PREVIOUS: u8 = 3;
+/// Codes are at most 12 bits wide.
+const MAX_CODES: usize = 4096;
+
+/// A GIF file in memory, e.g. from `include_bytes!`.
+#[derive(Debug, Clone, Copy)]
+pub struct Gif<'a> {
+    data: &'a [u8],
+    width: u16,
+    height: u16,
+    global_palette: &'a [u8],
+    background: Rgb888,
+    /// Offset of the first block after the header and global color table.
+    blocks: usize,
+}
+
+impl<'a> Gif<'a> {
+    /// Read the header of a GIF87a or GIF89a file. Returns `None` if `data` is not one, or if
+    /// one of its frames is to be replaced by what was there before once shown.
+    pub fn from_slice(data: &'a [u8]) -> Option<Self> {
+        let header = data.get(..HEADER_LEN)?;
+        if &header[..3] != b"GIF" || !matches!(&header[3..6], b"87a" | b"89a") {
+            return None;
+        }
+        let (width, height) = (u16::from_le_bytes([header[6], header[7]]), u16::from_le_bytes([header[8], header[9]]));
+        let palette_bytes = palette_bytes(header[10]);
+        let global_palette = data.get(HEADER_LEN..HEADER_LEN + palette_bytes)?;
+        let background = palette_color(global_palette, header[11]);
+        let gif = Self { data, width, height, global_palette, background, blocks: HEADER_LEN + palette_bytes };
+        if gif.frames().any(|frame| frame.disposal == DISPOSE_PREVIOUS) {
+            return None;
+        }
+        Some(gif)
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Frames in file order. Iteration ends at the trailer or at the first malformed block.
+    pub fn frames(&self) -> GifFrames<'a> {
+        GifFrames { gif: *self, position: self.blocks, clear: None }
+    }
+}
+
+/// Iterator over the frames of a [`Gif`].
+#[derive(Debug, Clone)]
+pub struct GifFrames<'a> {
+    gif: Gif<'a>,
+    position: usize,
+    /// Area of the previous frame, if it is to be replaced by the background color.
+    clear: Option<Rectangle>,
+}
+
+impl<'a> Iterator for GifFrames<'a> {
+    type Item = GifFrame<'a>;
+
+    fn next(&mut self) -> Option<GifFrame<'a>> {
+        let frame = self.parse_frame();
+        if frame.is_none() {
+            self.position = self.gif.data.len();
+        }
+        frame
+    }
+}
+
+impl<'a> GifFrames<'a> {
+    fn parse_frame(&mut self) -> Option<GifFrame<'a>> {
+        let data = self.gif.data;
+        let (mut delay_centis, mut transparent, mut disposal) = (0, None, 0);
+        loop {
+            match *data.get(self.position)? {
+                EXTENSION => {
+                    let label = *data.get(self.position + 1)?;
+                    let body = self.position + 2;
+                    if label == GRAPHIC_CONTROL {
+                        // Block size 4: flags, delay, transparent color index
+                        let control = data.get(body..body + 5)?;
+                        delay_centis = u16::from_le_bytes([control[2], control[3]]);
+                        transparent = (control[1] & 0x01 != 0).then_some(control[4]);
+                        disposal = (control[1] >> 2) & 0x07;
+                    }
+                    self.position = skip_sub_blocks(data, body)?;
+                }
+                IMAGE_DESCRIPTOR => {
+                    let descriptor = data.get(self.position + 1..self.position + 10)?;
+                    let field = |index: usize| u16::from_le_bytes([descriptor[index], descriptor[index + 1]]);
+                    let flags = descriptor[8];
+                    let mut position = self.position + 10;
+                    let palette = if flags & 0x80 != 0 {
+                        let palette = data.get(position..position + palette_bytes(flags))?;
+                        position += palette.len();
+                        palette
+                    } else {
+                        self.gif.global_palette
+                    };
+                    let min_code_size = *data.get(position)?;
+                    if !(1..=11).contains(&min_code_size) {
+                        return None;
+                    }
+                    let end = skip_sub_blocks(data, position + 1)?;
+                    self.position = end;
+                    let (left, top, width, height) = (field(0), field(2), field(4), field(6));
+                    let area = Rectangle::new(Point::new(left as i32, top as i32), Size::new(width as u32, height as u32));
+                    let clear = core::mem::replace(&mut self.clear, (disposal == DISPOSE_BACKGROUND).then_some(area));
+                    return Some(GifFrame {
+                        screen: Size::new(self.gif.width as u32, self.gif.height as u32),
+                        left,
+                        top,
+                        width,
+                        height,
+                        interlaced: flags & 0x40 != 0,
+                        palette,
+                        transparent,
+                        min_code_size,
+                        image_data: &data[position + 1..end],
+                        delay_centis,
+                        disposal,
+                        clear,
+                        background: self.gif.background,
+                    });
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// One frame of a [`Gif`], drawn at its offset within the logical screen. Pixels of the
+/// transparent color are left out, so the frames before show through. Drawing a frame first
+/// fills the area of the one before with the background color if that one asked for it.
+#[derive(Debug, Clone, Copy)]
+pub struct GifFrame<'a> {
+    screen: Size,
+    left: u16,
+    top: u16,
+    width: u16,
+    height: u16,
+    interlaced: bool,
+    palette: &'a [u8],
+    transparent: Option<u8>,
+    min_code_size: u8,
+    /// Compressed data as sub-blocks, with the terminator.
+    image_data: &'a [u8],
+    /// Time to show the frame, in hundredths of a second.
+    pub delay_centis: u16,
+    /// What becomes of the frame once shown.
+    disposal: u8,
+    /// Area to fill with `background` before drawing.
+    clear: Option<Rectangle>,
+    background: Rgb888,
+}
+
+impl GifFrame<'_> {
+    pub fn delay_ms(&self) -> u32 {
+        self.delay_centis as u32 * 10
+    }
+
+    /// Pixels of the frame on the logical screen, transparent ones left out.
+    fn pixels(&self) -> impl Iterator<Item = Pixel<Rgb888>> + '_ {
+        let width = self.width as u32;
+        let pixels = self.width as usize * self.height as usize;
+        Lzw::new(self.image_data, self.min_code_size, pixels)
+            .filter(move |&(_, index)| Some(index) != self.transparent)
+            .map(move |(n, index)| {
+                let (column, line) = (n as u32 % width, (n as u32 / width) as u16);
+                let row = if self.interlaced { interlaced_row(line, self.height) } else { line };
+                let point = Point::new((self.left as u32 + column) as i32, self.top as i32 + row as i32);
+                Pixel(point, palette_color(self.palette, index))
+            })
+    }
+}
+
+impl OriginDimensions for GifFrame<'_> {
+    fn size(&self) -> Size {
+        self.screen
+    }
+}
+
+impl ImageDrawable for GifFrame<'_> {
+    type Color = Rgb888;
+
+    fn draw<D: DrawTarget<Color = Rgb888>>(&self, target: &mut D) -> Result<(), D::Error> {
+        if let Some(clear) = self.clear {
+            target.fill_solid(&clear, self.background)?;
+        }
+        target.draw_iter(self.pixels())
+    }
+
+    fn draw_sub_image<D: DrawTarget<Color = Rgb888>>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error> {
+        let area = *area;
+        if let Some(clear) = self.clear.map(|clear| clear.intersection(&area)) {
+            target.fill_solid(&Rectangle::new(clear.top_left - area.top_left, clear.size), self.background)?;
+        }
+        let pixels = self.pixels().filter(move |pixel| area.contains(pixel.0));
+        target.draw_iter(pixels.map(move |Pixel(point, color)| Pixel(point - area.top_left, color)))
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Play `gif` once with its upper-left corner at (x, y), each frame shown for the delay
+    /// stored with it. Frames are composed off screen at `address` as with
+    /// [`play_animation`](Self::play_animation), which takes the GIF's width by height pixels
+    /// of display memory.
+    pub fn play_gif<D: DelayNs>(&mut self, gif: &Gif, x: u16, y: u16, address: u32, delay: &mut D) -> Result<(), CheckedError<I::Error>> {
+        let frames = gif.frames().map(|frame| (frame, frame.delay_ms()));
+        self.play_animation(frames, Rect::new(x, y, gif.width(), gif.height()), address, delay)
+    }
+}
+
+/// Bytes of the color table announced in a header or image descriptor `flags` byte.
+fn palette_bytes(flags: u8) -> usize {
+    if flags & 0x80 == 0 { 0 } else { 3 << ((flags & 0x07) + 1) }
+}
+
+/// Entry `index` of `palette`, black past its end.
+fn palette_color(palette: &[u8], index: u8) -> Rgb888 {
+    match palette.get(index as usize * 3..index as usize * 3 + 3) {
+        Some(&[r, g, b]) => Rgb888::new(r, g, b),
+        _ => Rgb888::new(0, 0, 0),
+    }
+}
+
+/// Offset just past the sub-blocks starting at `position` and their terminator.
+fn skip_sub_blocks(data: &[u8], mut position: usize) -> Option<usize> {
+    loop {
+        let len = *data.get(position)? as usize;
+        position += 1 + len;
+        if len == 0 {
+            return (position <= data.len()).then_some(position);
+        }
+    }
+}
+
+/// Row of the `line`th line of an interlaced image `height` rows high: every 8th row from 0,
+/// every 8th from 4, every 4th from 2, then every 2nd from 1.
+fn interlaced_row(line: u16, height: u16) -> u16 {
+    let mut line = line as u32;
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        let rows = (height as u32).saturating_sub(start).div_ceil(step);
+        if line < rows {
+            return (start + line * step) as u16;
+        }
+        line -= rows;
+    }
+    height
+}
+
+/// Color indices of a GIF's variable-width LZW stream, read from its sub-blocks, each with the
+/// number of the pixel it belongs to. Strings come out last index first.
+struct Lzw<'a> {
+    data: &'a [u8],
+    /// Offset of the next byte and bytes left in its sub-block.
+    position: usize,
+    block_left: usize,
+    bits: u32,
+    bit_count: u8,
+    min_code_size: u8,
+    code_size: u8,
+    next_code: u16,
+    previous: Option<u16>,
+    prefix: [u16; MAX_CODES],
+    suffix: [u8; MAX_CODES],
+    /// Pixels of the image; indices past them are dropped.
+    pixels: usize,
+    /// Pixels covered by the strings decoded so far.
+    decoded: usize,
+    /// Code whose suffix comes out next, with its pixel.
+    string: Option<(u16, usize)>,
+    /// Index repeating the first one of its string at the end, which comes out before it.
+    repeat: Option<(u8, usize)>,
+}
+
+impl<'a> Lzw<'a> {
+    fn new(data: &'a [u8], min_code_size: u8, pixels: usize) -> Self {
+        let mut lzw = Self {
+            data,
+            position: 0,
+            block_left: 0,
+            bits: 0,
+            bit_count: 0,
+            min_code_size,
+            code_size: 0,
+            next_code: 0,
+            previous: None,
+            prefix: [0; MAX_CODES],
+            suffix: [0; MAX_CODES],
+            pixels,
+            decoded: 0,
+            string: None,
+            repeat: None,
+        };
+        lzw.reset();
+        lzw
+    }
+
+    fn clear_code(&self) -> u16 {
+        1 << self.min_code_size
+    }
+
+    fn reset(&mut self) {
+        self.code_size = self.min_code_size + 1;
+        self.next_code = self.clear_code() + 2;
+        self.previous = None;
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        if self.block_left == 0 {
+            self.block_left = *self.data.get(self.position)? as usize;
+            self.position += 1;
+            if self.block_left == 0 {
+                return None;
+            }
+        }
+        let byte = *self.data.get(self.position)?;
+        self.position += 1;
+        self.block_left -= 1;
+        Some(byte)
+    }
+
+    fn read_code(&mut self) -> Option<u16> {
+        while self.bit_count < self.code_size {
+            self.bits |= (self.read_byte()? as u32) << self.bit_count;
+            self.bit_count += 8;
+        }
+        let code = (self.bits & ((1 << self.code_size) - 1)) as u16;
+        self.bits >>= self.code_size;
+        self.bit_count -= self.code_size;
+        Some(code)
+    }
+
+    /// Length and first index of the string of `code`. `None` if the table holds a loop,
+    /// which only corrupt data produces.
+    fn measure(&self, mut code: u16) -> Option<(usize, u8)> {
+        let mut len = 1;
+        while code >= self.clear_code() {
+            code = self.prefix[code as usize];
+            len += 1;
+            if len > MAX_CODES {
+                return None;
+            }
+        }
+        Some((len, code as u8))
+    }
+
+    /// Decode the next code into the string to put out. `None` at the end of the data or on
+    /// corrupt data.
+    fn decode(&mut self) -> Option<()> {
+        loop {
+            let code = self.read_code()?;
+            let clear = self.clear_code();
+            if code == clear {
+                self.reset();
+                continue;
+            }
+            if code == clear + 1 {
+                return None;
+            }
+            let Some(previous) = self.previous else {
+                if code > clear {
+                    return None;
+                }
+                self.previous = Some(code);
+                self.string = Some((code, self.decoded));
+                self.decoded += 1;
+                return Some(());
+            };
+            let (len, first) = if code < self.next_code {
+                let (len, first) = self.measure(code)?;
+                self.string = Some((code, self.decoded + len - 1));
+                (len, first)
+            } else if code == self.next_code {
+                // The string of the previous code plus its own first index
+                let (len, first) = self.measure(previous)?;
+                self.string = Some((previous, self.decoded + len - 1));
+                self.repeat = Some((first, self.decoded + len));
+                (len + 1, first)
+            } else {
+                return None;
+            };
+            self.decoded += len;
+            if (self.next_code as usize) < MAX_CODES {
+                self.prefix[self.next_code as usize] = previous;
+                self.suffix[self.next_code as usize] = first;
+                self.next_code += 1;
+                if self.next_code == 1 << self.code_size && self.code_size < 12 {
+                    self.code_size += 1;
+                }
+            }
+            self.previous = Some(code);
+            return Some(());
+        }
+    }
+}
+
+impl Iterator for Lzw<'_> {
+    /// Pixel number and color index.
+    type Item = (usize, u8);
+
+    fn next(&mut self) -> Option<(usize, u8)> {
+        loop {
+            let (index, pixel) = if let Some(repeat) = self.repeat.take() {
+                repeat
+            } else if let Some((code, pixel)) = self.string {
+                if code < self.clear_code() {
+                    self.string = None;
+                    (code as u8, pixel)
+                } else {
+                    self.string = Some((self.prefix[code as usize], pixel - 1));
+                    (self.suffix[code as usize], pixel)
+                }
+            } else {
+                if self.decoded >= self.pixels {
+                    return None;
+                }
+                self.decode()?;
+                continue;
+            };
+            if pixel < self.pixels {
+                return Some((pixel, index));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::convert::Infallible;
+    use embedded_graphics_core::geometry::Dimensions;
+    use std::borrow::Cow;
+    use std::vec;
+    use std::vec::Vec;
+
+    const RED: Rgb888 = Rgb888::new(0xFF, 0, 0);
+    const GREEN: Rgb888 = Rgb888::new(0, 0xFF, 0);
+    const BLUE: Rgb888 = Rgb888::new(0, 0, 0xFF);
+    const BLACK: Rgb888 = Rgb888::new(0, 0, 0);
+    /// Color of pixels no frame has drawn.
+    const UNDRAWN: Rgb888 = Rgb888::new(1, 2, 3);
+
+    /// Red, green and blue pixels in a row, with a 4-color global palette and 2-bit codes.
+    const RGB_ROW: &[u8] = &[
+        b'G', b'I', b'F', b'8', b'9', b'a', 3, 0, 1, 0, 0x91, 0, 0,
+        0xFF, 0, 0, 0, 0xFF, 0, 0, 0, 0xFF, 0, 0, 0,
+        // Graphic control: keep the frame
+        0x21, 0xF9, 4, 0x04, 0, 0, 0, 0,
+        0x2C, 0, 0, 0, 0, 3, 0, 1, 0, 0,
+        // Clear, 0, 1, 2, end of data, in 3-bit codes
+        2, 2, 0x44, 0x54, 0,
+        0x3B,
+    ];
+
+    struct Canvas {
+        width: u32,
+        pixels: Vec<Rgb888>,
+    }
+
+    impl Canvas {
+        fn new(gif: &Gif) -> Self {
+            Self { width: gif.width() as u32, pixels: vec![UNDRAWN; gif.width() as usize * gif.height() as usize] }
+        }
+
+        fn at(&self, x: u32, y: u32) -> Rgb888 {
+            self.pixels[(y * self.width + x) as usize]
+        }
+    }
+
+    impl OriginDimensions for Canvas {
+        fn size(&self) -> Size {
+            Size::new(self.width, self.pixels.len() as u32 / self.width)
+        }
+    }
+
+    impl DrawTarget for Canvas {
+        type Color = Rgb888;
+        type Error = Infallible;
+
+        fn draw_iter<P: IntoIterator<Item = Pixel<Rgb888>>>(&mut self, pixels: P) -> Result<(), Infallible> {
+            for Pixel(point, color) in pixels {
+                assert!(self.bounding_box().contains(point), "{point:?} is off the screen");
+                self.pixels[(point.y as u32 * self.width + point.x as u32) as usize] = color;
+            }
+            Ok(())
+        }
+    }
+
+    fn frame(area: (u16, u16, u16, u16), indices: Vec<u8>) -> ::gif::Frame<'static> {
+        let (left, top, width, height) = area;
+        ::gif::Frame { left, top, width, height, buffer: Cow::Owned(indices), ..Default::default() }
+    }
+
+    /// A GIF of `frames` over a screen `size`, with red, green, blue and black as its global
+    /// palette and the first of them as the background color.
+    fn encode(size: (u16, u16), frames: &[::gif::Frame]) -> Vec<u8> {
+        let mut file = Vec::new();
+        let palette = [0xFF, 0, 0, 0, 0xFF, 0, 0, 0, 0xFF, 0, 0, 0];
+        let mut encoder = ::gif::Encoder::new(&mut file, size.0, size.1, &palette).unwrap();
+        frames.iter().for_each(|frame| encoder.write_frame(frame).unwrap());
+        drop(encoder);
+        file
+    }
+
+    #[test]
+    fn single_frames_are_decoded() {
+        let gif = Gif::from_slice(RGB_ROW).unwrap();
+        assert_eq!((gif.width(), gif.height()), (3, 1));
+        let mut canvas = Canvas::new(&gif);
+        let mut frames = gif.frames();
+        frames.next().unwrap().draw(&mut canvas).unwrap();
+        assert!(frames.next().is_none());
+        assert_eq!(canvas.pixels, [RED, GREEN, BLUE]);
+    }
+
+    #[test]
+    fn frames_compose_in_order() {
+        let mut second = frame((1, 1, 2, 2), vec![0, 1, 1, 0]);
+        // A local palette replaces the global one
+        second.palette = Some(vec![0, 0, 0, 0x10, 0x20, 0x30]);
+        second.delay = 25;
+        let file = encode((4, 4), &[frame((0, 0, 4, 4), vec![2; 16]), second]);
+        let gif = Gif::from_slice(&file).unwrap();
+        let frames: Vec<_> = gif.frames().collect();
+        assert_eq!(frames.iter().map(GifFrame::delay_ms).collect::<Vec<_>>(), [0, 250]);
+        let mut canvas = Canvas::new(&gif);
+        frames.iter().for_each(|frame| frame.draw(&mut canvas).unwrap());
+        assert_eq!((canvas.at(0, 0), canvas.at(3, 3), canvas.at(1, 2)), (BLUE, BLUE, Rgb888::new(0x10, 0x20, 0x30)));
+        assert_eq!((canvas.at(1, 1), canvas.at(2, 2)), (BLACK, BLACK));
+    }
+
+    #[test]
+    fn transparent_pixels_show_the_frames_before() {
+        let mut second = frame((0, 0, 2, 1), vec![3, 1]);
+        second.transparent = Some(3);
+        let file = encode((2, 1), &[frame((0, 0, 2, 1), vec![0, 0]), second]);
+        let gif = Gif::from_slice(&file).unwrap();
+        let mut canvas = Canvas::new(&gif);
+        gif.frames().for_each(|frame| frame.draw(&mut canvas).unwrap());
+        assert_eq!(canvas.pixels, [RED, GREEN]);
+    }
+
+    #[test]
+    fn codes_widen_up_to_12_bits_and_restart_after_clear_codes() {
+        // Noise fills the code table, so the encoder has to clear it along the way
+        let mut seed = 1u32;
+        let indices: Vec<u8> = (0..256 * 256)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (seed >> 16) as u8 & 0x03
+            })
+            .collect();
+        let file = encode((256, 256), &[frame((0, 0, 256, 256), indices.clone())]);
+        let gif = Gif::from_slice(&file).unwrap();
+        let frame = gif.frames().next().unwrap();
+        let mut decoded = vec![None; indices.len()];
+        for (pixel, index) in Lzw::new(frame.image_data, frame.min_code_size, indices.len()) {
+            assert!(decoded[pixel].replace(index).is_none(), "pixel {pixel} decoded twice");
+        }
+        assert_eq!(decoded, indices.into_iter().map(Some).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn frames_disposed_to_the_background_are_cleared() {
+        let mut first = frame((0, 0, 3, 1), vec![1, 1, 1]);
+        first.dispose = ::gif::DisposalMethod::Background;
+        let file = encode((3, 1), &[first, frame((2, 0, 1, 1), vec![2])]);
+        let gif = Gif::from_slice(&file).unwrap();
+        let mut canvas = Canvas::new(&gif);
+        gif.frames().for_each(|frame| frame.draw(&mut canvas).unwrap());
+        assert_eq!(canvas.pixels, [RED, RED, BLUE]);
+    }
+
+    #[test]
+    fn frames_disposed_to_the_previous_image_are_refused() {
+        let mut first = frame((0, 0, 1, 1), vec![1]);
+        first.dispose = ::gif::DisposalMethod::Previous;
+        assert!(Gif::from_slice(&encode((1, 1), &[first])).is_none());
+    }
+
+    #[test]
+    fn corrupt_data_ends_the_frame() {
+        let mut file = RGB_ROW.to_vec();
+        // A code past the table right after the clear code
+        file[45] |= 0b0011_1000;
+        let gif = Gif::from_slice(&file).unwrap();
+        let mut canvas = Canvas::new(&gif);
+        gif.frames().next().unwrap().draw(&mut canvas).unwrap();
+        assert_eq!(canvas.pixels, [UNDRAWN; 3]);
+    }
+}
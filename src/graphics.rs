@@ -0,0 +1,289 @@
+//! [embedded-graphics](https://docs.rs/embedded-graphics) support. Enabled with the `graphics`
+//! feature.
+//!
+//! Mono font text is recognised by its drawing pattern and sent through BTE color expansion, one
+//! bit per pixel, instead of one memory write per pixel:
+//! - text with a background color arrives as two-colored `fill_contiguous` calls,
+//! - transparent text arrives as `draw_iter` runs of a single color within a small area.
+//!
+//! Other `fill_contiguous` calls, such as `Image`/`ImageRaw` drawing, are streamed through the
+//! memory port in bursts.
+
+use crate::{CheckedError, Color, LT7683, LT7683Interface, Rect, Rotation, WaitStrategy};
+use embedded_graphics_core::Pixel;
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{Dimensions, OriginDimensions, Point, Size};
+use embedded_graphics_core::pixelcolor::{BinaryColor, Rgb565, Rgb888, RgbColor};
+use embedded_graphics_core::primitives::{PointsIter, Rectangle};
+use embedded_hal::digital::OutputPin;
+
+/// Side of the largest square buffered for color expansion, enough for common mono fonts.
+const EXPANSION_SIZE: i32 = 64;
+const STRIDE: usize = EXPANSION_SIZE as usize / 8;
+/// Runs with fewer pixels than this are cheaper to write pixel by pixel.
+const MIN_EXPANSION_PIXELS: u32 = 8;
+/// Bytes of converted pixels collected per memory port burst.
+const STREAM_CHUNK: usize = 96;
+
+fn to_u32(color: Rgb888) -> u32 {
+    (color.r() as u32) << 16 | (color.g() as u32) << 8 | color.b() as u32
+}
+
+impl From<Rgb888> for Color {
+    fn from(color: Rgb888) -> Self {
+        Color(to_u32(color))
+    }
+}
+
+/// Channels are widened by repeating their top bits, so full intensity stays full and the color
+/// survives a 16 bpp canvas unchanged.
+impl From<Rgb565> for Color {
+    fn from(color: Rgb565) -> Self {
+        let (r, g, b) = (color.r(), color.g(), color.b());
+        Color::rgb(r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2)
+    }
+}
+
+/// `On` is white, `Off` black.
+impl From<BinaryColor> for Color {
+    fn from(color: BinaryColor) -> Self {
+        match color {
+            BinaryColor::On => Color(0x00FF_FFFF),
+            BinaryColor::Off => Color(0x0000_0000),
+        }
+    }
+}
+
+/// 1bpp image of up to `EXPANSION_SIZE` squared pixels, anchored at `origin`.
+struct BitBuffer {
+    bits: [u8; STRIDE * EXPANSION_SIZE as usize],
+    origin: Point,
+    top_left: Point,
+    bottom_right: Point,
+    count: u32,
+}
+
+impl BitBuffer {
+    fn new(origin: Point) -> Self {
+        Self {
+            bits: [0; STRIDE * EXPANSION_SIZE as usize],
+            origin,
+            top_left: Point::new(i32::MAX, i32::MAX),
+            bottom_right: Point::new(i32::MIN, i32::MIN),
+            count: 0,
+        }
+    }
+
+    /// Set the bit for `point`, returning false if it lies outside the buffer.
+    fn set(&mut self, point: Point) -> bool {
+        let offset = point - self.origin;
+        if !(0..EXPANSION_SIZE).contains(&offset.x) || !(0..EXPANSION_SIZE).contains(&offset.y) {
+            return false;
+        }
+        self.bits[offset.y as usize * STRIDE + offset.x as usize / 8] |= 0x80 >> (offset.x % 8);
+        self.top_left = self.top_left.component_min(point);
+        self.bottom_right = self.bottom_right.component_max(point);
+        self.count += 1;
+        true
+    }
+
+    fn get(&self, point: Point) -> bool {
+        let offset = point - self.origin;
+        self.bits[offset.y as usize * STRIDE + offset.x as usize / 8] & (0x80 >> (offset.x % 8)) != 0
+    }
+
+    /// Byte `index` of the row at `y`, with bit 7 being the pixel at `left + index * 8`.
+    fn row_byte(&self, y: i32, left: i32, right: i32, index: i32) -> u8 {
+        let mut byte = 0;
+        for bit in 0..8 {
+            let x = left + index * 8 + bit;
+            if x <= right && self.get(Point::new(x, y)) {
+                byte |= 0x80 >> bit;
+            }
+        }
+        byte
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// The canvas, cut to the clip rectangle if one is set.
+    fn visible_bounds(&self) -> Rectangle {
+        let bounds = self.bounding_box();
+        match self.clip_rect() {
+            Some(clip) => {
+                let clip = Rectangle::new(Point::new(clip.x as i32, clip.y as i32), Size::new(clip.width as u32, clip.height as u32));
+                bounds.intersection(&clip)
+            }
+            None => bounds,
+        }
+    }
+
+    /// Draw the set bits of `buffer` within `area` in `fg_color`, and the clear bits in
+    /// `bg_color` if given.
+    fn expand_bits(&mut self, buffer: &BitBuffer, area: Rectangle, fg_color: Rgb888, bg_color: Option<Rgb888>) -> Result<(), I::Error> {
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        let (left, top) = (area.top_left.x, area.top_left.y);
+        let (width, height) = (area.size.width as u16, area.size.height as u16);
+        self.begin_bte_color_expansion(left as u16, top as u16, width, height, to_u32(fg_color), bg_color.map(to_u32))?;
+        let bytes_per_row = (area.size.width as i32 + 7) / 8;
+        for y in top..=bottom_right.y {
+            for index in 0..bytes_per_row {
+                let byte = buffer.row_byte(y, left, bottom_right.x, index);
+                self.ll.write_data(byte)?;
+            }
+        }
+        self.wait_bte_complete()
+    }
+
+    /// Write `colors` into `area`, which must lie within the canvas, through the memory port.
+    fn stream_area<C>(&mut self, area: &Rectangle, colors: C) -> Result<(), CheckedError<I::Error>>
+    where
+        C: IntoIterator<Item = Rgb888>,
+    {
+        let rect = Rect::new(area.top_left.x as u16, area.top_left.y as u16, area.size.width as u16, area.size.height as u16);
+        let pixels = area.size.width as usize * area.size.height as usize;
+        let depth = self.config.color_depth;
+        // Only called unrotated, so rows are never sent bottom up
+        self.write_into(rect, |display, _| {
+            let mut buf = [0; STREAM_CHUNK];
+            let mut len = 0;
+            for color in colors.into_iter().take(pixels) {
+                let (bytes, count) = depth.pixel_bytes(display.correct_color(to_u32(color)));
+                buf[len..len + count].copy_from_slice(&bytes[..count]);
+                len += count;
+                if len + 3 > buf.len() {
+                    display.write_data_burst(&buf[..len])?;
+                    len = 0;
+                }
+            }
+            if len > 0 {
+                display.write_data_burst(&buf[..len])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Draw a single-color run collected by `draw_iter`.
+    fn flush_run(&mut self, buffer: &BitBuffer, color: Rgb888) -> Result<(), CheckedError<I::Error>> {
+        if buffer.count == 0 {
+            return Ok(());
+        }
+        let area = Rectangle::with_corners(buffer.top_left, buffer.bottom_right);
+        // The expansion engine cannot rotate the bits
+        if buffer.count >= MIN_EXPANSION_PIXELS && self.rotation == Rotation::Deg0 {
+            return Ok(self.expand_bits(buffer, area, color, None)?);
+        }
+        for point in area.points().filter(|&point| buffer.get(point)) {
+            self.draw_pixel(point.x as u16, point.y as u16, to_u32(color))?;
+        }
+        Ok(())
+    }
+}
+
+impl<I: LT7683Interface, RESET, W> OriginDimensions for LT7683<I, RESET, W> {
+    /// Canvas size in logical coordinates, see [`logical_size`](LT7683::logical_size).
+    fn size(&self) -> Size {
+        let (width, height) = self.logical_size();
+        Size::new(width as u32, height as u32)
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> DrawTarget for LT7683<I, RESET, W> {
+    type Color = Rgb888;
+    type Error = CheckedError<I::Error>;
+
+    fn draw_iter<P>(&mut self, pixels: P) -> Result<(), Self::Error>
+    where
+        P: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.visible_bounds();
+        let mut run: Option<(BitBuffer, Rgb888)> = None;
+        for Pixel(point, color) in pixels {
+            if !bounds.contains(point) {
+                continue;
+            }
+            if let Some((buffer, run_color)) = &mut run {
+                if *run_color == color && buffer.set(point) {
+                    continue;
+                }
+                self.flush_run(buffer, *run_color)?;
+            }
+            // Anchor the buffer so glyph rows extending left of the first pixel still fit
+            let origin = Point::new((point.x - EXPANSION_SIZE / 2).max(0), point.y);
+            let mut buffer = BitBuffer::new(origin);
+            buffer.set(point);
+            run = Some((buffer, color));
+        }
+        match &run {
+            Some((buffer, color)) => self.flush_run(buffer, *color),
+            None => Ok(()),
+        }
+    }
+
+    fn fill_contiguous<C>(&mut self, area: &Rectangle, colors: C) -> Result<(), Self::Error>
+    where
+        C: IntoIterator<Item = Self::Color>,
+    {
+        let rotated = self.rotation != Rotation::Deg0;
+        if rotated || area.is_zero_sized() || self.visible_bounds().intersection(area) != *area {
+            return self.draw_iter(area.points().zip(colors).map(|(point, color)| Pixel(point, color)));
+        }
+        let fits = area.size.width <= EXPANSION_SIZE as u32 && area.size.height <= EXPANSION_SIZE as u32;
+        if !fits {
+            return self.stream_area(area, colors);
+        }
+        // Two-colored areas (glyphs with a background) are expanded from a bitmap where set bits
+        // are the second color seen
+        let mut buffer = BitBuffer::new(area.top_left);
+        let mut first: Option<Rgb888> = None;
+        let mut second: Option<Rgb888> = None;
+        let mut points = area.points();
+        let mut colors = colors.into_iter();
+        for (index, (point, color)) in points.by_ref().zip(colors.by_ref()).enumerate() {
+            match (first, second) {
+                (None, _) => first = Some(color),
+                (Some(c0), _) if c0 == color => {}
+                (Some(_), None) => {
+                    second = Some(color);
+                    buffer.set(point);
+                }
+                (Some(_), Some(c1)) if c1 == color => {
+                    buffer.set(point);
+                }
+                (Some(c0), Some(c1)) => {
+                    // More than two colors, e.g. an image: replay what was buffered and stream
+                    // the rest
+                    let done = area.points().take(index).map(|done| if buffer.get(done) { c1 } else { c0 });
+                    return self.stream_area(area, done.chain([color]).chain(colors));
+                }
+            }
+        }
+        match (first, second) {
+            (Some(c0), Some(c1)) => Ok(self.expand_bits(&buffer, *area, c1, Some(c0))?),
+            (Some(c0), None) => {
+                let (x1, y1) = (area.top_left.x as u16, area.top_left.y as u16);
+                let (x2, y2) = (x1 + area.size.width as u16 - 1, y1 + area.size.height as u16 - 1);
+                Ok(self.draw_rectangle(x1, y1, x2, y2, to_u32(c0), true)?)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Filled with the BTE engine or the drawing engine as configured for
+    /// [`clear_area`](LT7683::clear_area).
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.bounding_box().intersection(area);
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        let size = bottom_right - area.top_left + Point::new(1, 1);
+        let rect = Rect::new(area.top_left.x as u16, area.top_left.y as u16, size.x as u16, size.y as u16);
+        self.clear_area(rect, to_u32(color))
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_screen(to_u32(color))
+    }
+}
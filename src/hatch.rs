@@ -0,0 +1,134 @@
+//! Hatched fills for printer-style charts: rectangles through the BTE pattern fill, triangles
+//! through line sweeps.
+
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Side of the repeating pattern in pixels.
+const PATTERN_SIZE: u16 = 8;
+
+/// Fill pattern, repeated every 8 pixels in both directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hatch {
+    /// Lines rising to the right.
+    Diagonal,
+    /// Lines rising and falling to the right.
+    Crosshatch,
+    /// Staggered dots.
+    Dots,
+}
+
+impl Hatch {
+    /// The pattern as 1bpp rows, MSB leftmost.
+    const fn rows(self) -> [u8; 8] {
+        match self {
+            Hatch::Diagonal => [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80],
+            Hatch::Crosshatch => [0x81, 0x42, 0x24, 0x18, 0x18, 0x24, 0x42, 0x81],
+            Hatch::Dots => [0x80, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00],
+        }
+    }
+
+    fn is_set(self, x: i32, y: i32) -> bool {
+        let row = self.rows()[y.rem_euclid(8) as usize];
+        row & (0x80 >> x.rem_euclid(8)) != 0
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Fill `rect` with `hatch` in `color` on `bg_color`, or over what is there with no
+    /// background, using the BTE pattern fill. The pattern starts at the rectangle's upper-left
+    /// corner. The 8x8 pattern image is written to display memory at `pattern_address`.
+    pub fn fill_rect_hatched(
+        &mut self, rect: Rect, hatch: Hatch, color: u32, bg_color: Option<u32>, pattern_address: u32
+    ) -> Result<(), CheckedError<I::Error>> {
+        if rect.is_empty() {
+            return Ok(());
+        }
+        // Without a background the gaps are drawn in a key color that the fill leaves out
+        let background = bg_color.unwrap_or(color ^ 0x00FF_FFFF);
+        let depth = self.config.color_depth;
+        let bytes_per_pixel = depth.bytes_per_pixel() as usize;
+        let mut image = [0; 3 * (PATTERN_SIZE * PATTERN_SIZE) as usize];
+        for (i, pixel) in image.chunks_exact_mut(bytes_per_pixel).take((PATTERN_SIZE * PATTERN_SIZE) as usize).enumerate() {
+            let set = hatch.is_set(i as i32 % 8, i as i32 / 8);
+            let (bytes, _) = depth.pixel_bytes(self.correct_color(if set { color } else { background }));
+            pixel.copy_from_slice(&bytes[..bytes_per_pixel]);
+        }
+        let len = (PATTERN_SIZE * PATTERN_SIZE) as usize * bytes_per_pixel;
+        self.draw_offscreen(pattern_address, PATTERN_SIZE, PATTERN_SIZE, |display| {
+            display.write_image(Rect::new(0, 0, PATTERN_SIZE, PATTERN_SIZE), &image[..len])
+        })?;
+        let chroma_key = bg_color.is_none().then_some(background);
+        // Pattern fill: ROP=0xC (S0), operation=0x6, or 0x7 with chroma key
+        let source = (pattern_address, PATTERN_SIZE);
+        self.bte_from_source(source, 0, 0, rect.x, rect.y, rect.width, rect.height, chroma_key, (0xC6, 0xC7))
+    }
+
+    /// Draw `hatch` in `color` inside the triangle with corners `p1`, `p2` and `p3`, leaving the
+    /// gaps untouched; fill the triangle first for a background. Lines are drawn with the
+    /// drawing engine and dots as single pixels, with the pattern aligned to the canvas.
+    pub fn fill_triangle_hatched(
+        &mut self, p1: (u16, u16), p2: (u16, u16), p3: (u16, u16), hatch: Hatch, color: u32
+    ) -> Result<(), CheckedError<I::Error>> {
+        let triangle = [p1, p2, p3].map(|(x, y)| (x as i32, y as i32));
+        let top = triangle.iter().map(|p| p.1).min().unwrap_or(0);
+        let bottom = triangle.iter().map(|p| p.1).max().unwrap_or(0);
+        let left = triangle.iter().map(|p| p.0).min().unwrap_or(0);
+        let right = triangle.iter().map(|p| p.0).max().unwrap_or(0);
+        // Diagonal lines y = slope * x + offset, at the offsets where the pattern has them
+        let diagonals: &[(i32, i32)] = match hatch {
+            Hatch::Diagonal => &[(-1, 7)],
+            Hatch::Crosshatch => &[(-1, 7), (1, 0)],
+            Hatch::Dots => &[],
+        };
+        for &(slope, residue) in diagonals {
+            // Range of offsets of the lines crossing the bounding box
+            let (first, last) = if slope < 0 { (left + top, right + bottom) } else { (top - right, bottom - left) };
+            let mut offset = first + (residue - first).rem_euclid(8);
+            while offset <= last {
+                if let Some((x1, x2)) = clip_line(&triangle, slope, offset) {
+                    let (y1, y2) = (slope * x1 + offset, slope * x2 + offset);
+                    self.draw_line(x1 as u16, y1 as u16, x2 as u16, y2 as u16, color)?;
+                }
+                offset += 8;
+            }
+        }
+        if hatch == Hatch::Dots {
+            for y in top..=bottom {
+                let Some((x1, x2)) = clip_line(&triangle, 0, y) else { continue };
+                for x in (x1..=x2).filter(|&x| hatch.is_set(x, y)) {
+                    self.draw_pixel(x as u16, y as u16, color)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Range of x over which the line y = slope * x + offset lies inside `triangle`, rounded
+/// inwards to whole pixels. `None` if the line misses it or the triangle is degenerate.
+fn clip_line(triangle: &[(i32, i32); 3], slope: i32, offset: i32) -> Option<(i32, i32)> {
+    let (mut low, mut high) = (i64::MIN, i64::MAX);
+    for i in 0..3 {
+        let (a, b, c) = (triangle[i], triangle[(i + 1) % 3], triangle[(i + 2) % 3]);
+        let (dx, dy) = ((b.0 - a.0) as i64, (b.1 - a.1) as i64);
+        // Cross product of the edge with a point, positive on the side of the opposite corner
+        let side = |x: i64, y: i64| dx * (y - a.1 as i64) - dy * (x - a.0 as i64);
+        let orientation = side(c.0 as i64, c.1 as i64).signum();
+        if orientation == 0 {
+            return None;
+        }
+        // Along the line the cross product is k * x + m; keep the x where it has the sign of
+        // the opposite corner or is zero
+        let k = (dx * slope as i64 - dy) * orientation;
+        let m = side(0, offset as i64) * orientation;
+        if k > 0 {
+            low = low.max((-m).div_euclid(k) + ((-m).rem_euclid(k) != 0) as i64);
+        } else if k < 0 {
+            high = high.min(m.div_euclid(-k));
+        } else if m < 0 {
+            return None;
+        }
+    }
+    (low <= high).then_some((low as i32, high as i32))
+}
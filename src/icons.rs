@@ -0,0 +1,425 @@
+//! Built-in 16x16 status icons, so basic indicators need no asset pipeline. Icons are drawn by
+//! BTE color expansion, or uploaded once as a user font and written with the text engine.
+
+use crate::{CheckedError, FontSize, LT7683, LT7683Interface, Rect, UserFont, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Character code of the first icon in the font written by
+/// [`upload_icon_font`](LT7683::upload_icon_font).
+const ICON_FIRST_CODE: u8 = 0x80;
+/// Rows of blank space above the icons in their 16x32 font cells.
+const ICON_CELL_TOP: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    BatteryEmpty,
+    BatteryLow,
+    BatteryHalf,
+    BatteryFull,
+    /// Signal strength, e.g. Wi-Fi, with none of the four bars filled.
+    Signal0,
+    Signal1,
+    Signal2,
+    Signal3,
+    Signal4,
+    ArrowUp,
+    ArrowRight,
+    ArrowDown,
+    ArrowLeft,
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconSize {
+    /// 16x16 pixels.
+    Small,
+    /// 32x32 pixels, each icon pixel doubled.
+    Large,
+}
+
+impl Icon {
+    pub const ALL: [Icon; 14] = [
+        Icon::BatteryEmpty, Icon::BatteryLow, Icon::BatteryHalf, Icon::BatteryFull,
+        Icon::Signal0, Icon::Signal1, Icon::Signal2, Icon::Signal3, Icon::Signal4,
+        Icon::ArrowUp, Icon::ArrowRight, Icon::ArrowDown, Icon::ArrowLeft, Icon::Warning,
+    ];
+
+    /// Battery icon for a charge of `percent`.
+    pub const fn battery(percent: u8) -> Icon {
+        match percent {
+            0..=10 => Icon::BatteryEmpty,
+            11..=40 => Icon::BatteryLow,
+            41..=80 => Icon::BatteryHalf,
+            _ => Icon::BatteryFull,
+        }
+    }
+
+    /// Signal icon with `bars` of four filled.
+    pub const fn signal(bars: u8) -> Icon {
+        match bars {
+            0 => Icon::Signal0,
+            1 => Icon::Signal1,
+            2 => Icon::Signal2,
+            3 => Icon::Signal3,
+            _ => Icon::Signal4,
+        }
+    }
+
+    /// Rows of the icon, top first, with bit 15 the leftmost pixel.
+    pub const fn rows(self) -> &'static [u16; 16] {
+        match self {
+            Icon::BatteryEmpty => &BATTERY_EMPTY,
+            Icon::BatteryLow => &BATTERY_LOW,
+            Icon::BatteryHalf => &BATTERY_HALF,
+            Icon::BatteryFull => &BATTERY_FULL,
+            Icon::Signal0 => &SIGNAL_0,
+            Icon::Signal1 => &SIGNAL_1,
+            Icon::Signal2 => &SIGNAL_2,
+            Icon::Signal3 => &SIGNAL_3,
+            Icon::Signal4 => &SIGNAL_4,
+            Icon::ArrowUp => &ARROW_UP,
+            Icon::ArrowRight => &ARROW_RIGHT,
+            Icon::ArrowDown => &ARROW_DOWN,
+            Icon::ArrowLeft => &ARROW_LEFT,
+            Icon::Warning => &WARNING,
+        }
+    }
+
+    /// Character code of the icon in the font written by
+    /// [`upload_icon_font`](LT7683::upload_icon_font). As a `char` (`char::from(code)`) it can
+    /// be part of the text passed to `write_text`.
+    pub const fn code(self) -> u8 {
+        ICON_FIRST_CODE + self as u8
+    }
+}
+
+/// Spread the 16 bits of `row` over 32, each bit doubled.
+const fn double_bits(row: u16) -> u32 {
+    let mut doubled = 0;
+    let mut bit = 0;
+    while bit < 16 {
+        if row & (1 << bit) != 0 {
+            doubled |= 0b11 << (bit * 2);
+        }
+        bit += 1;
+    }
+    doubled
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Draw `icon` with its upper-left corner at `x`, `y`. Set pixels take `fg_color`, the
+    /// others `bg_color` or, without one, are left untouched.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_icon(&mut self, icon: Icon, x: u16, y: u16, size: IconSize, fg_color: u32, bg_color: Option<u32>) -> Result<(), CheckedError<I::Error>> {
+        match size {
+            IconSize::Small => {
+                let mut bitmap = [0; 32];
+                for (bytes, row) in bitmap.chunks_mut(2).zip(icon.rows()) {
+                    bytes.copy_from_slice(&row.to_be_bytes());
+                }
+                self.draw_mono_bitmap(x, y, 16, 16, &bitmap, fg_color, bg_color)
+            }
+            IconSize::Large => {
+                let mut bitmap = [0; 128];
+                for (bytes, row) in bitmap.chunks_mut(8).zip(icon.rows()) {
+                    let doubled = double_bits(*row).to_be_bytes();
+                    bytes[..4].copy_from_slice(&doubled);
+                    bytes[4..].copy_from_slice(&doubled);
+                }
+                self.draw_mono_bitmap(x, y, 32, 32, &bitmap, fg_color, bg_color)
+            }
+        }
+    }
+
+    /// Write all icons into display memory at `cgram_address` as a user font with 16x32
+    /// cells, the icon in the middle of the cell, and return it for
+    /// [`Charset::User`](crate::Charset::User). Icons are then written like characters, by
+    /// their [`code`](Icon::code); a text scale of 2 gives 32x32 icons. Takes
+    /// [`FontSize::Size16x32.user_font_bytes()`](FontSize::user_font_bytes) of display memory
+    /// from `cgram_address`.
+    pub fn upload_icon_font(&mut self, cgram_address: u32) -> Result<UserFont, CheckedError<I::Error>> {
+        let font_size = FontSize::Size16x32;
+        let glyph_bytes = font_size.glyph_bytes();
+        // One glyph per row of an 8 bpp image, so row n holds the glyph for code n
+        self.draw_offscreen_bytes(cgram_address, glyph_bytes, 256, |display| {
+            for icon in Icon::ALL {
+                let mut glyph = [0; 64];
+                for (index, row) in icon.rows().iter().enumerate() {
+                    let offset = (ICON_CELL_TOP + index) * 2;
+                    glyph[offset..offset + 2].copy_from_slice(&row.to_be_bytes());
+                }
+                display.write_image(Rect::new(0, icon.code() as u16, glyph_bytes, 1), &glyph)?;
+            }
+            Ok(())
+        })?;
+        // The cursor was tracked in canvas pixels, not the bytes written
+        self.graphic_cursor = None;
+        Ok(UserFont { font_size, address: cgram_address, first_code: ICON_FIRST_CODE, glyph_count: Icon::ALL.len() as u16 })
+    }
+}
+
+const BATTERY_EMPTY: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000000000,
+    0b1111111111111100,
+    0b1000000000000100,
+    0b1000000000000100,
+    0b1000000000000111,
+    0b1000000000000111,
+    0b1000000000000111,
+    0b1000000000000111,
+    0b1000000000000100,
+    0b1000000000000100,
+    0b1111111111111100,
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000000000,
+];
+
+const BATTERY_LOW: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000000000,
+    0b1111111111111100,
+    0b1000000000000100,
+    0b1011100000000100,
+    0b1011100000000111,
+    0b1011100000000111,
+    0b1011100000000111,
+    0b1011100000000111,
+    0b1011100000000100,
+    0b1000000000000100,
+    0b1111111111111100,
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000000000,
+];
+
+const BATTERY_HALF: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000000000,
+    0b1111111111111100,
+    0b1000000000000100,
+    0b1011111000000100,
+    0b1011111000000111,
+    0b1011111000000111,
+    0b1011111000000111,
+    0b1011111000000111,
+    0b1011111000000100,
+    0b1000000000000100,
+    0b1111111111111100,
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000000000,
+];
+
+const BATTERY_FULL: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000000000,
+    0b1111111111111100,
+    0b1000000000000100,
+    0b1011111111110100,
+    0b1011111111110111,
+    0b1011111111110111,
+    0b1011111111110111,
+    0b1011111111110111,
+    0b1011111111110100,
+    0b1000000000000100,
+    0b1111111111111100,
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000000000,
+];
+
+const SIGNAL_0: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000001110,
+    0b0000000000001010,
+    0b0000000000001010,
+    0b0000000011101010,
+    0b0000000010101010,
+    0b0000000010101010,
+    0b0000111010101010,
+    0b0000101010101010,
+    0b0000101010101010,
+    0b1110101010101010,
+    0b1010101010101010,
+    0b1010101010101010,
+    0b1110111011101110,
+    0b0000000000000000,
+];
+
+const SIGNAL_1: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000001110,
+    0b0000000000001010,
+    0b0000000000001010,
+    0b0000000011101010,
+    0b0000000010101010,
+    0b0000000010101010,
+    0b0000111010101010,
+    0b0000101010101010,
+    0b0000101010101010,
+    0b1110101010101010,
+    0b1110101010101010,
+    0b1110101010101010,
+    0b1110111011101110,
+    0b0000000000000000,
+];
+
+const SIGNAL_2: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000001110,
+    0b0000000000001010,
+    0b0000000000001010,
+    0b0000000011101010,
+    0b0000000010101010,
+    0b0000000010101010,
+    0b0000111010101010,
+    0b0000111010101010,
+    0b0000111010101010,
+    0b1110111010101010,
+    0b1110111010101010,
+    0b1110111010101010,
+    0b1110111011101110,
+    0b0000000000000000,
+];
+
+const SIGNAL_3: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000001110,
+    0b0000000000001010,
+    0b0000000000001010,
+    0b0000000011101010,
+    0b0000000011101010,
+    0b0000000011101010,
+    0b0000111011101010,
+    0b0000111011101010,
+    0b0000111011101010,
+    0b1110111011101010,
+    0b1110111011101010,
+    0b1110111011101010,
+    0b1110111011101110,
+    0b0000000000000000,
+];
+
+const SIGNAL_4: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000000000000,
+    0b0000000000001110,
+    0b0000000000001110,
+    0b0000000000001110,
+    0b0000000011101110,
+    0b0000000011101110,
+    0b0000000011101110,
+    0b0000111011101110,
+    0b0000111011101110,
+    0b0000111011101110,
+    0b1110111011101110,
+    0b1110111011101110,
+    0b1110111011101110,
+    0b1110111011101110,
+    0b0000000000000000,
+];
+
+const ARROW_UP: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000110000000,
+    0b0000001111000000,
+    0b0000011111100000,
+    0b0000111111110000,
+    0b0001111111111000,
+    0b0011111111111100,
+    0b0111111111111110,
+    0b1111111111111111,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000000000000000,
+];
+
+const ARROW_RIGHT: [u16; 16] = [
+    0b0000000100000000,
+    0b0000000110000000,
+    0b0000000111000000,
+    0b0000000111100000,
+    0b0000000111110000,
+    0b0000000111111000,
+    0b0111111111111100,
+    0b0111111111111110,
+    0b0111111111111110,
+    0b0111111111111100,
+    0b0000000111111000,
+    0b0000000111110000,
+    0b0000000111100000,
+    0b0000000111000000,
+    0b0000000110000000,
+    0b0000000100000000,
+];
+
+const ARROW_DOWN: [u16; 16] = [
+    0b0000000000000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b0000001111000000,
+    0b1111111111111111,
+    0b0111111111111110,
+    0b0011111111111100,
+    0b0001111111111000,
+    0b0000111111110000,
+    0b0000011111100000,
+    0b0000001111000000,
+    0b0000000110000000,
+    0b0000000000000000,
+];
+
+const ARROW_LEFT: [u16; 16] = [
+    0b0000000010000000,
+    0b0000000110000000,
+    0b0000001110000000,
+    0b0000011110000000,
+    0b0000111110000000,
+    0b0001111110000000,
+    0b0011111111111110,
+    0b0111111111111110,
+    0b0111111111111110,
+    0b0011111111111110,
+    0b0001111110000000,
+    0b0000111110000000,
+    0b0000011110000000,
+    0b0000001110000000,
+    0b0000000110000000,
+    0b0000000010000000,
+];
+
+const WARNING: [u16; 16] = [
+    0b0000000000000000,
+    0b0000000110000000,
+    0b0000000110000000,
+    0b0000001111000000,
+    0b0000001001000000,
+    0b0000011111100000,
+    0b0000010110100000,
+    0b0000110110110000,
+    0b0001100110011000,
+    0b0001000110001000,
+    0b0011000000001100,
+    0b0010000110000100,
+    0b0110000110000110,
+    0b0111111111111110,
+    0b1111111111111111,
+    0b0000000000000000,
+];
@@ -0,0 +1,228 @@
+//! Images converted to raw pixels at compile time, ready for [`LT7683::draw_image`] or
+//! [`SpriteAtlas::load`](crate::SpriteAtlas::load):
+//!
+//! ```ignore
+//! static LOGO: Image = include_image!("logo.ppm", ColorDepth::Bpp16);
+//! display.draw_image(10, 10, &LOGO)?;
+//! ```
+//!
+//! Sources are binary PPM files (`P6`, up to 8 bits per channel), which most image tools
+//! write, e.g. `convert logo.png logo.ppm`, or with the `png` feature PNG files, decoded in
+//! the build by the `lt7683-macros` companion crate.
+
+use crate::{CheckedError, ColorDepth, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Raw pixels in memory port byte order, row by row, at `color_depth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Image {
+    pub width: u16,
+    pub height: u16,
+    pub color_depth: ColorDepth,
+    pub data: &'static [u8],
+}
+
+impl Image {
+    /// Bytes of `width` by `height` pixels at `color_depth`, for [`include_image!`].
+    #[doc(hidden)]
+    pub const fn raw_len(width: u16, height: u16, color_depth: ColorDepth) -> usize {
+        width as usize * height as usize * color_depth.bytes_per_pixel() as usize
+    }
+
+    /// Bytes of the converted pixels of `ppm`, for [`include_image!`].
+    #[doc(hidden)]
+    pub const fn ppm_len(ppm: &[u8], color_depth: ColorDepth) -> usize {
+        let (width, height, _, _) = ppm_header(ppm);
+        Self::raw_len(width, height, color_depth)
+    }
+
+    /// Size of the image in `ppm`, for [`include_image!`]. Evaluated in a `const`, as the
+    /// macro does, a malformed header fails the build:
+    ///
+    /// ```compile_fail,E0080
+    /// const SIZE: (u16, u16) = lt7683::Image::ppm_size(b"P6\n4 x\n255\n");
+    /// ```
+    #[doc(hidden)]
+    pub const fn ppm_size(ppm: &[u8]) -> (u16, u16) {
+        let (width, height, _, _) = ppm_header(ppm);
+        (width, height)
+    }
+
+    /// Pixels of `ppm` converted to `color_depth`, for [`include_image!`].
+    #[doc(hidden)]
+    pub const fn convert_ppm<const LEN: usize>(ppm: &[u8], color_depth: ColorDepth) -> [u8; LEN] {
+        let (_, _, max_value, start) = ppm_header(ppm);
+        convert(ppm.split_at(start).1, max_value, color_depth)
+    }
+
+    /// 8-bit RGB pixels converted to `color_depth`, for [`include_image!`].
+    #[doc(hidden)]
+    pub const fn convert_rgb<const LEN: usize>(rgb: &[u8], color_depth: ColorDepth) -> [u8; LEN] {
+        convert(rgb, 255, color_depth)
+    }
+}
+
+/// Embed the image file at `path`, relative to the current file like [`include_bytes!`], as
+/// an [`Image`] at the given color depth, 16 bpp if none is given. The conversion runs at
+/// compile time. Binary PPM files are always understood, PNG files with the `png` feature;
+/// other files fail to build.
+#[macro_export]
+macro_rules! include_image {
+    ($path:literal) => {
+        $crate::include_image!($path, $crate::ColorDepth::Bpp16)
+    };
+    ($path:literal, $color_depth:expr) => {
+        $crate::__include_image!($crate, $path, $color_depth)
+    };
+}
+
+/// Without the `png` feature every image is a PPM file.
+#[cfg(not(feature = "png"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __include_image {
+    ($krate:tt, $path:literal, $color_depth:expr) => {
+        $crate::__include_ppm!($path, $color_depth)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __include_ppm {
+    ($path:literal, $color_depth:expr) => {{
+        // The color depth is not given a name, which might be the caller's own
+        const PPM: &[u8] = include_bytes!($path);
+        const SIZE: (u16, u16) = $crate::Image::ppm_size(PPM);
+        // Large images take many steps to convert
+        #[allow(long_running_const_eval)]
+        static DATA: [u8; $crate::Image::ppm_len(PPM, $color_depth)] = $crate::Image::convert_ppm(PPM, $color_depth);
+        $crate::Image { width: SIZE.0, height: SIZE.1, color_depth: $color_depth, data: &DATA }
+    }};
+}
+
+/// Pixels of three channels of up to `max_value` each converted to `color_depth`.
+const fn convert<const LEN: usize>(rgb: &[u8], max_value: u16, color_depth: ColorDepth) -> [u8; LEN] {
+    let mut data = [0; LEN];
+    let mut source = 0;
+    let mut target = 0;
+    while target < LEN {
+        let r = scale_channel(rgb[source], max_value);
+        let g = scale_channel(rgb[source + 1], max_value);
+        let b = scale_channel(rgb[source + 2], max_value);
+        let (bytes, len) = color_depth.pixel_bytes((r as u32) << 16 | (g as u32) << 8 | b as u32);
+        let mut index = 0;
+        while index < len {
+            data[target + index] = bytes[index];
+            index += 1;
+        }
+        source += 3;
+        target += len;
+    }
+    data
+}
+
+/// Width, height, largest channel value and start of the pixels of a binary PPM file. Panics,
+/// at compile time when converting in a `const`, if `ppm` is not one.
+const fn ppm_header(ppm: &[u8]) -> (u16, u16, u16, usize) {
+    assert!(ppm.len() >= 2 && ppm[0] == b'P' && ppm[1] == b'6', "image is not a binary PPM (P6) file");
+    // Width, height and largest channel value, separated by whitespace and comments
+    let mut fields = [0u32; 3];
+    let mut field = 0;
+    let mut pos = 2;
+    while field < fields.len() {
+        loop {
+            assert!(pos < ppm.len(), "PPM header is truncated");
+            match ppm[pos] {
+                b'#' => {
+                    while pos < ppm.len() && ppm[pos] != b'\n' {
+                        pos += 1;
+                    }
+                }
+                b' ' | b'\t' | b'\n' | b'\r' => pos += 1,
+                _ => break,
+            }
+        }
+        let start = pos;
+        while pos < ppm.len() && ppm[pos].is_ascii_digit() {
+            fields[field] = fields[field] * 10 + (ppm[pos] - b'0') as u32;
+            assert!(fields[field] <= u16::MAX as u32, "PPM header value out of range");
+            pos += 1;
+        }
+        assert!(pos > start, "PPM header is malformed");
+        field += 1;
+    }
+    // A single whitespace character ends the header
+    pos += 1;
+    let [width, height, max_value] = fields;
+    assert!(width > 0 && height > 0, "PPM image is empty");
+    assert!(max_value > 0 && max_value < 256, "only PPM files with up to 8 bits per channel are supported");
+    assert!(ppm.len() >= pos + width as usize * height as usize * 3, "PPM pixel data is truncated");
+    (width as u16, height as u16, max_value as u16, pos)
+}
+
+/// Channel `value` out of `max_value` scaled to 8 bits.
+const fn scale_channel(value: u8, max_value: u16) -> u8 {
+    if max_value == 255 { value } else { (value as u32 * 255 / max_value as u32) as u8 }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Write `image` with its upper-left corner at `x`, `y`. Returns whether it was drawn:
+    /// images at another color depth than the canvas are not.
+    pub fn draw_image(&mut self, x: u16, y: u16, image: &Image) -> Result<bool, CheckedError<I::Error>> {
+        if image.color_depth != self.config.color_depth {
+            return Ok(false);
+        }
+        self.write_image(Rect::new(x, y, image.width, image.height), image.data)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2x1 pixels, one red and one cyan at 4 bits per channel, under a header with comments
+    /// and every kind of whitespace.
+    const PPM: &[u8] = b"P6\n# written by hand\n  2\t1 # size\r\n#\n15\n\x0f\x00\x00\x00\x0f\x0f";
+    /// Headers are read at compile time.
+    const HEADER: (u16, u16, u16, usize) = ppm_header(PPM);
+
+    #[test]
+    fn headers_skip_comments_and_whitespace() {
+        assert_eq!(HEADER, (2, 1, 15, PPM.len() - 6));
+        assert_eq!(Image::ppm_size(b"P6 1 2 255 ......"), (1, 2));
+        assert_eq!(Image::ppm_size(b"P6#c\n1#c\n1#c\n255\t..."), (1, 1));
+    }
+
+    #[test]
+    fn channels_are_scaled_to_8_bits() {
+        let converted: [u8; 6] = Image::convert_ppm(PPM, ColorDepth::Bpp24);
+        let expected: [u8; 6] = Image::convert_rgb(&[255, 0, 0, 0, 255, 255], ColorDepth::Bpp24);
+        assert_eq!(converted, expected);
+        assert_eq!(Image::ppm_len(PPM, ColorDepth::Bpp16), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "PPM header is malformed")]
+    fn malformed_headers_are_refused() {
+        Image::ppm_size(b"P6\n4 x\n255\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "PPM header is truncated")]
+    fn truncated_headers_are_refused() {
+        Image::ppm_size(b"P6\n4 4\n# no maximum");
+    }
+
+    #[test]
+    #[should_panic(expected = "PPM pixel data is truncated")]
+    fn truncated_pixels_are_refused() {
+        Image::ppm_size(b"P6 2 2 255\n......");
+    }
+
+    #[test]
+    #[should_panic(expected = "not a binary PPM")]
+    fn other_formats_are_refused() {
+        Image::ppm_size(b"P3 1 1 255\n0 0 0");
+    }
+}
@@ -0,0 +1,170 @@
+//! What went wrong in [`LT7683::init`], to tell wiring and clock problems apart during
+//! bring-up, retrying init for modules that need more than one reset after power-up, and
+//! resetting a running chip without losing track of its state.
+
+use crate::registers::Register;
+use crate::{CheckedError, InitStage, LT7683, LT7683Interface, OutOfBounds, Rotation, WaitStrategy};
+use core::fmt;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+/// Part of the init sequence that was running when it failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitStep {
+    /// Hardware and software reset.
+    Reset,
+    /// Programming and starting the pixel, memory and core clock PLLs.
+    Pll,
+    /// SDRAM configuration and initialization.
+    Sdram,
+    /// Host bus, memory access and interrupt setup.
+    Registers,
+    /// Panel timing.
+    Timing,
+    /// Main window and canvas setup.
+    MainWindow,
+    /// Drawing the splash picture.
+    Splash,
+    /// Switching the display on.
+    DisplayOn,
+    /// Setting the backlight: dark before the splash picture, faded in once the display is
+    /// on, or back to its level after a soft reset.
+    Backlight,
+    /// The board's hook at the given stage.
+    Hook(InitStage),
+}
+
+/// Error of [`LT7683::init`] and its variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitError<E> {
+    /// The interface failed while `step` was running.
+    Bus { step: InitStep, error: E },
+    /// The PLLs did not report lock after being started.
+    PllLock,
+    /// The SDRAM did not report ready after initialization.
+    SdramNotReady,
+    /// `register` read back `read` after `written` was written to it.
+    RegisterVerify { register: Register, written: u8, read: u8 },
+    /// The splash picture reaches past the canvas.
+    OutOfBounds(OutOfBounds),
+    /// The memory write FIFO did not drain while the splash picture was written.
+    FifoTimeout,
+}
+
+impl<E> InitError<E> {
+    /// Wrap interface errors of `step`, for `map_err`.
+    pub(crate) fn at(step: InitStep) -> impl FnOnce(E) -> Self {
+        move |error| InitError::Bus { step, error }
+    }
+
+    /// Wrap errors of drawing calls made during `step`, for `map_err`.
+    pub(crate) fn drawing_at(step: InitStep) -> impl FnOnce(CheckedError<E>) -> Self {
+        move |error| match error {
+            CheckedError::Bus(error) => InitError::Bus { step, error },
+            CheckedError::OutOfBounds(error) => InitError::OutOfBounds(error),
+            CheckedError::FifoTimeout => InitError::FifoTimeout,
+        }
+    }
+
+    /// The interface error, if the failure was one.
+    pub fn bus_error(&self) -> Option<&E> {
+        match self {
+            InitError::Bus { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for InitError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InitError::Bus { step, error } => write!(f, "interface error during {step:?}: {error:?}"),
+            InitError::PllLock => f.write_str("PLLs did not lock"),
+            InitError::SdramNotReady => f.write_str("SDRAM did not become ready"),
+            InitError::RegisterVerify { register, written, read } => {
+                write!(f, "register {register:?} read back {read:#04x} after writing {written:#04x}")
+            }
+            InitError::OutOfBounds(error) => fmt::Display::fmt(error, f),
+            InitError::FifoTimeout => f.write_str("memory write FIFO did not drain"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for InitError<E> {}
+
+/// How often [`LT7683::init_with_retry`] runs the reset and init sequence before giving up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, at least one.
+    pub attempts: u8,
+    /// Pause before each retry, on top of the hardware reset's own delays.
+    pub backoff_ms: u32,
+}
+
+impl RetryPolicy {
+    pub const fn new(attempts: u8, backoff_ms: u32) -> Self {
+        Self { attempts, backoff_ms }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Two attempts, enough for modules that come up only after a second reset.
+    fn default() -> Self {
+        Self::new(2, 100)
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Run [`init`](Self::init), hardware reset included, until it succeeds or `policy` runs
+    /// out of attempts. Returns the number of attempts needed, or the last attempt's error.
+    pub fn init_with_retry<D: DelayNs>(&mut self, delay: &mut D, policy: RetryPolicy) -> Result<u8, InitError<I::Error>> {
+        let mut attempt = 1;
+        loop {
+            match self.init(delay) {
+                Ok(()) => return Ok(attempt),
+                Err(error) if attempt >= policy.attempts => return Err(error),
+                Err(_) => {
+                    attempt += 1;
+                    delay.delay_ms(policy.backoff_ms);
+                }
+            }
+        }
+    }
+
+    /// Reset the chip's registers with a software reset, wait for it to finish and program
+    /// the configuration again: clocks, SDRAM, color depth, panel timing, main window, the
+    /// canvas and active window in use and the backlight level, then switch the display on.
+    /// Rotation, origin and clip rectangle live in the driver and carry on unchanged. PIP
+    /// windows and the text engine have to be set up again by their users, and display memory
+    /// should be redrawn, as the SDRAM is initialized again.
+    pub fn soft_reset<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), InitError<I::Error>> {
+        let (canvas_address, canvas_width) = (self.canvas_address, self.canvas_image_width);
+        let canvas_height = self.canvas_image_height;
+        let (window, backlight) = (self.active_window, self.backlight_level);
+        let (rotation, origin) = (self.rotation, self.origin);
+        self.software_reset(delay).map_err(InitError::at(InitStep::Reset))?;
+        self.start_clocks_and_memory(delay)?;
+        // The main window setup is in canvas coordinates
+        self.set_rotation(Rotation::Deg0);
+        self.set_origin(0, 0);
+        let configured = self.configure_panel();
+        self.set_rotation(rotation);
+        self.set_origin(origin.0, origin.1);
+        configured?;
+        let restore = |display: &mut Self| {
+            display.set_canvas_address(canvas_address)?;
+            display.set_canvas_image_width(canvas_width)?;
+            display.canvas_image_height = canvas_height;
+            if let Some(window) = window {
+                display.set_active_window(window.x, window.y, window.width, window.height)?;
+            }
+            Ok(())
+        };
+        restore(self).map_err(InitError::at(InitStep::MainWindow))?;
+        if self.config.backlight.is_some() {
+            self.set_backlight(backlight).map_err(InitError::at(InitStep::Backlight))?;
+        }
+        // Display on
+        self.write_register(Register::Dpcr, 0xC0).map_err(InitError::at(InitStep::DisplayOn))
+    }
+}
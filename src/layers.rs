@@ -0,0 +1,197 @@
+//! Z-ordered layers over the main window and the two PIP windows. The background and the
+//! composited layers are off-screen images copied onto the framebuffer by BTE on
+//! [`Layers::commit`], bottom to top; the PIP windows are shown by the chip above all of them,
+//! PIP1 above PIP2.
+//!
+//! Layers are drawn in canvas coordinates, without the origin, rotation or clip rectangle.
+
+use crate::{CheckedError, DisplayConfig, LT7683, LT7683Interface, Pip, PipWindow, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// Canvas-sized image everything else is composited onto.
+    Background,
+    /// Image copied onto the background, numbered bottom to top.
+    Composited(u8),
+    Pip(PipWindow),
+}
+
+/// Image in display memory backing a layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Surface {
+    pub address: u32,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Surface {
+    pub const fn new(address: u32, width: u16, height: u16) -> Self {
+        Self { address, width, height }
+    }
+
+    /// Row length of the stored image, rounded up as images must be.
+    const fn stride(&self) -> u16 {
+        self.width.next_multiple_of(4)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    surface: Surface,
+    x: u16,
+    y: u16,
+    visible: bool,
+    /// Color left out when compositing, so the layers below show through.
+    chroma_key: Option<u32>,
+}
+
+/// Background, up to `N` composited layers and the PIP windows, with what has changed since
+/// the last commit.
+#[derive(Debug, Clone)]
+pub struct Layers<const N: usize> {
+    background: Surface,
+    composited: [Option<Placement>; N],
+    pips: [Option<(Pip, Placement)>; 2],
+    /// The framebuffer has to be composited again.
+    dirty: bool,
+    /// PIP windows have to be moved or shown again.
+    pips_dirty: bool,
+}
+
+impl<const N: usize> Layers<N> {
+    /// Layers over a background image at `background_address` the size of the canvas of
+    /// `config`, e.g. a [`RegionKind::BackBuffer`](crate::RegionKind::BackBuffer) region.
+    pub const fn new(background_address: u32, config: &DisplayConfig) -> Self {
+        Self {
+            background: Surface::new(background_address, config.canvas_width(), config.canvas_height()),
+            composited: [None; N],
+            pips: [None; 2],
+            dirty: true,
+            pips_dirty: false,
+        }
+    }
+
+    /// Back composited layer `index` with `surface`, placed at the canvas origin and visible.
+    /// Pixels of the `chroma_key` color let the layers below show through. Indices from `N`
+    /// on are ignored.
+    pub fn set_composited(&mut self, index: u8, surface: Surface, chroma_key: Option<u32>) {
+        if let Some(slot) = self.composited.get_mut(index as usize) {
+            *slot = Some(Placement { surface, x: 0, y: 0, visible: true, chroma_key });
+            self.dirty = true;
+        }
+    }
+
+    /// Manage `pip`, whose image is `surface`, as a layer. It starts hidden.
+    pub fn attach_pip(&mut self, pip: Pip, surface: Surface) {
+        let (x, y) = pip.position();
+        let placement = Placement { surface, x, y, visible: false, chroma_key: None };
+        self.pips[pip_index(pip.window())] = Some((pip, placement));
+        self.pips_dirty = true;
+    }
+
+    /// Move `layer` so its upper-left corner is at `x`, `y` on the canvas, or on screen for
+    /// PIP windows. The background does not move.
+    pub fn move_layer(&mut self, layer: Layer, x: u16, y: u16) {
+        if let Some(placement) = self.placement_mut(layer) {
+            (placement.x, placement.y) = (x, y);
+            self.mark_changed(layer);
+        }
+    }
+
+    pub fn set_visible(&mut self, layer: Layer, visible: bool) {
+        if let Some(placement) = self.placement_mut(layer) {
+            placement.visible = visible;
+            self.mark_changed(layer);
+        }
+    }
+
+    fn placement_mut(&mut self, layer: Layer) -> Option<&mut Placement> {
+        match layer {
+            Layer::Background => None,
+            Layer::Composited(index) => self.composited.get_mut(index as usize)?.as_mut(),
+            Layer::Pip(window) => self.pips[pip_index(window)].as_mut().map(|(_, placement)| placement),
+        }
+    }
+
+    fn surface(&self, layer: Layer) -> Option<Surface> {
+        match layer {
+            Layer::Background => Some(self.background),
+            Layer::Composited(index) => self.composited.get(index as usize)?.map(|placement| placement.surface),
+            Layer::Pip(window) => self.pips[pip_index(window)].map(|(_, placement)| placement.surface),
+        }
+    }
+
+    fn mark_changed(&mut self, layer: Layer) {
+        match layer {
+            Layer::Pip(_) => self.pips_dirty = true,
+            _ => self.dirty = true,
+        }
+    }
+
+    /// Run `draw` with the canvas moved to the image of `layer`. Layers without an image are
+    /// skipped. PIP windows show their image directly; other layers appear on the next
+    /// [`commit`](Self::commit).
+    pub fn draw_on<I, RESET, W, F>(&mut self, display: &mut LT7683<I, RESET, W>, layer: Layer, draw: F) -> Result<(), CheckedError<I::Error>>
+    where
+        I: LT7683Interface,
+        RESET: OutputPin,
+        W: WaitStrategy,
+        F: FnOnce(&mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>>,
+    {
+        let Some(surface) = self.surface(layer) else {
+            return Ok(());
+        };
+        display.draw_offscreen(surface.address, surface.stride(), surface.height, draw)?;
+        self.mark_changed(layer);
+        Ok(())
+    }
+
+    /// Bring the screen up to date: composite the background and the visible composited
+    /// layers onto the framebuffer, bottom to top, if any of them changed, then move and show
+    /// or hide the PIP windows. Call after [`wait_vsync`](LT7683::wait_vsync) to avoid
+    /// tearing.
+    pub fn commit<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        if self.dirty {
+            let background = self.background;
+            let composited = self.composited;
+            let (canvas_width, canvas_height) = (display.config.canvas_width(), display.config.canvas_height());
+            display.with_physical_coordinates(|display| -> Result<(), CheckedError<I::Error>> {
+                let source = (background.address, background.stride());
+                display.bte_copy(source, 0, 0, 0, 0, background.width, background.height, None)?;
+                for placement in composited.iter().flatten().filter(|placement| placement.visible) {
+                    let surface = placement.surface;
+                    // Only the part on the canvas is copied
+                    let width = surface.width.min(canvas_width.saturating_sub(placement.x));
+                    let height = surface.height.min(canvas_height.saturating_sub(placement.y));
+                    if width == 0 || height == 0 {
+                        continue;
+                    }
+                    let source = (surface.address, surface.stride());
+                    display.bte_copy(source, 0, 0, placement.x, placement.y, width, height, placement.chroma_key)?;
+                }
+                Ok(())
+            })?;
+            self.dirty = false;
+        }
+        if self.pips_dirty {
+            let pips = &mut self.pips;
+            display.with_physical_coordinates(|display| -> Result<(), I::Error> {
+                for (pip, placement) in pips.iter_mut().flatten() {
+                    pip.set_position(display, placement.x, placement.y)?;
+                    pip.show(display, placement.visible)?;
+                }
+                Ok(())
+            })?;
+            self.pips_dirty = false;
+        }
+        Ok(())
+    }
+}
+
+const fn pip_index(window: PipWindow) -> usize {
+    match window {
+        PipWindow::Pip1 => 0,
+        PipWindow::Pip2 => 1,
+    }
+}
@@ -1,19 +1,154 @@
 #![no_std]
 #![deny(unsafe_code)]
 
+mod ambient;
+mod assets;
+#[cfg(feature = "graphics")]
+mod animation;
+mod bench;
+mod bounds;
+mod clock;
+mod clip;
+mod curve;
+mod diagnostics;
+mod differ;
+mod fade;
+mod flash;
+mod font;
+mod frames;
+#[cfg(feature = "gif")]
+mod gif;
+mod hatch;
+mod icons;
+mod image;
+mod init;
+mod layers;
+mod low_power;
+#[cfg(feature = "graphics")]
+mod graphics;
+mod ll;
+mod marquee;
+mod math;
+mod memory_map;
+mod mmio;
+mod number;
+mod origin;
+mod parallel;
+mod pip;
+mod pwm;
+#[cfg(feature = "qrcode")]
+mod qrcode;
+pub mod raw;
 mod registers;
+mod rotation;
+#[cfg(feature = "shadow")]
+mod shadow;
+mod shared;
+mod spi_flash;
+mod sprite;
+mod text;
+mod touch;
+#[cfg(feature = "trace")]
+mod trace;
+#[cfg(feature = "widgets")]
+mod trig;
+mod wait;
+#[cfg(feature = "widgets")]
+pub mod widgets;
 
+use crate::math::{PllConfig, crc32_update, horizontal_period, image_bytes, sdram_refresh_interval, split_u16, vertical_period};
 use crate::registers::Register;
+#[cfg(not(feature = "bench"))]
+use crate::bench::BenchOp;
+#[cfg(feature = "bench")]
+use crate::bench::Bench;
+use crate::ambient::AutoBacklight;
+use crate::bounds::{ellipse_area, span};
+use crate::clip::clip_line;
+use crate::low_power::LowPowerRestore;
+use crate::text::wrap_line;
+pub use crate::assets::{Asset, AssetKind, AssetStore};
+#[cfg(feature = "bench")]
+pub use crate::bench::{BenchClock, BenchOp, BenchSample};
+pub use crate::bounds::{CheckedError, OutOfBounds};
+pub use crate::diagnostics::SelfTestReport;
+pub use crate::differ::FrameDiffer;
+pub use crate::flash::{FlashReadMode, FlashTarget};
+pub use crate::font::{BitmapFont, GlyphCache};
+pub use crate::frames::FrameCounters;
+#[cfg(feature = "gif")]
+pub use crate::gif::{Gif, GifFrame, GifFrames};
+pub use crate::hatch::Hatch;
+pub use crate::icons::{Icon, IconSize};
+pub use crate::image::Image;
+pub use crate::init::{InitError, InitStep, RetryPolicy};
+pub use crate::layers::{Layer, Layers, Surface};
+pub use crate::ll::Lt7683Ll;
+pub use crate::low_power::LowPowerConfig;
+pub use crate::marquee::Marquee;
+pub use crate::memory_map::{MemoryMap, Region, RegionKind};
+pub use crate::mmio::{MmioInterface, MmioPort};
+pub use crate::number::NumberBuffer;
+pub use crate::parallel::{BusTiming, NoDelay, NoPin, ParallelBus, ParallelBusDevice, ParallelBusError, ParallelPort};
+pub use crate::pip::{Pip, PipConfig, PipRegisters, PipWindow};
+pub use crate::pwm::{BacklightConfig, PwmChannel};
+pub use crate::rotation::Rotation;
+#[cfg(feature = "shadow")]
+pub use crate::shadow::RegisterShadow;
+pub use crate::shared::SharedInterface;
+pub use crate::spi_flash::{FlashError, FlashStatus};
+pub use crate::sprite::SpriteAtlas;
+pub use crate::text::{Charset, ExternalFont, FontRom, FontSize, FontWidth, FontWidths, RomEncoding, TextAlign, TextStyle, UserFont, WidthTable};
+pub use crate::touch::TouchCalibration;
+#[cfg(feature = "trace")]
+pub use crate::trace::{TraceEvent, TraceInterface};
+pub use crate::wait::{FixedDelay, InterruptPin, Polling, WaitEvent, WaitStrategy};
+#[cfg(feature = "png")]
+#[doc(hidden)]
+pub use lt7683_macros::include_image as __include_image;
 use embedded_hal::delay::DelayNs;
 use embedded_hal::digital::OutputPin;
-use embedded_hal::spi::SpiDevice;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+/// Bytes the memory write FIFO takes before it has to drain.
+const MEMORY_WRITE_FIFO_DEPTH: usize = 16;
+/// Status reads allowed for the memory write FIFO to drain before a burst gives up.
+const FIFO_DRAIN_POLLS: u32 = 1000;
+/// Size of the embedded display memory (128 Mbit SDRAM).
+const SDRAM_BYTES: u32 = 16 * 1024 * 1024;
+/// Time allowed for SDRAM initialization when the ready flag is not read.
+const WRITE_ONLY_SDRAM_READY_MS: u32 = 50;
+/// Time allowed for a software reset or power saving exit when the status is not read.
+const WRITE_ONLY_RESET_MS: u32 = 20;
+/// Largest panel width or height the timing registers can describe.
+const MAX_PANEL_SIZE: u16 = 2048;
 
 pub trait LT7683Interface {
     type Error;
     fn write_command(&mut self, register: Register) -> Result<(), Self::Error>;
     fn write_data(&mut self, data: u8) -> Result<(), Self::Error>;
-    fn read_data(&mut self) -> Result<u8, Self::Error>;
-    fn read_status(&mut self) -> Result<u8, Self::Error>;
+
+    /// Data read cycle. Interfaces without a read path, e.g. SPI without MISO, can leave the
+    /// reads out; they then read as 0, and the `write-only` feature keeps the driver from
+    /// relying on them.
+    fn read_data(&mut self) -> Result<u8, Self::Error> {
+        Ok(0)
+    }
+
+    /// Status read cycle, left out like [`read_data`](Self::read_data) where there is no read
+    /// path.
+    fn read_status(&mut self) -> Result<u8, Self::Error> {
+        Ok(0)
+    }
+
+    /// Write several data bytes to the selected register, e.g. pixels to the memory data port.
+    /// Interfaces that can stream without per-byte overhead should override this.
+    fn write_data_burst(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        for &byte in data {
+            self.write_data(byte)?;
+        }
+        Ok(())
+    }
 }
 
 pub struct DisplayConfig {
@@ -26,11 +161,59 @@ pub struct DisplayConfig {
     pub v_front_porch: u16,
     pub v_sync_width: u16,
     pub color_depth: ColorDepth,
+    /// Width of the image drawn into, if wider than the panel (`width`). The panel shows the
+    /// part selected with [`LT7683::pan_to`].
+    pub canvas_width: Option<u16>,
+    /// Height of the image drawn into, if taller than the panel (`height`).
+    pub canvas_height: Option<u16>,
+    /// Display memory address of the canvas, which is also the main image shown on the panel.
+    /// See [`MemoryMap`] for placing other images around it.
+    pub framebuffer_address: u32,
+    /// Clear areas with the BTE engine, which fills large areas much faster than the drawing
+    /// engine.
+    pub bte_fill: bool,
+    /// Wait for the memory write FIFO to drain between chunks of burst writes. Needed on buses
+    /// fast enough to overrun the controller; SPI is not. Has no effect in write-only builds.
+    pub fifo_flow_control: bool,
+    /// Backlight on the chip's PWM0 output, faded in by `init` once the screen is cleared and
+    /// faded out before power saving. `None` leaves PWM0 alone.
+    pub backlight: Option<BacklightConfig>,
+    /// Refresh rate, backlight level and clocks of
+    /// [`low_power_display_mode`](LT7683::low_power_display_mode).
+    pub low_power: LowPowerConfig,
+    /// Shown by `init` as soon as the panel timing is set up, before the display is switched
+    /// on. For anything else draw from the [`InitStage::BeforeDisplayOn`] hook of
+    /// [`LT7683::init_with_hook`].
+    pub splash: Option<Splash>,
+    /// Chip select of the serial flash [`copy_from_flash`](LT7683::copy_from_flash) and the
+    /// flash splash image read from.
+    pub flash_target: FlashTarget,
+    /// Read command for copying from the serial flash; faster modes need a flash chip that
+    /// supports them.
+    pub flash_read_mode: FlashReadMode,
+    /// Crystal frequency feeding the PLLs.
+    pub oscillator_hz: u32,
+    /// Pixel clock (SCLK) driving the panel.
+    pub pixel_clock_hz: u32,
+    /// SDRAM clock (MCLK).
+    pub memory_clock_hz: u32,
+    /// Core clock (CCLK).
+    pub core_clock_hz: u32,
 }
 
 impl Default for DisplayConfig {
-    /// Default config for 7" 1024x600 display (ER-TFT070A2-6-5633).
     fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The defaults must pass the checks users rely on
+const _: DisplayConfig = DisplayConfig::new().validate();
+
+impl DisplayConfig {
+    /// Default config for 7" 1024x600 display (ER-TFT070A2-6-5633).
+    /// Usable in constants, e.g. `DisplayConfig { width: 800, ..DisplayConfig::new() }`.
+    pub const fn new() -> Self {
         Self {
             width: 1024,
             height: 600,
@@ -41,65 +224,270 @@ impl Default for DisplayConfig {
             v_front_porch: 12,
             v_sync_width: 10,
             color_depth: ColorDepth::Bpp16,
+            canvas_width: None,
+            canvas_height: None,
+            framebuffer_address: 0,
+            bte_fill: true,
+            fifo_flow_control: false,
+            backlight: None,
+            low_power: LowPowerConfig::new(),
+            splash: None,
+            flash_target: FlashTarget::Cs0,
+            flash_read_mode: FlashReadMode::Normal,
+            oscillator_hz: 10_000_000,
+            pixel_clock_hz: 50_000_000,
+            memory_clock_hz: 100_000_000,
+            core_clock_hz: 100_000_000,
+        }
+    }
+
+    pub const fn canvas_width(&self) -> u16 {
+        match self.canvas_width {
+            Some(width) if width > self.width => width,
+            _ => self.width,
         }
     }
+
+    pub const fn canvas_height(&self) -> u16 {
+        match self.canvas_height {
+            Some(height) if height > self.height => height,
+            _ => self.height,
+        }
+    }
+
+    /// Panel refresh rate in millihertz, from the pixel clock and the panel timing.
+    pub const fn refresh_rate_millihz(&self) -> u32 {
+        match (self.pixel_clock_hz as u64 * 1000).checked_div(self.frame_pixels()) {
+            Some(rate) => rate as u32,
+            None => 0,
+        }
+    }
+
+    /// Pixel clock periods per frame, porches and sync pulses included.
+    const fn frame_pixels(&self) -> u64 {
+        let columns = self.width as u64 + self.h_back_porch as u64 + self.h_front_porch as u64 + self.h_sync_width as u64;
+        let lines = self.height as u64 + self.v_back_porch as u64 + self.v_front_porch as u64 + self.v_sync_width as u64;
+        columns * lines
+    }
+
+    /// Display memory taken by the canvas, in bytes.
+    pub const fn framebuffer_bytes(&self) -> u32 {
+        image_bytes(self.canvas_width(), self.canvas_height(), self.color_depth.bytes_per_pixel())
+    }
+
+    /// Panic unless the chip can drive this configuration. Evaluated in a `const` item the
+    /// check happens at compile time, so a bad configuration does not build:
+    ///
+    /// ```ignore
+    /// const CONFIG: DisplayConfig = DisplayConfig { width: 800, height: 480, ..DisplayConfig::new() }.validate();
+    /// ```
+    pub const fn validate(self) -> Self {
+        assert!(self.width > 0 && self.width <= MAX_PANEL_SIZE, "panel width out of range");
+        assert!(self.height > 0 && self.height <= MAX_PANEL_SIZE, "panel height out of range");
+        assert!(self.h_back_porch >= 8 && self.h_back_porch <= 2048, "horizontal back porch out of range");
+        assert!(self.h_front_porch >= 8 && self.h_front_porch <= 2048, "horizontal front porch out of range");
+        assert!(self.h_sync_width >= 8 && self.h_sync_width <= 2048, "horizontal sync width out of range");
+        assert!(self.v_back_porch > 0 && self.v_back_porch <= 1024, "vertical back porch out of range");
+        assert!(self.v_front_porch > 0 && self.v_front_porch <= 256, "vertical front porch out of range");
+        assert!(self.v_sync_width > 0 && self.v_sync_width <= 256, "vertical sync width out of range");
+        assert!(self.canvas_width().is_multiple_of(4), "canvas width must be a multiple of 4");
+        assert!(self.framebuffer_address.is_multiple_of(4), "framebuffer address must be a multiple of 4");
+        assert!(
+            self.framebuffer_address as u64 + self.framebuffer_bytes() as u64 <= SDRAM_BYTES as u64,
+            "canvas does not fit in display memory"
+        );
+        self
+    }
 }
 
-impl DisplayConfig {
-    pub fn new() -> Self {
-        Self::default()
+/// Rectangular area in pixels, given by its upper-left corner and size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self { x, y, width, height }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// The area covered by both rectangles, `None` if they do not overlap.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let left = self.x.max(other.x);
+        let top = self.y.max(other.y);
+        let right = (self.x as u32 + self.width as u32).min(other.x as u32 + other.width as u32);
+        let bottom = (self.y as u32 + self.height as u32).min(other.y as u32 + other.height as u32);
+        if right <= left as u32 || bottom <= top as u32 {
+            return None;
+        }
+        Some(Rect::new(left, top, (right - left as u32) as u16, (bottom - top as u32) as u16))
+    }
+
+    /// Position of the lower-right pixel of a non-empty rectangle, clamped to the coordinate
+    /// range.
+    pub(crate) const fn last_pixel(&self) -> (u16, u16) {
+        let right = self.x as u32 + self.width as u32 - 1;
+        let bottom = self.y as u32 + self.height as u32 - 1;
+        let max = u16::MAX as u32;
+        ((if right > max { max } else { right }) as u16, (if bottom > max { max } else { bottom }) as u16)
+    }
+
+    /// Whether the point `x`, `y` lies inside, e.g. for matching touches to on-screen controls.
+    pub const fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.x && y >= self.y && x - self.x < self.width && y - self.y < self.height
     }
 }
 
-pub struct LT7683<I: LT7683Interface, RESET> {
-    pub spi_interface: I,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// From the left edge (`from_color`) to the right edge (`to_color`).
+    Horizontal,
+    /// From the top edge (`from_color`) to the bottom edge (`to_color`).
+    Vertical,
+}
+
+/// High-level driver. Drawing state the chip keeps (colors, active window, graphic cursor, line
+/// coordinates) is cached to skip redundant register writes; after changing those registers
+/// through `ll`, call [`invalidate_cache`](Self::invalidate_cache).
+pub struct LT7683<I: LT7683Interface, RESET, W = Polling> {
+    pub ll: Lt7683Ll<I>,
     pub res: RESET,
     pub config: DisplayConfig,
+    pub wait_strategy: W,
     last_fg: Option<u32>,
     last_bg: Option<u32>,
+    charset: Charset,
+    font_size: FontSize,
+    text_mode: bool,
+    active_window: Option<Rect>,
+    graphic_cursor: Option<(u16, u16)>,
+    line_points: [Option<u16>; 4],
+    memory_direction: Option<MemoryDirection>,
+    sdram_powered_down: bool,
+    backlight_level: u8,
+    /// Display memory address and width of the image drawing and BTE operations write to.
+    canvas_address: u32,
+    canvas_image_width: u16,
+    /// Height of that image, which the chip does not need but the bounds checks do.
+    canvas_image_height: u16,
+    rotation: Rotation,
+    color_correction: Option<&'static ColorCorrection>,
+    clip: Option<Rect>,
+    /// Offset added to drawing coordinates, see `set_origin`.
+    origin: (u16, u16),
+    frame_counters: Option<FrameCounters>,
+    /// Settings to go back to when leaving low power display mode, while in it.
+    low_power: Option<LowPowerRestore>,
+    /// Advances of the proportional external font, see `set_font_widths`.
+    font_widths: Option<FontWidths>,
+    auto_backlight: Option<AutoBacklight>,
+    #[cfg(feature = "bench")]
+    bench: Bench,
 }
 
 impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
+    /// Create a driver that polls the chip while waiting for the engines.
     pub fn new(spi_interface: I, res: RESET, display_config: DisplayConfig) -> Self {
+        Self::with_wait_strategy(spi_interface, res, display_config, Polling::new())
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Create a driver that waits for the engines using `wait_strategy`.
+    pub fn with_wait_strategy(spi_interface: I, res: RESET, display_config: DisplayConfig, wait_strategy: W) -> Self {
         Self {
-            spi_interface,
+            ll: Lt7683Ll::new(spi_interface),
             res,
             config: display_config,
+            wait_strategy,
             last_fg: None,
             last_bg: None,
+            charset: Charset::default(),
+            font_size: FontSize::default(),
+            text_mode: false,
+            active_window: None,
+            graphic_cursor: None,
+            line_points: [None; 4],
+            memory_direction: None,
+            sdram_powered_down: false,
+            backlight_level: 0,
+            canvas_address: 0,
+            canvas_image_width: 0,
+            canvas_image_height: 0,
+            rotation: Rotation::Deg0,
+            color_correction: None,
+            clip: None,
+            origin: (0, 0),
+            frame_counters: None,
+            low_power: None,
+            font_widths: None,
+            auto_backlight: None,
+            #[cfg(feature = "bench")]
+            bench: Bench::default(),
         }
     }
 
+    /// Bus interface, formerly the public `spi_interface` field.
+    #[deprecated(note = "use `ll.interface`")]
+    pub fn spi_interface(&mut self) -> &mut I {
+        &mut self.ll.interface
+    }
+
     pub fn write_register(&mut self, register: Register, data: u8) -> Result<(), I::Error> {
-        self.spi_interface.write_command(register)?;
-        self.spi_interface.write_data(data)?;
-        Ok(())
+        self.ll.write_register(register, data)
     }
 
     pub fn read_register(&mut self, register: Register) -> Result<u8, I::Error> {
-        self.spi_interface.write_command(register)?;
-        self.spi_interface.read_data()
-    }
-
-    /// Read the status register.
-    /// Bit 7: Memory read/write busy
-    /// Bit 6: BTE busy
-    /// Bit 5: DMA busy
-    /// Bit 4: Text engine busy
-    /// Bit 3: Core busy
-    /// Bit 2: SDRAM ready (1 = ready)
-    /// Bit 1: reserved
-    /// Bit 0: Operation mode status
+        self.ll.read_register(register)
+    }
+
+    /// Update only the bits of `register` selected by `mask`. With the `shadow` feature the
+    /// current value is taken from the shadow when known, otherwise it is read from the chip.
+    /// Write-only builds leave a register the shadow has no value for untouched.
+    pub fn modify_register(&mut self, register: Register, mask: u8, value: u8) -> Result<(), I::Error> {
+        self.ll.modify_register(register, mask, value)
+    }
+
+    /// Values written to the control registers so far.
+    /// The shadow survives resets, so it keeps describing the configured state.
+    #[cfg(feature = "shadow")]
+    pub fn shadow(&self) -> &RegisterShadow {
+        self.ll.shadow()
+    }
+
+    /// Write every recorded register value back to the chip, in address order, and drop the
+    /// cached drawing state. Operations such as SDRAM initialization or power mode changes are
+    /// not replayed.
+    #[cfg(feature = "shadow")]
+    pub fn restore_registers(&mut self) -> Result<(), I::Error> {
+        self.invalidate_cache();
+        self.ll.restore_registers()
+    }
+
+    /// Read the status register. The bits are listed in [`raw::status`].
     pub fn read_status(&mut self) -> Result<u8, I::Error> {
-        self.spi_interface.read_status()
+        self.ll.read_status()
     }
 
     pub fn is_sdram_ready(&mut self) -> Result<bool, I::Error> {
         let status = self.read_status()?;
-        Ok((status & 0x04) != 0) // Bit 2 = SDRAM ready
+        Ok((status & raw::status::SDRAM_READY) != 0)
     }
 
+    /// Wait up to a second for the SDRAM to report ready, returning whether it did.
+    /// Write-only builds do not poll and wait long enough for it to be ready instead.
     pub fn wait_sdram_ready<D: DelayNs>(&mut self, delay: &mut D) -> Result<bool, I::Error> {
+        if cfg!(feature = "write-only") {
+            delay.delay_ms(WRITE_ONLY_SDRAM_READY_MS);
+            return Ok(true);
+        }
         for _ in 0..100 {
             if self.is_sdram_ready()? {
                 return Ok(true);
@@ -114,19 +502,22 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         delay.delay_ms(10);
         let _ = self.res.set_high();
         delay.delay_ms(100);
-        self.last_fg = None;
-        self.last_bg = None;
+        self.invalidate_cache();
         Ok(())
     }
 
     pub fn software_reset<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
         self.write_register(Register::Srr, 0x01)?;
+        if cfg!(feature = "write-only") {
+            delay.delay_ms(WRITE_ONLY_RESET_MS);
+            self.invalidate_cache();
+            return Ok(());
+        }
         // Wait for reset to complete (bit 0 clears when done)
         for _ in 0..100 {
             let val = self.read_register(Register::Srr)?;
             if (val & 0x01) == 0 {
-                self.last_fg = None;
-                self.last_bg = None;
+                self.invalidate_cache();
                 return Ok(());
             }
             delay.delay_ms(1);
@@ -134,6 +525,18 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
+    /// Forget everything the driver assumes about register contents, after the chip was reset
+    /// or its registers were written behind the driver's back.
+    pub fn invalidate_cache(&mut self) {
+        self.last_fg = None;
+        self.last_bg = None;
+        self.text_mode = false;
+        self.active_window = None;
+        self.graphic_cursor = None;
+        self.line_points = [None; 4];
+        self.memory_direction = None;
+    }
+
     pub fn init_color_bar_test<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
         self.hardware_reset(delay)?;
         self.software_reset(delay)?;
@@ -148,120 +551,323 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
-    pub fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
-        self.hardware_reset(delay)?;
-        self.software_reset(delay)?;
-        self.configure_pll(delay)?;
-        self.configure_sdram(delay)?;
-        self.wait_sdram_ready(delay)?;
+    /// Reset the chip and bring the display up. Errors tell which step failed, see
+    /// [`InitError`].
+    pub fn init<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), InitError<I::Error>> {
+        self.init_with_hook(delay, |_, _, _| Ok(()))
+    }
+
+    /// Like [`init`](Self::init), calling `hook` at each [`InitStage`] so boards can insert
+    /// their own steps, such as switching on panel power rails before the display is enabled.
+    pub fn init_with_hook<D, F>(&mut self, delay: &mut D, mut hook: F) -> Result<(), InitError<I::Error>>
+    where
+        D: DelayNs,
+        F: FnMut(&mut Self, &mut D, InitStage) -> Result<(), I::Error>,
+    {
+        let mut hook = |display: &mut Self, delay: &mut D, stage| {
+            hook(display, delay, stage).map_err(InitError::at(InitStep::Hook(stage)))
+        };
+        self.hardware_reset(delay).map_err(InitError::at(InitStep::Reset))?;
+        self.software_reset(delay).map_err(InitError::at(InitStep::Reset))?;
+        hook(self, delay, InitStage::Reset)?;
+        self.start_clocks_and_memory(delay)?;
+        hook(self, delay, InitStage::ClocksAndMemory)?;
+        self.configure_panel()?;
+        if self.config.backlight.is_some() {
+            self.set_backlight(0).map_err(InitError::at(InitStep::Backlight))?;
+        }
+        self.show_splash().map_err(InitError::drawing_at(InitStep::Splash))?;
+        hook(self, delay, InitStage::BeforeDisplayOn)?;
+        // Display on
+        self.write_register(Register::Dpcr, 0xC0).map_err(InitError::at(InitStep::DisplayOn))?;
+        hook(self, delay, InitStage::DisplayOn)?;
+        self.fade_in_backlight(delay).map_err(InitError::at(InitStep::Backlight))
+    }
+
+    /// Start the PLLs and initialize the SDRAM, checking that both come up.
+    fn start_clocks_and_memory<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), InitError<I::Error>> {
+        self.configure_pll(delay).map_err(InitError::at(InitStep::Pll))?;
+        if !self.wait_pll_lock(delay).map_err(InitError::at(InitStep::Pll))? {
+            return Err(InitError::PllLock);
+        }
+        self.configure_sdram(delay).map_err(InitError::at(InitStep::Sdram))?;
+        if !self.wait_sdram_ready(delay).map_err(InitError::at(InitStep::Sdram))? {
+            return Err(InitError::SdramNotReady);
+        }
+        Ok(())
+    }
+
+    /// Program everything from the host bus to the main window for the configuration, with
+    /// the display still off.
+    fn configure_panel(&mut self) -> Result<(), InitError<I::Error>> {
+        self.configure_registers().map_err(InitError::at(InitStep::Registers))?;
+        self.configure_display_timing().map_err(InitError::at(InitStep::Timing))?;
+        if !cfg!(feature = "write-only") {
+            self.verify_registers(&self.display_timing())?;
+        }
+        // HSYNC high active, VSYNC high active, DE high active
+        self.write_register(Register::Pcsr, 0xC0).map_err(InitError::at(InitStep::Timing))?;
+        self.configure_main_window().map_err(InitError::at(InitStep::MainWindow))
+    }
+
+    /// Host bus, memory access and interrupt setup, between the clocks and the panel timing.
+    fn configure_registers(&mut self) -> Result<(), I::Error> {
         // TFT 24-bit output, SPI flash enabled, 8-bit host bus
         self.write_register(Register::Ccr, 0x80)?;
         // Memory access color depth
-        // NOTE: Memory Store Direction is hardcoded as original (00b).
+        // NOTE: Memory Store Direction starts as original (00b), see set_memory_write_direction.
         // See: 5.4 Image Rotate and Mirror in https://www.buydisplay.com/download/ic/LT7683.pdf
         self.write_register(Register::Macr, (self.config.color_depth as u8) << 6)?;
+        self.memory_direction = Some(MemoryDirection::LeftRightTopDown);
         // Graphic mode, SDRAM memory
         self.write_register(Register::Icr, 0x00)?;
-        self.configure_display_timing()?;
-        // HSYNC high active, VSYNC high active, DE high active
-        self.write_register(Register::Pcsr, 0xC0)?;
-        self.configure_main_window()?;
-        // Display on
-        self.write_register(Register::Dpcr, 0xC0)?;
+        self.configure_interrupts()
+    }
+
+    /// Read back `written` register values, failing on the first that differs. A register
+    /// that reads back wrong points at the bus wiring or timing rather than the chip setup.
+    fn verify_registers(&mut self, written: &[(Register, u8)]) -> Result<(), InitError<I::Error>> {
+        for &(register, value) in written {
+            let read = self.read_register(register).map_err(InitError::at(InitStep::Timing))?;
+            if read != value {
+                return Err(InitError::RegisterVerify { register, written: value, read });
+            }
+        }
         Ok(())
+    }
 
+    fn show_splash(&mut self) -> Result<(), CheckedError<I::Error>> {
+        let (width, height) = (self.config.width, self.config.height);
+        match self.config.splash {
+            Some(Splash::Color(color)) => self.clear_screen(color),
+            Some(Splash::FlashImage { address }) => self.copy_from_flash(address, Rect::new(0, 0, width, height), width),
+            // Keep the backlight off until the SDRAM noise is cleared
+            None if self.config.backlight.is_some() => self.clear_screen(0x000000),
+            None => Ok(()),
+        }
     }
 
-    fn configure_pll<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
-        self.write_register(Register::Ppllc1, 0x06)?;
-        self.write_register(Register::Ppllc2, 39)?;
+    fn fade_in_backlight<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
+        match self.config.backlight {
+            Some(backlight) => self.fade_backlight(backlight.level, backlight.fade_ms as u32, delay),
+            None => Ok(()),
+        }
+    }
 
-        self.write_register(Register::Mpllc1, 0x04)?;
-        self.write_register(Register::Mpllc2, 39)?;
+    fn fade_out_backlight<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
+        match self.config.backlight {
+            Some(backlight) => self.fade_backlight(0, backlight.fade_ms as u32, delay),
+            None => Ok(()),
+        }
+    }
 
-        self.write_register(Register::Cpllc1, 0x04)?;
-        self.write_register(Register::Cpllc2, 39)?;
+    fn configure_pll<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
+        let clocks = [
+            (Register::Ppllc1, Register::Ppllc2, self.config.pixel_clock_hz),
+            (Register::Mpllc1, Register::Mpllc2, self.config.memory_clock_hz),
+            (Register::Cpllc1, Register::Cpllc2, self.config.core_clock_hz),
+        ];
+        for (control1, control2, target_hz) in clocks {
+            // Only an oscillator far outside the VCO range has no setting; keep the PLL at
+            // the 10 MHz crystal defaults then
+            let pll = PllConfig::compute(self.config.oscillator_hz, target_hz).unwrap_or(PllConfig { k: 2, m: 0, n: 39 });
+            self.write_register(control1, pll.control1())?;
+            self.write_register(control2, pll.n)?;
+        }
+        self.restart_plls(delay)
+    }
 
-        // Activate PLLs
+    /// Make the PLLs pick up their new settings. Clearing and setting CCR restarts all three
+    /// PLLs, so the panel output and memory stop until they lock again.
+    fn restart_plls<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
         self.write_register(Register::Ccr, 0x00)?;
         delay.delay_us(10);
-        self.write_register(Register::Ccr, 0x80)?;
+        self.write_register(Register::Ccr, raw::ccr::PLL_READY)?;
         delay.delay_ms(1);
         Ok(())
     }
 
+    /// Wait up to 10 ms for the PLLs to report lock, returning whether they did. Write-only
+    /// builds cannot check; `configure_pll` already waited for the lock.
+    fn wait_pll_lock<D: DelayNs>(&mut self, delay: &mut D) -> Result<bool, I::Error> {
+        if cfg!(feature = "write-only") {
+            return Ok(true);
+        }
+        for _ in 0..10 {
+            if self.read_register(Register::Ccr)? & raw::ccr::PLL_READY != 0 {
+                return Ok(true);
+            }
+            delay.delay_ms(1);
+        }
+        Ok(false)
+    }
+
+    /// Put the chip into a power saving mode. In `Suspend` and `Sleep` the SDRAM is put into
+    /// `memory` mode first; [`MemoryRetention::PowerDown`] saves more power but loses the
+    /// display memory contents. A configured backlight is faded out first.
+    pub fn enter_power_mode<D: DelayNs>(&mut self, mode: PowerMode, memory: MemoryRetention, delay: &mut D) -> Result<(), I::Error> {
+        self.fade_out_backlight(delay)?;
+        self.enter_power_saving(mode, memory)
+    }
+
+    /// Wake the chip from a power saving mode. SDRAM that was powered down is initialized again,
+    /// with its contents undefined. A configured backlight is faded back in.
+    pub fn exit_power_mode<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
+        self.exit_power_saving(delay)?;
+        self.fade_in_backlight(delay)
+    }
+
+    /// Lowest power state for displays that are off most of the time: backlight faded out,
+    /// panel output off, SDRAM in `memory` mode and the chip asleep with its clocks stopped.
+    /// Leave it with [`wake_from_deep_power_down`](Self::wake_from_deep_power_down).
+    pub fn deep_power_down<D: DelayNs>(&mut self, memory: MemoryRetention, delay: &mut D) -> Result<(), I::Error> {
+        self.fade_out_backlight(delay)?;
+        // Display off
+        self.modify_register(Register::Dpcr, 0x40, 0x00)?;
+        self.enter_power_saving(PowerMode::Sleep, memory)
+    }
+
+    /// Wake from [`deep_power_down`](Self::deep_power_down): restart the PLLs, bring the SDRAM
+    /// back, switch the panel on and fade the backlight in.
+    pub fn wake_from_deep_power_down<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
+        self.write_register(Register::Pmu, 0x00)?;
+        self.wait_power_saving_exit(delay)?;
+        self.configure_pll(delay)?;
+        self.exit_sdram_power_saving(delay)?;
+        // Display on
+        self.modify_register(Register::Dpcr, 0x40, 0x40)?;
+        self.fade_in_backlight(delay)
+    }
+
+    fn enter_power_saving(&mut self, mode: PowerMode, memory: MemoryRetention) -> Result<(), I::Error> {
+        if mode != PowerMode::Standby {
+            let sdrcr = match memory {
+                MemoryRetention::SelfRefresh => raw::sdrcr::POWER_SAVING,
+                MemoryRetention::PowerDown => raw::sdrcr::POWER_SAVING | raw::sdrcr::POWER_DOWN,
+            };
+            self.write_register(Register::Sdrcr, sdrcr)?;
+            self.sdram_powered_down = memory == MemoryRetention::PowerDown;
+        }
+        self.write_register(Register::Pmu, raw::pmu::ENTER | mode as u8)
+    }
+
+    fn exit_power_saving<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
+        self.write_register(Register::Pmu, 0x00)?;
+        self.wait_power_saving_exit(delay)?;
+        self.exit_sdram_power_saving(delay)
+    }
+
+    fn wait_power_saving_exit<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
+        if cfg!(feature = "write-only") {
+            delay.delay_ms(WRITE_ONLY_RESET_MS);
+            return Ok(());
+        }
+        for _ in 0..100 {
+            if self.read_status()? & raw::status::POWER_SAVING == 0 {
+                break;
+            }
+            delay.delay_ms(1);
+        }
+        Ok(())
+    }
+
+    fn exit_sdram_power_saving<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
+        if self.sdram_powered_down {
+            self.sdram_powered_down = false;
+            self.write_register(Register::Sdrcr, raw::sdrcr::INITIALIZE)?;
+            self.wait_sdram_ready(delay)?;
+            return Ok(());
+        }
+        self.write_register(Register::Sdrcr, 0x00)
+    }
+
     fn configure_sdram<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), I::Error> {
         self.write_register(Register::Sdrar, 0x29)?;
         // CAS latency 3
         self.write_register(Register::Sdrmd, 0x03)?;
 
-        // TODO: Verify that this is correct. Do we want to configure this by user?
-        let sdram_itv: u16 = 0x030D;
-        self.write_register(Register::SdrRef1, sdram_itv as u8)?;
-        self.write_register(Register::SdrRef2, (sdram_itv >> 8) as u8)?;
+        let (itv_low, itv_high) = split_u16(sdram_refresh_interval(self.config.memory_clock_hz));
+        self.write_register(Register::SdrRef1, itv_low)?;
+        self.write_register(Register::SdrRef2, itv_high)?;
 
         // Start SDRAM initialization
-        self.write_register(Register::Sdrcr, 0x01)?;
+        self.write_register(Register::Sdrcr, raw::sdrcr::INITIALIZE)?;
         delay.delay_ms(10);
         Ok(())
     }
 
+    fn configure_interrupts(&mut self) -> Result<(), I::Error> {
+        // Flag engine completion and vsync; the wait strategy picks what reaches the INT pin
+        let events = raw::interrupt::TASK_DONE | raw::interrupt::VSYNC;
+        self.write_register(Register::Inten, events)?;
+        self.write_register(Register::Mintfr, !self.wait_strategy.interrupt_pin_sources())?;
+        self.write_register(Register::Intf, 0xFF)
+    }
+
     fn configure_display_timing(&mut self) -> Result<(), I::Error> {
-        // Horizontal display width
-        self.write_register(Register::Hdwr, ((self.config.width / 8) - 1) as u8)?;
-        self.write_register(Register::Hdwftr, (self.config.width % 8) as u8)?;
-        // Horizontal non-display period (back porch)
-        self.write_register(Register::Hndr, ((self.config.h_back_porch / 8) - 1) as u8)?;
-        self.write_register(Register::Hndftr, (self.config.h_back_porch % 8) as u8)?;
-        // HSYNC start position (front porch - from end of display to start of HSYNC)
-        self.write_register(Register::Hstr, ((self.config.h_front_porch / 8) - 1) as u8)?;
-        // HSYNC pulse width
-        self.write_register(Register::Hpwr, ((self.config.h_sync_width / 8) - 1) as u8)?;
-        // Vertical display height
-        let height_minus_1 = self.config.height - 1;
-        self.write_register(Register::Vdhr1, (height_minus_1 & 0xFF) as u8)?;
-        self.write_register(Register::Vdhr2, ((height_minus_1 >> 8) & 0xFF) as u8)?;
-        // Vertical non-display period (back porch)
-        self.write_register(Register::Vndr1, (self.config.v_back_porch - 1) as u8)?;
-        self.write_register(Register::Vndr2, 0x00)?;
-        // VSYNC start position (front porch - from end of display to start of VSYNC)
-        self.write_register(Register::Vstr, (self.config.v_front_porch - 1) as u8)?;
-        // VSYNC pulse width
-        self.write_register(Register::Vpwr, (self.config.v_sync_width - 1) as u8)?;
+        for (register, value) in self.display_timing() {
+            self.write_register(register, value)?;
+        }
         Ok(())
     }
 
+    /// Panel timing register values for the configuration.
+    fn display_timing(&self) -> [(Register, u8); 12] {
+        let (width, width_fine) = horizontal_period(self.config.width);
+        let (back_porch, back_porch_fine) = horizontal_period(self.config.h_back_porch);
+        let (height_low, height_high) = split_u16(vertical_period(self.config.height));
+        let (back_porch_low, back_porch_high) = split_u16(vertical_period(self.config.v_back_porch));
+        [
+            // Horizontal display width
+            (Register::Hdwr, width),
+            (Register::Hdwftr, width_fine),
+            // Horizontal non-display period (back porch)
+            (Register::Hndr, back_porch),
+            (Register::Hndftr, back_porch_fine),
+            // HSYNC start position (front porch - from end of display to start of HSYNC)
+            (Register::Hstr, horizontal_period(self.config.h_front_porch).0),
+            // HSYNC pulse width
+            (Register::Hpwr, horizontal_period(self.config.h_sync_width).0),
+            // Vertical display height
+            (Register::Vdhr1, height_low),
+            (Register::Vdhr2, height_high),
+            // Vertical non-display period (back porch)
+            (Register::Vndr1, back_porch_low),
+            (Register::Vndr2, back_porch_high),
+            // VSYNC start position (front porch - from end of display to start of VSYNC)
+            (Register::Vstr, vertical_period(self.config.v_front_porch) as u8),
+            // VSYNC pulse width
+            (Register::Vpwr, vertical_period(self.config.v_sync_width) as u8),
+        ]
+    }
+
     fn configure_main_window(&mut self) -> Result<(), I::Error> {
         // Main/PIP window color depth
         self.write_register(Register::Mpwctr, (self.config.color_depth as u8) << 2)?;
         // Main image start address
-        self.write_register(Register::Misa1, 0x00)?;
-        self.write_register(Register::Misa2, 0x00)?;
-        self.write_register(Register::Misa3, 0x00)?;
-        self.write_register(Register::Misa4, 0x00)?;
-        let width = self.config.width;
-        self.write_register(Register::Miw1, (width & 0xFF) as u8)?;
-        self.write_register(Register::Miw2, ((width >> 8) & 0xFF) as u8)?;
-        // Main window upper-left corner
-        self.write_register(Register::Mwulx1, 0x00)?;
-        self.write_register(Register::Mwulx2, 0x00)?;
-        self.write_register(Register::Mwuly1, 0x00)?;
-        self.write_register(Register::Mwuly2, 0x00)?;
-        // Canvas start address
-        self.write_register(Register::Cvssa1, 0x00)?;
-        self.write_register(Register::Cvssa2, 0x00)?;
-        self.write_register(Register::Cvssa3, 0x00)?;
-        self.write_register(Register::Cvssa4, 0x00)?;
-        // Canvas image width in pixels
-        self.write_register(Register::CvsImwth1, (width & 0xFF) as u8)?;
-        self.write_register(Register::CvsImwth2, ((width >> 8) & 0xFF) as u8)?;
-        // Active window
-        self.set_active_window(0, 0, self.config.width, self.config.height)?;
+        self.set_main_image_address(self.config.framebuffer_address)?;
+        let (width_low, width_high) = split_u16(self.config.canvas_width());
+        self.write_register(Register::Miw1, width_low)?;
+        self.write_register(Register::Miw2, width_high)?;
+        self.set_main_window_offset(0, 0)?;
+        self.set_canvas_address(self.config.framebuffer_address)?;
+        self.set_canvas_image_width(self.config.canvas_width())?;
+        self.canvas_image_height = self.config.canvas_height();
+        // Active window over the whole canvas, whatever rotation and origin are already set
+        let (width, height) = (self.config.canvas_width(), self.config.canvas_height());
+        self.with_physical_coordinates(|display| display.set_active_window(0, 0, width, height))?;
         self.write_register(Register::AwColor, self.config.color_depth as u8)?;
         Ok(())
     }
 
+    /// Set the active window drawing is restricted to. Writes are skipped when the window is
+    /// already programmed.
     pub fn set_active_window(&mut self, x: u16, y: u16, width: u16, height: u16) -> Result<(), I::Error> {
+        let window = Rect::new(x, y, width, height);
+        if self.active_window == Some(window) {
+            return Ok(());
+        }
+        let Rect { x, y, width, height } = self.rect_to_physical(window);
         self.write_register(Register::AwulX1, x as u8)?;
         self.write_register(Register::AwulX2, (x >> 8) as u8)?;
         self.write_register(Register::AwulY1, y as u8)?;
@@ -271,10 +877,15 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         self.write_register(Register::AwWth2, (width >> 8) as u8)?;
         self.write_register(Register::AwHt1, height as u8)?;
         self.write_register(Register::AwHt2, (height >> 8) as u8)?;
+        self.active_window = Some(window);
         Ok(())
     }
 
+    /// Set the foreground color used by the drawing and text engines.
+    /// The last programmed color is cached, so repeated calls with a color that reduces to the
+    /// same register values at the configured depth skip the register writes.
     pub fn set_foreground_color(&mut self, color: u32) -> Result<(), I::Error> {
+        let color = self.correct_color(color) & self.config.color_depth.color_mask();
         if self.last_fg == Some(color) {
             return Ok(());
         }
@@ -283,7 +894,10 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
+    /// Set the background color used by the text engine and BTE color expansion.
+    /// Cached the same way as the foreground color.
     pub fn set_background_color(&mut self, color: u32) -> Result<(), I::Error> {
+        let color = self.correct_color(color) & self.config.color_depth.color_mask();
         if self.last_bg == Some(color) {
             return Ok(());
         }
@@ -292,6 +906,23 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
+    /// Pass every color given to the driver through `correction` before it reaches the chip, or
+    /// stop correcting with `None`. Raw pixel data given to `write_image` and `write_data_burst`
+    /// is already in memory port format and is written unchanged.
+    pub fn set_color_correction(&mut self, correction: Option<&'static ColorCorrection>) {
+        self.color_correction = correction;
+        self.last_fg = None;
+        self.last_bg = None;
+    }
+
+    /// `color` after the configured color correction.
+    fn correct_color(&self, color: u32) -> u32 {
+        match self.color_correction {
+            Some(correction) => correction.apply(color),
+            None => color,
+        }
+    }
+
     /// Sets the blink period of text cursor from range of 1 (0x00) to 256 (0xFF), expressed in
     /// number of display frames. For example if the panel runs at 60hz, blink period of 0x00 would
     /// mean the text cursor blinks every 16.7ms.
@@ -309,6 +940,45 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
+    pub fn disable_text_cursor(&mut self) -> Result<(), I::Error> {
+        self.write_register(Register::Gtccr, 0x00)
+    }
+
+    /// Write text followed by the hardware text cursor, blinking as a block the size of one
+    /// scaled character cell (at most 32x32) every `blink_period` + 1 frames. The chip blinks
+    /// only its text cursor, not characters, so one label at a time carries the blinking
+    /// marker and later text writes take it along; switch it off with
+    /// [`disable_text_cursor`](Self::disable_text_cursor).
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_text_blinking(
+        &mut self, text: &str, x: u16, y: u16,
+        bg_color: Option<u32>, fg_color: u32, scale: u8, blink_period: u8
+    ) -> Result<(), I::Error> {
+        self.write_text_scaled(text, x, y, bg_color, fg_color, scale, scale)?;
+        let (width, height) = self.charset.font_size(self.font_size).cell_size();
+        let scale = scale.clamp(1, 4) as u16;
+        self.write_register(Register::Curhs, ((width * scale).min(32) - 1) as u8)?;
+        self.write_register(Register::Curvs, ((height * scale).min(32) - 1) as u8)?;
+        self.set_text_cursor_blink_period(blink_period)?;
+        self.enable_text_cursor(true)
+    }
+
+    /// Move the hardware text cursor to `x`, `y` and make it `width` by `height` pixels (1-32),
+    /// e.g. a thin bar marking an insertion point. The cursor shows while the chip stays in
+    /// text mode, so place it again after drawing anything else; text writes start at its
+    /// position and move it along.
+    pub fn place_text_cursor(&mut self, x: u16, y: u16, width: u16, height: u16) -> Result<(), I::Error> {
+        self.write_register(Register::Curhs, (width.clamp(1, 32) - 1) as u8)?;
+        self.write_register(Register::Curvs, (height.clamp(1, 32) - 1) as u8)?;
+        self.write_register(Register::Icr, 0x04)?;
+        self.text_mode = true;
+        let (x, y) = self.to_physical(x, y);
+        self.write_register(Register::FCurx1, x as u8)?;
+        self.write_register(Register::FCurx2, (x >> 8) as u8)?;
+        self.write_register(Register::FCury1, y as u8)?;
+        self.write_register(Register::FCury2, (y >> 8) as u8)
+    }
+
     fn set_color_registers(&mut self, color: u32, reg_r: Register, reg_g: Register, reg_b: Register) -> Result<(), I::Error> {
         // Input: 0x00RRGGBB (8 bits per channel)
         let r = ((color >> 16) & 0xFF) as u8;
@@ -334,29 +1004,47 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
-    /// Wait for drawing engine to complete (check status bit 3 = core busy).
+    /// Wait for drawing engine to complete.
     pub fn wait_busy_draw(&mut self) -> Result<(), I::Error> {
-        loop {
-            let status = self.read_status()?;
-            if (status & 0x08) == 0 {
-                break;
-            }
-        }
+        self.wait_strategy.wait(&mut self.ll.interface, WaitEvent::Draw)
+    }
+
+    /// Wait for the start of the next vertical sync, e.g. to update the screen without tearing.
+    pub fn wait_vsync(&mut self) -> Result<(), I::Error> {
+        self.wait_strategy.wait(&mut self.ll.interface, WaitEvent::Vsync)?;
+        self.count_vsync();
         Ok(())
     }
 
     pub fn draw_rectangle(&mut self, x1: u16, y1: u16, x2: u16, y2: u16, color: u32, fill: bool) -> Result<(), I::Error> {
+        if let Some(clip) = self.clip {
+            let (left, top, right, bottom) = (x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2));
+            // A rectangle spanning all 65536 columns or rows is one pixel short; the canvas is
+            // far smaller anyway
+            let rect = Rect::new(left, top, (right - left).saturating_add(1), (bottom - top).saturating_add(1));
+            match rect.intersection(&clip) {
+                None => return Ok(()),
+                Some(visible) if fill => {
+                    let (x2, y2) = visible.last_pixel();
+                    return self.draw_rectangle_unclipped(visible.x, visible.y, x2, y2, color, true);
+                }
+                // Partly hidden outlines are drawn edge by edge, each clipped as a line
+                Some(visible) if visible != rect => {
+                    self.draw_line(left, top, right, top, color)?;
+                    self.draw_line(right, top, right, bottom, color)?;
+                    self.draw_line(right, bottom, left, bottom, color)?;
+                    return self.draw_line(left, bottom, left, top, color);
+                }
+                Some(_) => {}
+            }
+        }
+        self.draw_rectangle_unclipped(x1, y1, x2, y2, color, fill)
+    }
+
+    fn draw_rectangle_unclipped(&mut self, x1: u16, y1: u16, x2: u16, y2: u16, color: u32, fill: bool) -> Result<(), I::Error> {
+        self.debug_check_bounds(Some(span(&[(x1, y1), (x2, y2)])));
         self.set_foreground_color(color)?;
-        // Set start point
-        self.write_register(Register::Dlhsr1, x1 as u8)?;
-        self.write_register(Register::Dlhsr2, (x1 >> 8) as u8)?;
-        self.write_register(Register::Dlvsr1, y1 as u8)?;
-        self.write_register(Register::Dlvsr2, (y1 >> 8) as u8)?;
-        // Set end point
-        self.write_register(Register::Dlher1, x2 as u8)?;
-        self.write_register(Register::Dlher2, (x2 >> 8) as u8)?;
-        self.write_register(Register::Dlver1, y2 as u8)?;
-        self.write_register(Register::Dlver2, (y2 >> 8) as u8)?;
+        self.set_corner_points(x1, y1, x2, y2)?;
         let draw_data = if fill { 0xE0 } else { 0xA0 };
         self.write_register(Register::Dcr1, draw_data)?;
         self.wait_busy_draw()?;
@@ -364,26 +1052,94 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
     }
 
     pub fn draw_line(&mut self, x1: u16, y1: u16, x2: u16, y2: u16, color: u32) -> Result<(), I::Error> {
+        let (x1, y1, x2, y2) = match self.clip {
+            Some(clip) => match clip_line(&clip, (x1, y1), (x2, y2)) {
+                Some(((x1, y1), (x2, y2))) => (x1, y1, x2, y2),
+                None => return Ok(()),
+            },
+            None => (x1, y1, x2, y2),
+        };
+        self.debug_check_bounds(Some(span(&[(x1, y1), (x2, y2)])));
         self.set_foreground_color(color)?;
-        // Set start point
-        self.write_register(Register::Dlhsr1, x1 as u8)?;
-        self.write_register(Register::Dlhsr2, (x1 >> 8) as u8)?;
-        self.write_register(Register::Dlvsr1, y1 as u8)?;
-        self.write_register(Register::Dlvsr2, (y1 >> 8) as u8)?;
-        // Set end point
-        self.write_register(Register::Dlher1, x2 as u8)?;
-        self.write_register(Register::Dlher2, (x2 >> 8) as u8)?;
-        self.write_register(Register::Dlver1, y2 as u8)?;
-        self.write_register(Register::Dlver2, (y2 >> 8) as u8)?;
+        self.set_line_points(x1, y1, x2, y2)?;
 
         self.write_register(Register::Dcr0, 0x80)?;
         self.wait_busy_draw()?;
         Ok(())
     }
 
+    /// Draw connected lines through `points`, e.g. a plot or an open outline. Segments run
+    /// alternately forwards and backwards, so each shared point stays in the register pair it
+    /// was written to and only the new point's coordinates are written per segment.
+    pub fn draw_lines(&mut self, points: &[(u16, u16)], color: u32) -> Result<(), I::Error> {
+        for (i, pair) in points.windows(2).enumerate() {
+            let ((x1, y1), (x2, y2)) = if i % 2 == 0 { (pair[0], pair[1]) } else { (pair[1], pair[0]) };
+            self.draw_line(x1, y1, x2, y2, color)?;
+        }
+        Ok(())
+    }
+
+    /// Draw a horizontal line of `length` pixels starting at `x`, `y`. Rows of a table or grid
+    /// share their x coordinates, so only the y registers are rewritten.
+    pub fn draw_hline(&mut self, x: u16, y: u16, length: u16, color: u32) -> Result<(), I::Error> {
+        if length == 0 {
+            return Ok(());
+        }
+        self.draw_line(x, y, x.saturating_add(length - 1), y, color)
+    }
+
+    /// Draw a vertical line of `length` pixels starting at `x`, `y`. Columns of a table or
+    /// grid share their y coordinates, so only the x registers are rewritten.
+    pub fn draw_vline(&mut self, x: u16, y: u16, length: u16, color: u32) -> Result<(), I::Error> {
+        if length == 0 {
+            return Ok(());
+        }
+        self.draw_line(x, y, x, y.saturating_add(length - 1), color)
+    }
+
+    /// Program the start and end points shared by lines, rectangles and triangles, skipping
+    /// coordinates that are already set.
+    fn set_line_points(&mut self, x1: u16, y1: u16, x2: u16, y2: u16) -> Result<(), I::Error> {
+        let (x1, y1) = self.to_physical(x1, y1);
+        let (x2, y2) = self.to_physical(x2, y2);
+        self.set_physical_line_points(x1, y1, x2, y2)
+    }
+
+    /// Program opposite corners of a rectangle. Rotation can swap the corners' roles, so they
+    /// are ordered again on the canvas.
+    fn set_corner_points(&mut self, x1: u16, y1: u16, x2: u16, y2: u16) -> Result<(), I::Error> {
+        let (x1, y1) = self.to_physical(x1, y1);
+        let (x2, y2) = self.to_physical(x2, y2);
+        if self.rotation == Rotation::Deg0 {
+            return self.set_physical_line_points(x1, y1, x2, y2);
+        }
+        self.set_physical_line_points(x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2))
+    }
+
+    fn set_physical_line_points(&mut self, x1: u16, y1: u16, x2: u16, y2: u16) -> Result<(), I::Error> {
+        let registers = [
+            (Register::Dlhsr1, Register::Dlhsr2),
+            (Register::Dlvsr1, Register::Dlvsr2),
+            (Register::Dlher1, Register::Dlher2),
+            (Register::Dlver1, Register::Dlver2),
+        ];
+        for (index, value) in [x1, y1, x2, y2].into_iter().enumerate() {
+            if self.line_points[index] == Some(value) {
+                continue;
+            }
+            let (low, high) = registers[index];
+            self.write_register(low, value as u8)?;
+            self.write_register(high, (value >> 8) as u8)?;
+            self.line_points[index] = Some(value);
+        }
+        Ok(())
+    }
+
     pub fn draw_circle(&mut self, center_x: u16, center_y: u16, radius: u16, color: u32, fill: bool) -> Result<(), I::Error> {
+        self.debug_check_bounds(ellipse_area((center_x, center_y), (radius, radius), None));
         self.set_foreground_color(color)?;
         // Set center point
+        let (center_x, center_y) = self.to_physical(center_x, center_y);
         self.write_register(Register::Dehr1, center_x as u8)?;
         self.write_register(Register::Dehr2, (center_x >> 8) as u8)?;
         self.write_register(Register::Devr1, center_y as u8)?;
@@ -400,13 +1156,16 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
     }
 
     pub fn draw_ellipse(&mut self, center_x: u16, center_y: u16, radius_x: u16, radius_y: u16, color: u32, fill: bool) -> Result<(), I::Error> {
+        self.debug_check_bounds(ellipse_area((center_x, center_y), (radius_x, radius_y), None));
         self.set_foreground_color(color)?;
         // Set center point
+        let (center_x, center_y) = self.to_physical(center_x, center_y);
         self.write_register(Register::Dehr1, center_x as u8)?;
         self.write_register(Register::Dehr2, (center_x >> 8) as u8)?;
         self.write_register(Register::Devr1, center_y as u8)?;
         self.write_register(Register::Devr2, (center_y >> 8) as u8)?;
         // Set radii
+        let (radius_x, radius_y) = if self.rotation.is_quarter_turn() { (radius_y, radius_x) } else { (radius_x, radius_y) };
         self.write_register(Register::EllA1, radius_x as u8)?;
         self.write_register(Register::EllA2, (radius_x >> 8) as u8)?;
         self.write_register(Register::EllB1, radius_y as u8)?;
@@ -417,17 +1176,36 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
+    /// Draw one quarter of an ellipse. With `fill` the quarter is drawn as a filled pie slice.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_ellipse_curve(&mut self, center_x: u16, center_y: u16, radius_x: u16, radius_y: u16, quadrant: Quadrant, color: u32, fill: bool) -> Result<(), I::Error> {
+        self.debug_check_bounds(ellipse_area((center_x, center_y), (radius_x, radius_y), Some(quadrant)));
+        self.set_foreground_color(color)?;
+        // Set center point
+        let (center_x, center_y) = self.to_physical(center_x, center_y);
+        self.write_register(Register::Dehr1, center_x as u8)?;
+        self.write_register(Register::Dehr2, (center_x >> 8) as u8)?;
+        self.write_register(Register::Devr1, center_y as u8)?;
+        self.write_register(Register::Devr2, (center_y >> 8) as u8)?;
+        // Set radii
+        let (radius_x, radius_y) = if self.rotation.is_quarter_turn() { (radius_y, radius_x) } else { (radius_x, radius_y) };
+        self.write_register(Register::EllA1, radius_x as u8)?;
+        self.write_register(Register::EllA2, (radius_x >> 8) as u8)?;
+        self.write_register(Register::EllB1, radius_y as u8)?;
+        self.write_register(Register::EllB2, (radius_y >> 8) as u8)?;
+        let draw_data = if fill { 0xD0 } else { 0x90 };
+        let quadrant = self.rotation.rotate_quadrant(quadrant);
+        self.write_register(Register::Dcr1, draw_data | quadrant as u8)?;
+        self.wait_busy_draw()?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_rounded_rectangle(&mut self, x1: u16, y1: u16, x2: u16, y2: u16, corner_radius: u16, color: u32, fill: bool) -> Result<(), I::Error> {
+        self.debug_check_bounds(Some(span(&[(x1, y1), (x2, y2)])));
         self.set_foreground_color(color)?;
         // Set rectangle corners
-        self.write_register(Register::Dlhsr1, x1 as u8)?;
-        self.write_register(Register::Dlhsr2, (x1 >> 8) as u8)?;
-        self.write_register(Register::Dlvsr1, y1 as u8)?;
-        self.write_register(Register::Dlvsr2, (y1 >> 8) as u8)?;
-        self.write_register(Register::Dlher1, x2 as u8)?;
-        self.write_register(Register::Dlher2, (x2 >> 8) as u8)?;
-        self.write_register(Register::Dlver1, y2 as u8)?;
-        self.write_register(Register::Dlver2, (y2 >> 8) as u8)?;
+        self.set_corner_points(x1, y1, x2, y2)?;
         // Set corner radius
         self.write_register(Register::EllA1, corner_radius as u8)?;
         self.write_register(Register::EllA2, (corner_radius >> 8) as u8)?;
@@ -439,19 +1217,14 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn draw_triangle(&mut self, x1: u16, y1: u16, x2: u16, y2: u16, x3: u16, y3: u16, color: u32, fill: bool) -> Result<(), I::Error> {
+        self.debug_check_bounds(Some(span(&[(x1, y1), (x2, y2), (x3, y3)])));
         self.set_foreground_color(color)?;
-        // Set point 1
-        self.write_register(Register::Dlhsr1, x1 as u8)?;
-        self.write_register(Register::Dlhsr2, (x1 >> 8) as u8)?;
-        self.write_register(Register::Dlvsr1, y1 as u8)?;
-        self.write_register(Register::Dlvsr2, (y1 >> 8) as u8)?;
-        // Set point 2
-        self.write_register(Register::Dlher1, x2 as u8)?;
-        self.write_register(Register::Dlher2, (x2 >> 8) as u8)?;
-        self.write_register(Register::Dlver1, y2 as u8)?;
-        self.write_register(Register::Dlver2, (y2 >> 8) as u8)?;
+        // Set points 1 and 2
+        self.set_line_points(x1, y1, x2, y2)?;
         // Set point 3
+        let (x3, y3) = self.to_physical(x3, y3);
         self.write_register(Register::Dtph1, x3 as u8)?;
         self.write_register(Register::Dtph2, (x3 >> 8) as u8)?;
         self.write_register(Register::Dtpv1, y3 as u8)?;
@@ -462,21 +1235,189 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
+    /// Select the character set used by subsequent text writes.
+    /// With [`Charset::External`] the font ROM is read from its
+    /// [`chip_select`](ExternalFont::chip_select).
+    pub fn set_charset(&mut self, charset: Charset) {
+        self.charset = charset;
+    }
+
+    /// Select the character cell size used by subsequent text writes.
+    pub fn set_font_size(&mut self, font_size: FontSize) {
+        self.font_size = font_size;
+    }
+
     /// When bg_color is not provided, characters background will be the canvas background.
+    /// A given bg_color is programmed for this call only, so callers need not track the
+    /// background color between labels.
     pub fn write_text(&mut self, text: &str, x: u16, y: u16, bg_color: Option<u32>, fg_color: u32) -> Result<(), I::Error> {
         self.write_text_scaled(text, x, y, bg_color, fg_color, 1, 1)
     }
 
     /// When bg_color is not provided, characters background will be the canvas background.
     /// scale_x and scale_y: 1-4 (1 = normal size, 2 = 2x, 3 = 3x, 4 = 4x).
+    /// Characters are encoded for the selected [`Charset`]; those it cannot represent are drawn
+    /// as `?`.
+    #[allow(clippy::too_many_arguments)]
     pub fn write_text_scaled(
         &mut self, text: &str, x: u16, y: u16,
         bg_color: Option<u32>, fg_color: u32, scale_x: u8, scale_y: u8
     ) -> Result<(), I::Error> {
-        // TODO: This is now hardcoded as Internal CGROM character.
-        // Make it user configurable, in case external one is desired.
-        // This also controls the size (8x16, 12x24, 16x32)
-        self.write_register(Register::Ccr0, 0x00)?;
+        let style = TextStyle { bg_color, scale_x, scale_y, font_size: self.font_size, charset: self.charset, ..TextStyle::new(fg_color) };
+        let Some((text, x)) = self.clip_text(text, x, y, &style) else { return Ok(()) };
+        self.begin_text(x, y, bg_color, fg_color, scale_x, scale_y)?;
+        self.write_chars(text)
+    }
+
+    /// Write runs of text in different styles as one line starting at `x`, `y`. Each run
+    /// continues where the previous one ended, aligned to the bottom of the tallest character
+    /// cell. The charset and font size selected for other writes are left unchanged.
+    pub fn write_spans(&mut self, spans: &[(TextStyle, &str)], x: u16, y: u16) -> Result<(), I::Error> {
+        let (charset, font_size) = (self.charset, self.font_size);
+        let result = self.write_spans_from(spans, x, y);
+        self.charset = charset;
+        self.font_size = font_size;
+        result
+    }
+
+    fn write_spans_from(&mut self, spans: &[(TextStyle, &str)], mut x: u16, y: u16) -> Result<(), I::Error> {
+        let line_height = spans.iter().map(|(style, _)| style.cell_height()).max().unwrap_or(0);
+        for (style, text) in spans {
+            let top = y + line_height - style.cell_height();
+            // The text cursor only moves over the characters the clip rectangle lets through
+            if self.clip_text(text, x, top, style) != Some((text, x)) {
+                self.write_styled(text, x, top, style)?;
+                x = x.saturating_add(self.text_width(text, style));
+                continue;
+            }
+            self.write_styled(text, x, top, style)?;
+            // Where the cursor is not read back, assume the run stayed on one line
+            if cfg!(feature = "write-only") {
+                x = x.saturating_add(self.text_width(text, style));
+                continue;
+            }
+            // The text engine has moved its cursor past the run
+            let low = self.read_register(Register::FCurx1)?;
+            let high = self.read_register(Register::FCurx2)?;
+            // The engine writes along canvas rows, so the cursor only moved on the canvas x axis
+            let (_, row) = self.to_physical(x, y);
+            x = self.to_logical(u16::from_le_bytes([low, high]), row).0;
+        }
+        Ok(())
+    }
+
+    /// Write `text` into `rect`, breaking lines at spaces and newlines and splitting words too
+    /// long for a line, with characters measured as [`text_width`](Self::text_width) does.
+    /// Lines that do not fit in `rect` are left out; returns how many characters of `text`
+    /// were written, counting the spaces and newlines lines were broken at. The charset and
+    /// font size selected for other writes are left unchanged.
+    pub fn write_text_wrapped(&mut self, text: &str, rect: Rect, style: TextStyle) -> Result<usize, I::Error> {
+        let rows = rect.height / style.cell_height();
+        let (charset, font_size) = (self.charset, self.font_size);
+        let mut rest = text;
+        let mut result = Ok(());
+        for row in 0..rows {
+            if rest.is_empty() {
+                break;
+            }
+            let (line, next) = wrap_line(rest, rect.width as u32, |c| self.char_advance(c, &style));
+            // Not even one character fits on a line
+            if line.is_empty() && next.len() == rest.len() {
+                break;
+            }
+            result = self.write_styled(line, rect.x, rect.y + row * style.cell_height(), &style);
+            if result.is_err() {
+                break;
+            }
+            rest = next.trim_start_matches(' ');
+        }
+        self.charset = charset;
+        self.font_size = font_size;
+        result.map(|()| text.chars().count() - rest.chars().count())
+    }
+
+    /// Use `widths`, from [`read_font_widths`](Self::read_font_widths), to measure text in
+    /// the proportional styles of the external font ROM. Without them such text is measured
+    /// as if it were fixed width.
+    pub fn set_font_widths(&mut self, widths: Option<FontWidths>) {
+        self.font_widths = widths;
+    }
+
+    /// Width of `text` in `style` in pixels, with the widths set by
+    /// [`set_font_widths`](Self::set_font_widths) for proportional text. Characters without
+    /// a known width take a full cell.
+    pub fn text_width(&self, text: &str, style: &TextStyle) -> u16 {
+        let width: u32 = text.chars().map(|c| self.char_advance(c, style)).sum();
+        width.min(u16::MAX as u32) as u16
+    }
+
+    /// Width of `c` in `style` in pixels, as [`text_width`](Self::text_width) measures it.
+    fn char_advance(&self, c: char, style: &TextStyle) -> u32 {
+        let cell = style.char_width() as u32;
+        match self.font_widths.filter(|_| style.is_proportional()) {
+            Some(widths) => widths.width(c).map_or(cell, |width| width as u32 * style.scale_x() as u32),
+            None => cell,
+        }
+    }
+
+    /// Write a line of `text` at the top of `rect`, placed horizontally by `align`. Text wider
+    /// than `rect` starts at its left edge.
+    pub fn write_text_aligned(&mut self, text: &str, rect: Rect, align: TextAlign, style: TextStyle) -> Result<(), I::Error> {
+        let spare = rect.width.saturating_sub(self.text_width(text, &style));
+        let x = match align {
+            TextAlign::Left => rect.x,
+            TextAlign::Center => rect.x + spare / 2,
+            TextAlign::Right => rect.x + spare,
+        };
+        let (charset, font_size) = (self.charset, self.font_size);
+        let result = self.write_styled(text, x, rect.y, &style);
+        self.charset = charset;
+        self.font_size = font_size;
+        result
+    }
+
+    /// Write `text` at `x`, `y` in `style`, leaving its charset and font size selected.
+    fn write_styled(&mut self, text: &str, x: u16, y: u16, style: &TextStyle) -> Result<(), I::Error> {
+        self.charset = style.charset;
+        self.font_size = style.font_size;
+        let Some((text, x)) = self.clip_text(text, x, y, style) else { return Ok(()) };
+        self.begin_text(x, y, style.bg_color, style.fg_color, style.scale_x, style.scale_y)?;
+        self.write_chars(text)
+    }
+
+    /// Encode `text` for the selected [`Charset`] and feed it to the text engine.
+    fn write_chars(&mut self, text: &str) -> Result<(), I::Error> {
+        let charset = self.charset;
+        self.timed(BenchOp::Text, |display| {
+            let mut buf = [0; 2];
+            for c in text.chars() {
+                for &byte in charset.encode(c, &mut buf) {
+                    display.write_text_byte(byte)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Write text that is already encoded for the selected character set, e.g. GB2312 or BIG5
+    /// byte sequences for an external font ROM. Bytes are passed to the text engine unchanged.
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_text_encoded(
+        &mut self, text: &[u8], x: u16, y: u16,
+        bg_color: Option<u32>, fg_color: u32, scale_x: u8, scale_y: u8
+    ) -> Result<(), I::Error> {
+        self.begin_text(x, y, bg_color, fg_color, scale_x, scale_y)?;
+        self.timed(BenchOp::Text, |display| {
+            for &byte in text {
+                display.write_text_byte(byte)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn begin_text(&mut self, x: u16, y: u16, bg_color: Option<u32>, fg_color: u32, scale_x: u8, scale_y: u8) -> Result<(), I::Error> {
+        self.debug_check_bounds(Some(Rect::new(x, y, 1, 1)));
+        self.configure_font()?;
 
         let scale_x_bits = (scale_x.saturating_sub(1).min(3)) & 0x03;
         let scale_y_bits = (scale_y.saturating_sub(1).min(3)) & 0x03;
@@ -492,20 +1433,330 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         }
         self.set_foreground_color(fg_color)?;
         self.write_register(Register::Icr, 0x04)?;
+        self.text_mode = true;
+        let (x, y) = self.to_physical(x, y);
         self.write_register(Register::FCurx1, x as u8)?;
         self.write_register(Register::FCurx2, (x >> 8) as u8)?;
         self.write_register(Register::FCury1, y as u8)?;
         self.write_register(Register::FCury2, (y >> 8) as u8)?;
-        for &char in text.as_bytes() {
-            self.write_register(Register::Mrwdp, char)?;
-            self.wait_busy_draw()?;
+        Ok(())
+    }
+
+    fn write_text_byte(&mut self, byte: u8) -> Result<(), I::Error> {
+        self.write_register(Register::Mrwdp, byte)?;
+        self.wait_strategy.wait(&mut self.ll.interface, WaitEvent::Text)
+    }
+
+    fn configure_font(&mut self) -> Result<(), I::Error> {
+        let font_size = self.charset.font_size(self.font_size);
+        // Character source, cell size and internal CGROM codepage
+        self.write_register(Register::Ccr0, self.charset.ccr0_bits() | (font_size as u8) << 4)?;
+        match self.charset {
+            Charset::External(font) => {
+                // A DMA copy may have left the interface on the other chip or in DMA mode
+                self.select_flash(font.chip_select, 0x00, FlashReadMode::Normal)?;
+                self.write_register(Register::GtfntSel, (font.rom as u8) << 5)?;
+                self.write_register(Register::GtfntCr, (font.encoding as u8) << 3 | font.width as u8)?;
+            }
+            Charset::User(font) => {
+                self.write_register(Register::CgramStr0, font.address as u8)?;
+                self.write_register(Register::CgramStr1, (font.address >> 8) as u8)?;
+                self.write_register(Register::CgramStr2, (font.address >> 16) as u8)?;
+                self.write_register(Register::CgramStr3, (font.address >> 24) as u8)?;
+            }
+            _ => {}
         }
         Ok(())
     }
 
-    /// Clear entire screen with color.
-    pub fn clear_screen(&mut self, color: u32) -> Result<(), I::Error> {
-        self.draw_rectangle(0, 0, self.config.width - 1, self.config.height - 1, color, true)
+    /// Clear the entire canvas with color, wherever the origin is.
+    pub fn clear_screen(&mut self, color: u32) -> Result<(), CheckedError<I::Error>> {
+        let canvas = Rect::new(0, 0, self.config.canvas_width(), self.config.canvas_height());
+        self.timed(BenchOp::Clear, |display| {
+            display.with_physical_coordinates(|display| display.clear_area(canvas, color))
+        })
+    }
+
+    /// Fill `rect` with color, using a BTE solid fill if `config.bte_fill` is set and the
+    /// drawing engine otherwise.
+    pub fn clear_area(&mut self, rect: Rect, color: u32) -> Result<(), CheckedError<I::Error>> {
+        let Some(rect) = self.clip_area(rect) else {
+            return Ok(());
+        };
+        self.check_bounds(rect)?;
+        self.timed(BenchOp::Fill, |display| {
+            if display.config.bte_fill {
+                return display.bte_solid_fill(rect.x, rect.y, rect.width, rect.height, color);
+            }
+            let (x2, y2) = rect.last_pixel();
+            Ok(display.draw_rectangle(rect.x, rect.y, x2, y2, color, true)?)
+        })
+    }
+
+    /// Draw the outline of `rect` with the border extending `thickness` pixels inwards.
+    /// Built from four filled rectangles; a border thick enough to cover the whole area fills it.
+    pub fn draw_rect_border(&mut self, rect: Rect, thickness: u16, color: u32) -> Result<(), I::Error> {
+        if rect.is_empty() || thickness == 0 {
+            return Ok(());
+        }
+        let (x2, y2) = rect.last_pixel();
+        if thickness.saturating_mul(2) >= rect.width || thickness.saturating_mul(2) >= rect.height {
+            return self.draw_rectangle(rect.x, rect.y, x2, y2, color, true);
+        }
+        // Edges of the area inside the border, kept in the coordinate range for areas reaching
+        // past it
+        let (inner_left, inner_top) = (rect.x.saturating_add(thickness), rect.y.saturating_add(thickness));
+        let (inner_right, inner_bottom) = (x2.saturating_sub(thickness), y2.saturating_sub(thickness));
+        // Top and bottom span the full width, left and right fill the gap between them
+        self.draw_rectangle(rect.x, rect.y, x2, inner_top - 1, color, true)?;
+        self.draw_rectangle(rect.x, inner_bottom + 1, x2, y2, color, true)?;
+        self.draw_rectangle(rect.x, inner_top, inner_left - 1, inner_bottom, color, true)?;
+        self.draw_rectangle(inner_right + 1, inner_top, x2, inner_bottom, color, true)?;
+        Ok(())
+    }
+
+    /// Fill `rect` with a linear gradient between two colors.
+    /// Drawn as a series of filled rectangles, one per band of lines that end up with the same
+    /// color at the configured color depth, so shallow gradients only cost a few draw operations.
+    pub fn fill_gradient(&mut self, rect: Rect, from_color: u32, to_color: u32, direction: GradientDirection) -> Result<(), I::Error> {
+        if rect.is_empty() {
+            return Ok(());
+        }
+        let steps = match direction {
+            GradientDirection::Horizontal => rect.width,
+            GradientDirection::Vertical => rect.height,
+        };
+        let mask = self.config.color_depth.color_mask();
+        let (right, bottom) = rect.last_pixel();
+        let mut band_start = 0;
+        let mut band_color = interpolate_color(from_color, to_color, 0, steps) & mask;
+        for i in 1..=steps {
+            let color = interpolate_color(from_color, to_color, i, steps) & mask;
+            if i < steps && color == band_color {
+                continue;
+            }
+            // Bands past the coordinate range collapse onto its last row or column
+            let (x1, y1, x2, y2) = match direction {
+                GradientDirection::Horizontal => (rect.x.saturating_add(band_start), rect.y, rect.x.saturating_add(i - 1), bottom),
+                GradientDirection::Vertical => (rect.x, rect.y.saturating_add(band_start), right, rect.y.saturating_add(i - 1)),
+            };
+            self.draw_rectangle(x1, y1, x2, y2, band_color, true)?;
+            band_start = i;
+            band_color = color;
+        }
+        Ok(())
+    }
+
+    /// Write a single pixel through the memory port.
+    pub fn draw_pixel(&mut self, x: u16, y: u16, color: u32) -> Result<(), CheckedError<I::Error>> {
+        if self.clip.is_some_and(|clip| !clip.contains(x, y)) {
+            return Ok(());
+        }
+        self.check_bounds(Rect::new(x, y, 1, 1))?;
+        self.enter_graphic_mode()?;
+        self.set_graphic_cursor(x, y)?;
+        self.ll.write_command(Register::Mrwdp)?;
+        let (bytes, len) = self.config.color_depth.pixel_bytes(self.correct_color(color));
+        for &byte in &bytes[..len] {
+            self.ll.write_data(byte)?;
+        }
+        self.advance_graphic_cursor(1);
+        Ok(())
+    }
+
+    /// Stream raw pixel data in memory port byte order to the graphic cursor position, issuing
+    /// the memory port command once for the whole slice.
+    pub fn write_data_burst(&mut self, data: &[u8]) -> Result<(), CheckedError<I::Error>> {
+        self.enter_graphic_mode()?;
+        self.ll.write_command(Register::Mrwdp)?;
+        // The FIFO is not watched in write-only builds; flow control is left to the bus speed
+        if self.config.fifo_flow_control && !cfg!(feature = "write-only") {
+            for chunk in data.chunks(MEMORY_WRITE_FIFO_DEPTH) {
+                self.wait_write_fifo_empty()?;
+                self.ll.write_data_burst(chunk)?;
+            }
+        } else {
+            self.ll.write_data_burst(data)?;
+        }
+        let pixels = data.len() as u32 / self.config.color_depth.bytes_per_pixel() as u32;
+        self.advance_graphic_cursor(pixels);
+        Ok(())
+    }
+
+    /// Wait for the memory write FIFO to drain, with the wait strategy's hint between status
+    /// reads.
+    fn wait_write_fifo_empty(&mut self) -> Result<(), CheckedError<I::Error>> {
+        for _ in 0..FIFO_DRAIN_POLLS {
+            if self.read_status()? & raw::status::WRITE_FIFO_EMPTY != 0 {
+                return Ok(());
+            }
+            self.wait_strategy.hint();
+        }
+        Err(CheckedError::FifoTimeout)
+    }
+
+    /// Write `data` (raw pixels in memory port byte order, row by row) into `rect`. The memory
+    /// port direction and active window are set up for the upload and restored afterwards.
+    pub fn write_image(&mut self, rect: Rect, data: &[u8]) -> Result<(), CheckedError<I::Error>> {
+        let bytes_per_pixel = self.config.color_depth.bytes_per_pixel() as usize;
+        let row_bytes = rect.width as usize * bytes_per_pixel;
+        let Some(visible) = self.clip_area(rect) else {
+            return Ok(());
+        };
+        if visible != rect {
+            // Send only the visible columns of the visible rows
+            let left = (visible.x - rect.x) as usize * bytes_per_pixel;
+            let right = left + visible.width as usize * bytes_per_pixel;
+            let rows = data.chunks_exact(row_bytes).skip((visible.y - rect.y) as usize).take(visible.height as usize);
+            let mut rows = rows.map(|row| &row[left..right]);
+            return self.write_into(visible, |display, bottom_up| {
+                if bottom_up {
+                    rows.rev().try_for_each(|row| display.write_data_burst(row))
+                } else {
+                    rows.try_for_each(|row| display.write_data_burst(row))
+                }
+            });
+        }
+        self.write_into(rect, |display, bottom_up| {
+            if !bottom_up || row_bytes == 0 {
+                return display.write_data_burst(data);
+            }
+            let rows = data.len().min(row_bytes * rect.height as usize) / row_bytes;
+            for row in data[..rows * row_bytes].chunks(row_bytes).rev() {
+                display.write_data_burst(row)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Run `write` with the memory port set up to fill `rect` row by row, then restore the
+    /// previous direction and active window. `write` is told whether the rotation needs the
+    /// rows sent bottom row first; each row always runs from its left end.
+    fn write_into<F>(&mut self, rect: Rect, write: F) -> Result<(), CheckedError<I::Error>>
+    where
+        F: FnOnce(&mut Self, bool) -> Result<(), CheckedError<I::Error>>,
+    {
+        if rect.is_empty() {
+            return Ok(());
+        }
+        self.check_bounds(rect)?;
+        let previous_direction = self.memory_direction;
+        let previous_window = self.active_window;
+        let (direction, bottom_up) = self.rotation.write_layout();
+        self.set_memory_write_direction(direction)?;
+        self.set_active_window(rect.x, rect.y, rect.width, rect.height)?;
+        let first_row = if bottom_up { rect.y + rect.height - 1 } else { rect.y };
+        self.set_graphic_cursor(rect.x, first_row)?;
+        let result = self.timed(BenchOp::ImageUpload, |display| write(display, bottom_up));
+        if let Some(window) = previous_window {
+            self.set_active_window(window.x, window.y, window.width, window.height)?;
+        }
+        if let Some(direction) = previous_direction {
+            self.set_memory_write_direction(direction)?;
+        }
+        result
+    }
+
+    /// Order in which memory port writes advance the cursor through the active window.
+    pub fn set_memory_write_direction(&mut self, direction: MemoryDirection) -> Result<(), I::Error> {
+        if self.memory_direction == Some(direction) {
+            return Ok(());
+        }
+        self.modify_register(Register::Macr, 0x06, (direction as u8) << 1)?;
+        self.memory_direction = Some(direction);
+        self.graphic_cursor = None;
+        Ok(())
+    }
+
+    /// Order in which memory port reads advance the cursor through the active window.
+    pub fn set_memory_read_direction(&mut self, direction: MemoryDirection) -> Result<(), I::Error> {
+        self.modify_register(Register::Macr, 0x30, (direction as u8) << 4)
+    }
+
+    /// CRC-32 (IEEE) of the pixels in `rect` read back from display memory, row by row, with
+    /// each pixel's bytes in memory port order (blue first at 24 bpp). Lets tests check
+    /// rendering results without transferring the pixels themselves. With a rotation set, the
+    /// canvas area under `rect` is read in canvas row order.
+    pub fn region_crc32(&mut self, rect: Rect) -> Result<u32, I::Error> {
+        if rect.is_empty() {
+            return Ok(0);
+        }
+        let rect = self.rect_to_physical(rect);
+        let mut crc = 0;
+        self.with_physical_coordinates(|display| {
+            crc = display.canvas_crc32(rect)?;
+            Ok(())
+        })?;
+        Ok(crc)
+    }
+
+    fn canvas_crc32(&mut self, rect: Rect) -> Result<u32, I::Error> {
+        self.enter_graphic_mode()?;
+        let previous_window = self.active_window;
+        self.set_active_window(rect.x, rect.y, rect.width, rect.height)?;
+        self.set_graphic_cursor(rect.x, rect.y)?;
+        self.ll.write_command(Register::Mrwdp)?;
+        // The first read after moving the cursor returns stale data
+        self.ll.read_data()?;
+        let bytes = rect.width as u32 * rect.height as u32 * self.config.color_depth.bytes_per_pixel() as u32;
+        let mut crc = !0;
+        for _ in 0..bytes {
+            crc = crc32_update(crc, self.ll.read_data()?);
+        }
+        self.graphic_cursor = None;
+        if let Some(window) = previous_window {
+            self.set_active_window(window.x, window.y, window.width, window.height)?;
+        }
+        Ok(!crc)
+    }
+
+    /// Set the memory read/write position used by the memory port.
+    /// Writes are skipped when the cursor is known to be there already.
+    fn set_graphic_cursor(&mut self, x: u16, y: u16) -> Result<(), I::Error> {
+        if self.graphic_cursor == Some((x, y)) {
+            return Ok(());
+        }
+        let (physical_x, physical_y) = self.to_physical(x, y);
+        self.write_register(Register::Curh1, physical_x as u8)?;
+        self.write_register(Register::Curh2, (physical_x >> 8) as u8)?;
+        self.write_register(Register::Curv1, physical_y as u8)?;
+        self.write_register(Register::Curv2, (physical_y >> 8) as u8)?;
+        self.graphic_cursor = Some((x, y));
+        Ok(())
+    }
+
+    /// Track the cursor moving through the active window as `pixels` are transferred
+    /// left to right, top to bottom.
+    fn advance_graphic_cursor(&mut self, pixels: u32) {
+        if self.memory_direction != Some(MemoryDirection::LeftRightTopDown) || self.rotation != Rotation::Deg0 {
+            self.graphic_cursor = None;
+            return;
+        }
+        let (Some((x, y)), Some(window)) = (self.graphic_cursor, self.active_window) else {
+            self.graphic_cursor = None;
+            return;
+        };
+        if x < window.x || y < window.y || x - window.x >= window.width || y - window.y >= window.height {
+            self.graphic_cursor = None;
+            return;
+        }
+        let column = (x - window.x) as u32 + pixels;
+        let row = (y - window.y) as u32 + column / window.width as u32;
+        self.graphic_cursor = if row < window.height as u32 {
+            Some((window.x + (column % window.width as u32) as u16, window.y + row as u16))
+        } else {
+            None
+        };
+    }
+
+    /// Route memory port data to display memory instead of the text engine.
+    fn enter_graphic_mode(&mut self) -> Result<(), I::Error> {
+        if self.text_mode {
+            // Graphic mode, SDRAM memory
+            self.write_register(Register::Icr, 0x00)?;
+            self.text_mode = false;
+        }
+        Ok(())
     }
 
     /// Set the canvas start address (where drawing operations write to).
@@ -514,6 +1765,41 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         self.write_register(Register::Cvssa2, (addr >> 8) as u8)?;
         self.write_register(Register::Cvssa3, (addr >> 16) as u8)?;
         self.write_register(Register::Cvssa4, (addr >> 24) as u8)?;
+        self.canvas_address = addr;
+        Ok(())
+    }
+
+    /// Run `draw` with the canvas moved to the off-screen image at `address`, `width` by
+    /// `height` pixels, then switch back to the previous canvas and active window.
+    fn draw_offscreen<F>(&mut self, address: u32, width: u16, height: u16, draw: F) -> Result<(), CheckedError<I::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), CheckedError<I::Error>>,
+    {
+        let (previous_address, previous_width) = (self.canvas_address, self.canvas_image_width);
+        let (previous_height, previous_window) = (self.canvas_image_height, self.active_window);
+        // The clip rectangle, origin and rotation are for the canvas and do not apply off screen
+        let result = self.with_physical_coordinates(|display| {
+            display.set_canvas_address(address)?;
+            display.set_canvas_image_width(width)?;
+            display.canvas_image_height = height;
+            display.set_active_window(0, 0, width, height)?;
+            draw(display)
+        });
+        self.set_canvas_address(previous_address)?;
+        self.set_canvas_image_width(previous_width)?;
+        self.canvas_image_height = previous_height;
+        if let Some(window) = previous_window {
+            self.set_active_window(window.x, window.y, window.width, window.height)?;
+        }
+        result
+    }
+
+    /// Width in pixels of the image at the canvas address, which drawing wraps rows at.
+    fn set_canvas_image_width(&mut self, width: u16) -> Result<(), I::Error> {
+        let (width_low, width_high) = split_u16(width);
+        self.write_register(Register::CvsImwth1, width_low)?;
+        self.write_register(Register::CvsImwth2, width_high)?;
+        self.canvas_image_width = width;
         Ok(())
     }
 
@@ -526,15 +1812,26 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         Ok(())
     }
 
+    /// Show the part of the main image whose upper-left corner is at `x`, `y`. With a main
+    /// image wider or taller than the panel this pans the view without redrawing.
+    pub fn set_main_window_offset(&mut self, x: u16, y: u16) -> Result<(), I::Error> {
+        self.write_register(Register::Mwulx1, x as u8)?;
+        self.write_register(Register::Mwulx2, (x >> 8) as u8)?;
+        self.write_register(Register::Mwuly1, y as u8)?;
+        self.write_register(Register::Mwuly2, (y >> 8) as u8)?;
+        Ok(())
+    }
+
+    /// Show the part of a canvas larger than the panel whose upper-left corner is at `x`, `y`,
+    /// clamped so the view stays within the canvas.
+    pub fn pan_to(&mut self, x: u16, y: u16) -> Result<(), I::Error> {
+        let x = x.min(self.config.canvas_width() - self.config.width);
+        let y = y.min(self.config.canvas_height() - self.config.height);
+        self.set_main_window_offset(x, y)
+    }
+
     pub fn wait_bte_complete(&mut self) -> Result<(), I::Error> {
-        // TODO: timeout?
-        loop {
-            let ctrl = self.read_register(Register::BteCtrl0)?;
-            // Bit 4 = BTE busy when read
-            if (ctrl & 0x10) == 0 {
-                break;
-            }
-        }
+        self.wait_strategy.wait(&mut self.ll.interface, WaitEvent::Bte)?;
         // Disable BTE
         self.write_register(Register::BteCtrl0, 0x00)?;
         Ok(())
@@ -542,14 +1839,145 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
 
     /// Fill a rectangular area with a solid color using BTE.
     /// This is faster than draw_filled_rectangle for large areas.
-    pub fn bte_solid_fill(&mut self, x: u16, y: u16, width: u16, height: u16, color: u32) -> Result<(), I::Error> {
+    pub fn bte_solid_fill(&mut self, x: u16, y: u16, width: u16, height: u16, color: u32) -> Result<(), CheckedError<I::Error>> {
+        let Some(Rect { x, y, width, height }) = self.clip_area(Rect::new(x, y, width, height)) else {
+            return Ok(());
+        };
+        self.check_bounds(Rect::new(x, y, width, height))?;
         self.set_foreground_color(color)?;
-        // Set destination memory start address (canvas address = 0)
-        self.write_register(Register::DtStr0, 0x00)?;
-        self.write_register(Register::DtStr1, 0x00)?;
-        self.write_register(Register::DtStr2, 0x00)?;
-        self.write_register(Register::DtStr3, 0x00)?;
+        self.configure_bte_destination(x, y, width, height)?;
+        // BTE Solid fill
+        self.write_register(Register::BteCtrl1, 0x0C)?;
+        // Enable BTE write
+        self.write_register(Register::BteCtrl0, 0x10)?;
+        self.wait_bte_complete()?;
+        Ok(())
+    }
+
+    /// Draw a packed 1bpp bitmap, `width` by `height` pixels with each row MSB first and padded
+    /// to whole bytes, by BTE color expansion. Set bits take `fg_color`, clear bits `bg_color`
+    /// or, without one, leave the canvas untouched. Missing data draws as clear bits. The
+    /// engine cannot rotate the bitmap, so while rotated it is drawn pixel by pixel.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_mono_bitmap(&mut self, x: u16, y: u16, width: u16, height: u16, data: &[u8], fg_color: u32, bg_color: Option<u32>) -> Result<(), CheckedError<I::Error>> {
+        self.draw_mono_bitmap_with_order(x, y, width, height, data, BitOrder::MsbFirst, fg_color, bg_color)
+    }
+
+    /// Like [`draw_mono_bitmap`](Self::draw_mono_bitmap) with the bit order of `data` given, so
+    /// XBM images (LSB first, as exported by GIMP or ImageMagick) can be drawn unchanged.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_mono_bitmap_with_order(
+        &mut self, x: u16, y: u16, width: u16, height: u16, data: &[u8],
+        bit_order: BitOrder, fg_color: u32, bg_color: Option<u32>
+    ) -> Result<(), CheckedError<I::Error>> {
+        let Some(visible) = self.clip_area(Rect::new(x, y, width, height)) else {
+            return Ok(());
+        };
+        self.check_bounds(visible)?;
+        let size = width.div_ceil(8) as usize * height as usize;
+        if self.rotation != Rotation::Deg0 {
+            return self.draw_mono_bitmap_pixels(x, y, width, height, data, bit_order, fg_color, bg_color);
+        }
+        if visible != Rect::new(x, y, width, height) {
+            return Ok(self.draw_mono_bitmap_cropped(Rect::new(x, y, width, height), visible, data, bit_order, fg_color, bg_color)?);
+        }
+        self.begin_bte_color_expansion(x, y, width, height, fg_color, bg_color)?;
+        let data = &data[..size.min(data.len())];
+        match bit_order {
+            BitOrder::MsbFirst => self.ll.write_data_burst(data)?,
+            BitOrder::LsbFirst => {
+                // The engine takes bit 7 first; reverse in small bursts
+                let mut buf = [0; 32];
+                for chunk in data.chunks(buf.len()) {
+                    for (reversed, &byte) in buf.iter_mut().zip(chunk) {
+                        *reversed = byte.reverse_bits();
+                    }
+                    self.ll.write_data_burst(&buf[..chunk.len()])?;
+                }
+            }
+        }
+        // The engine only finishes once it has seen the whole area
+        for _ in data.len()..size {
+            self.ll.write_data(0x00)?;
+        }
+        Ok(self.wait_bte_complete()?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_mono_bitmap_pixels(
+        &mut self, x: u16, y: u16, width: u16, height: u16, data: &[u8],
+        bit_order: BitOrder, fg_color: u32, bg_color: Option<u32>
+    ) -> Result<(), CheckedError<I::Error>> {
+        let stride = width.div_ceil(8) as usize;
+        for row in 0..height {
+            for column in 0..width {
+                match (mono_bit(data, stride, row, column, bit_order), bg_color) {
+                    (true, _) => self.draw_pixel(x + column, y + row, fg_color)?,
+                    (false, Some(bg_color)) => self.draw_pixel(x + column, y + row, bg_color)?,
+                    (false, None) => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw the part of the bitmap covering `area` that lies in `visible` by BTE color
+    /// expansion, repacking its rows to start at the first visible column.
+    fn draw_mono_bitmap_cropped(
+        &mut self, area: Rect, visible: Rect, data: &[u8],
+        bit_order: BitOrder, fg_color: u32, bg_color: Option<u32>
+    ) -> Result<(), I::Error> {
+        let stride = area.width.div_ceil(8) as usize;
+        let (left, top) = (visible.x - area.x, visible.y - area.y);
+        self.begin_bte_color_expansion(visible.x, visible.y, visible.width, visible.height, fg_color, bg_color)?;
+        let mut buf = [0; 32];
+        let mut len = 0;
+        for row in top..top + visible.height {
+            for index in 0..visible.width.div_ceil(8) {
+                let mut byte = 0;
+                for bit in 0..8 {
+                    let column = index * 8 + bit;
+                    if column < visible.width && mono_bit(data, stride, row, left + column, bit_order) {
+                        byte |= 0x80 >> bit;
+                    }
+                }
+                buf[len] = byte;
+                len += 1;
+                if len == buf.len() {
+                    self.ll.write_data_burst(&buf)?;
+                    len = 0;
+                }
+            }
+        }
+        self.ll.write_data_burst(&buf[..len])?;
+        self.wait_bte_complete()
+    }
+
+    /// Start a BTE MPU write with color expansion into the given area. The caller streams the
+    /// 1bpp image rows (MSB first, each row padded to whole bytes) with `write_data` and then
+    /// calls `wait_bte_complete`. Set bits take the foreground color, clear bits the background
+    /// color or, without one, leave the destination untouched.
+    fn begin_bte_color_expansion(&mut self, x: u16, y: u16, width: u16, height: u16, fg_color: u32, bg_color: Option<u32>) -> Result<(), I::Error> {
+        self.set_foreground_color(fg_color)?;
+        if let Some(bg_color) = bg_color {
+            self.set_background_color(bg_color)?;
+        }
+        self.configure_bte_destination(x, y, width, height)?;
+        // Expansion starts from bit 7 of each byte on the 8-bit host bus;
+        // operation 0x8 = color expansion, 0x9 = color expansion with transparency
+        let operation = if bg_color.is_some() { 0x78 } else { 0x79 };
+        self.write_register(Register::BteCtrl1, operation)?;
+        self.enter_graphic_mode()?;
+        // Enable BTE write
+        self.write_register(Register::BteCtrl0, 0x10)?;
+        self.ll.write_command(Register::Mrwdp)
+    }
+
+    fn configure_bte_destination(&mut self, x: u16, y: u16, width: u16, height: u16) -> Result<(), I::Error> {
+        // Set destination memory start address
+        self.set_bte_destination_image()?;
         // Set destination X/Y coordinates
+        let Rect { x, y, width, height } = self.rect_to_physical(Rect::new(x, y, width, height));
         self.write_register(Register::DtX0, x as u8)?;
         self.write_register(Register::DtX1, (x >> 8) as u8)?;
         self.write_register(Register::DtY0, y as u8)?;
@@ -557,52 +1985,95 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         // NOTE: when doing constant color/alpha blending, bits 4-2 should be different.
         let depth = self.config.color_depth as u8;
         self.write_register(Register::BteColr, depth | (depth << 5) | (depth << 2))?;
-        // Set destination image width
-        let canvas_width = self.config.width;
-        self.write_register(Register::DtWth0, canvas_width as u8)?;
-        self.write_register(Register::DtWth1, (canvas_width >> 8) as u8)?;
         // Set BTE window width and height
         self.write_register(Register::BteWth0, width as u8)?;
         self.write_register(Register::BteWth1, (width >> 8) as u8)?;
         self.write_register(Register::BteHig0, height as u8)?;
         self.write_register(Register::BteHig1, (height >> 8) as u8)?;
+        Ok(())
+    }
 
-        // BTE Solid fill
-        self.write_register(Register::BteCtrl1, 0x0C)?;
-        // Enable BTE write
-        self.write_register(Register::BteCtrl0, 0x10)?;
-        self.wait_bte_complete()?;
+    /// Point the BTE destination at the canvas image.
+    fn set_bte_destination_image(&mut self) -> Result<(), I::Error> {
+        let address = self.canvas_address;
+        self.write_register(Register::DtStr0, address as u8)?;
+        self.write_register(Register::DtStr1, (address >> 8) as u8)?;
+        self.write_register(Register::DtStr2, (address >> 16) as u8)?;
+        self.write_register(Register::DtStr3, (address >> 24) as u8)?;
+        let width = self.canvas_image_width;
+        self.write_register(Register::DtWth0, width as u8)?;
+        self.write_register(Register::DtWth1, (width >> 8) as u8)?;
         Ok(())
     }
 
+    /// Copy an area of the canvas to another position on it.
     pub fn bte_memory_copy(
         &mut self,
         src_x: u16, src_y: u16,
         dst_x: u16, dst_y: u16,
         width: u16, height: u16
-    ) -> Result<(), I::Error> {
-        let canvas_width = self.config.width;
-        // Set source 0 memory start address (canvas address = 0)
-        self.write_register(Register::S0Str0, 0x00)?;
-        self.write_register(Register::S0Str1, 0x00)?;
-        self.write_register(Register::S0Str2, 0x00)?;
-        self.write_register(Register::S0Str3, 0x00)?;
+    ) -> Result<(), CheckedError<I::Error>> {
+        let area = Rect::new(src_x, src_y, width, height);
+        self.check_bounds(area)?;
+        let source = (self.canvas_address, self.canvas_image_width);
+        let area = self.rect_to_physical(area);
+        self.bte_copy(source, area.x, area.y, dst_x, dst_y, width, height, None)
+    }
+
+    /// Copy an area of the image at `source` (display memory address and image width in
+    /// pixels) to the canvas. Source pixels of the `chroma_key` color are left out.
+    #[allow(clippy::too_many_arguments)]
+    fn bte_copy(
+        &mut self,
+        source: (u32, u16),
+        src_x: u16, src_y: u16,
+        dst_x: u16, dst_y: u16,
+        width: u16, height: u16,
+        chroma_key: Option<u32>
+    ) -> Result<(), CheckedError<I::Error>> {
+        // BTE memory copy: ROP=0xC (S0), operation=0x2, or 0x5 with chroma key
+        self.bte_from_source(source, src_x, src_y, dst_x, dst_y, width, height, chroma_key, (0xC2, 0xC5))
+    }
+
+    /// Run a BTE operation reading source 0 from `source` (memory start address and image
+    /// width). `operations` holds the BTE_CTRL1 values without and with chroma key. The
+    /// destination is logical; the source is read as stored, over the destination's canvas size.
+    #[allow(clippy::too_many_arguments)]
+    fn bte_from_source(
+        &mut self,
+        source: (u32, u16),
+        src_x: u16, src_y: u16,
+        dst_x: u16, dst_y: u16,
+        width: u16, height: u16,
+        chroma_key: Option<u32>,
+        operations: (u8, u8)
+    ) -> Result<(), CheckedError<I::Error>> {
+        let (source_address, source_width) = source;
+        let destination = Rect::new(dst_x, dst_y, width, height);
+        let Some(visible) = self.clip_area(destination) else {
+            return Ok(());
+        };
+        self.check_bounds(visible)?;
+        // The source is stored like the canvas, so it moves by the clipped-off part of the
+        // destination on the canvas
+        let full = self.rect_to_physical(destination);
+        let Rect { x: dst_x, y: dst_y, width, height } = self.rect_to_physical(visible);
+        let (src_x, src_y) = (src_x + dst_x.saturating_sub(full.x), src_y + dst_y.saturating_sub(full.y));
+        // Set source 0 memory start address
+        self.write_register(Register::S0Str0, source_address as u8)?;
+        self.write_register(Register::S0Str1, (source_address >> 8) as u8)?;
+        self.write_register(Register::S0Str2, (source_address >> 16) as u8)?;
+        self.write_register(Register::S0Str3, (source_address >> 24) as u8)?;
         // Set source 0 image width
-        self.write_register(Register::S0Wth0, canvas_width as u8)?;
-        self.write_register(Register::S0Wth1, (canvas_width >> 8) as u8)?;
+        self.write_register(Register::S0Wth0, source_width as u8)?;
+        self.write_register(Register::S0Wth1, (source_width >> 8) as u8)?;
         // Set source 0 X/Y coordinates
         self.write_register(Register::S0X0, src_x as u8)?;
         self.write_register(Register::S0X1, (src_x >> 8) as u8)?;
         self.write_register(Register::S0Y0, src_y as u8)?;
         self.write_register(Register::S0Y1, (src_y >> 8) as u8)?;
-        // Set destination memory start address
-        self.write_register(Register::DtStr0, 0x00)?;
-        self.write_register(Register::DtStr1, 0x00)?;
-        self.write_register(Register::DtStr2, 0x00)?;
-        self.write_register(Register::DtStr3, 0x00)?;
-        // Set destination image width
-        self.write_register(Register::DtWth0, canvas_width as u8)?;
-        self.write_register(Register::DtWth1, (canvas_width >> 8) as u8)?;
+        // Set destination memory start address and image width
+        self.set_bte_destination_image()?;
         // Set destination X/Y coordinates
         self.write_register(Register::DtX0, dst_x as u8)?;
         self.write_register(Register::DtX1, (dst_x >> 8) as u8)?;
@@ -616,8 +2087,14 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
         // NOTE: when doing constant color/alpha blending, bits should be 4-2 different.
         let depth = self.config.color_depth as u8;
         self.write_register(Register::BteColr, depth | (depth << 5) | (depth << 2))?;
-        // BTE memory copy: ROP=0xC (S0), operation=0x2
-        self.write_register(Register::BteCtrl1, 0xC2)?;
+        match chroma_key {
+            Some(key) => {
+                // Chroma key is taken from the background color
+                self.set_background_color(key)?;
+                self.write_register(Register::BteCtrl1, operations.1)?;
+            }
+            None => self.write_register(Register::BteCtrl1, operations.0)?,
+        }
         // Enable BTE write
         self.write_register(Register::BteCtrl0, 0x10)?;
         self.wait_bte_complete()?;
@@ -625,6 +2102,34 @@ impl<I: LT7683Interface, RESET: OutputPin> LT7683<I, RESET> {
     }
 }
 
+/// Whether the pixel at `row`, `column` of a packed 1bpp bitmap with rows `stride` bytes apart
+/// is set. Pixels past the end of `data` are clear.
+fn mono_bit(data: &[u8], stride: usize, row: u16, column: u16, bit_order: BitOrder) -> bool {
+    let byte = data.get(row as usize * stride + column as usize / 8).copied().unwrap_or(0);
+    let mask = match bit_order {
+        BitOrder::MsbFirst => 0x80 >> (column % 8),
+        BitOrder::LsbFirst => 0x01 << (column % 8),
+    };
+    byte & mask != 0
+}
+
+/// Linear interpolation between two 0x00RRGGBB colors, `step` out of `steps` (0 = `from`).
+fn interpolate_color(from: u32, to: u32, step: u16, steps: u16) -> u32 {
+    if steps <= 1 {
+        return from;
+    }
+    let step = step.min(steps - 1) as i32;
+    let last = (steps - 1) as i32;
+    let mut color = 0;
+    for shift in [16, 8, 0] {
+        let a = ((from >> shift) & 0xFF) as i32;
+        let b = ((to >> shift) & 0xFF) as i32;
+        let c = a + (b - a) * step / last;
+        color |= (c as u32) << shift;
+    }
+    color
+}
+
 pub struct SpiInterface<SPI> {
     pub spi: SPI,
 }
@@ -658,9 +2163,88 @@ where
         self.spi.transfer_in_place(&mut buf)?;
         Ok(buf[1])
     }
+
+    fn write_data_burst(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        // One data write cycle header, then the bytes back to back
+        self.spi.transaction(&mut [Operation::Write(&[0x80]), Operation::Write(data)])
+    }
+}
+
+/// 0x00RRGGBB color as taken by the drawing calls, convertible from embedded-graphics colors
+/// with the `graphics` feature: `display.clear_screen(Color::from(Rgb565::BLUE).into())`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Color(pub u32);
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self((r as u32) << 16 | (g as u32) << 8 | b as u32)
+    }
+
+    /// The color as stored at `depth`, with the bits the depth drops cleared.
+    pub const fn quantize(self, depth: ColorDepth) -> Self {
+        Self(self.0 & depth.color_mask())
+    }
+}
+
+impl From<u32> for Color {
+    fn from(color: u32) -> Self {
+        Self(color)
+    }
+}
+
+impl From<Color> for u32 {
+    fn from(color: Color) -> Self {
+        color.0
+    }
+}
+
+/// Per-channel lookup tables compensating for a panel's color response, e.g. a washed-out or
+/// tinted panel. Each 0-255 channel intensity is replaced by its table entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorCorrection {
+    pub red: [u8; 256],
+    pub green: [u8; 256],
+    pub blue: [u8; 256],
+}
+
+impl ColorCorrection {
+    /// Tables that leave every color unchanged.
+    pub const fn identity() -> Self {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            table[i] = i as u8;
+            i += 1;
+        }
+        Self { red: table, green: table, blue: table }
+    }
+
+    /// Tables built from `f(channel, intensity)`, channel 0-2 being red, green and blue.
+    pub fn from_fn(mut f: impl FnMut(usize, u8) -> u8) -> Self {
+        let mut correction = Self::identity();
+        for (channel, table) in [&mut correction.red, &mut correction.green, &mut correction.blue].into_iter().enumerate() {
+            for (i, entry) in table.iter_mut().enumerate() {
+                *entry = f(channel, i as u8);
+            }
+        }
+        correction
+    }
+
+    pub const fn apply(&self, color: u32) -> u32 {
+        let r = self.red[(color >> 16) as u8 as usize] as u32;
+        let g = self.green[(color >> 8) as u8 as usize] as u32;
+        let b = self.blue[color as u8 as usize] as u32;
+        r << 16 | g << 8 | b
+    }
+}
+
+impl Default for ColorCorrection {
+    fn default() -> Self {
+        Self::identity()
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorDepth {
     /// 8-bit color (RGB 3:3:2).
     Bpp8 = 0x00,
@@ -669,3 +2253,108 @@ pub enum ColorDepth {
     /// 24-bit color (RGB 8:8:8).
     Bpp24 = 0x02,
 }
+
+/// Order of the pixels within each byte of a 1bpp bitmap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Bit 7 is the leftmost pixel, as in u8g2 and BDF-derived fonts.
+    MsbFirst,
+    /// Bit 0 is the leftmost pixel, as in XBM.
+    LsbFirst,
+}
+
+/// Order in which the memory port moves through the active window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryDirection {
+    /// Left to right, then top to bottom.
+    LeftRightTopDown = 0x00,
+    /// Right to left, then top to bottom.
+    RightLeftTopDown = 0x01,
+    /// Top to bottom, then left to right.
+    TopDownLeftRight = 0x02,
+    /// Bottom to top, then left to right.
+    BottomUpLeftRight = 0x03,
+}
+
+/// Points during [`LT7683::init_with_hook`] at which the hook is called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitStage {
+    /// The chip has been reset; clocks still run from the oscillator.
+    Reset,
+    /// PLLs are running and the SDRAM is initialized.
+    ClocksAndMemory,
+    /// Panel timing and the main window are configured, the display is still off.
+    BeforeDisplayOn,
+    /// The display has been switched on.
+    DisplayOn,
+}
+
+/// First picture shown by `init`, so the panel never shows uninitialized display memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Splash {
+    /// Fill the canvas with a 0x00RRGGBB color.
+    Color(u32),
+    /// Copy a panel-sized image, stored row by row at the configured color depth, from the
+    /// serial flash on [`DisplayConfig::flash_target`].
+    FlashImage { address: u32 },
+}
+
+/// Power saving modes of the PMU, from least to most saving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerMode {
+    /// Clocks keep running; wakes immediately.
+    Standby = 0x01,
+    /// PLLs stopped; the chip runs from the oscillator.
+    Suspend = 0x02,
+    /// All clocks stopped.
+    Sleep = 0x03,
+}
+
+/// What happens to display memory during `Suspend` and `Sleep`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryRetention {
+    /// The SDRAM refreshes itself and keeps its contents.
+    SelfRefresh,
+    /// The SDRAM is powered down and its contents are lost.
+    PowerDown,
+}
+
+/// Quarter of an ellipse, as selected by the curve drawing mode of DCR1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quadrant {
+    BottomLeft = 0x00,
+    UpperLeft = 0x01,
+    UpperRight = 0x02,
+    BottomRight = 0x03,
+}
+
+impl ColorDepth {
+    /// Bits of a 0x00RRGGBB color that are kept at this depth.
+    const fn color_mask(self) -> u32 {
+        match self {
+            ColorDepth::Bpp8 => 0x00E0_E0C0,
+            ColorDepth::Bpp16 => 0x00F8_FCF8,
+            ColorDepth::Bpp24 => 0x00FF_FFFF,
+        }
+    }
+
+    const fn bytes_per_pixel(self) -> u8 {
+        match self {
+            ColorDepth::Bpp8 => 1,
+            ColorDepth::Bpp16 => 2,
+            ColorDepth::Bpp24 => 3,
+        }
+    }
+
+    /// Memory port bytes of a 0x00RRGGBB color on the 8-bit host bus, and how many are used.
+    const fn pixel_bytes(self, color: u32) -> ([u8; 3], usize) {
+        let r = (color >> 16) as u8;
+        let g = (color >> 8) as u8;
+        let b = color as u8;
+        match self {
+            ColorDepth::Bpp8 => ([(r & 0xE0) | ((g & 0xE0) >> 3) | (b >> 6), 0, 0], 1),
+            ColorDepth::Bpp16 => ([((g << 3) & 0xE0) | (b >> 3), (r & 0xF8) | (g >> 5), 0], 2),
+            ColorDepth::Bpp24 => ([b, g, r], 3),
+        }
+    }
+}
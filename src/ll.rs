@@ -0,0 +1,105 @@
+//! Low-level access to the chip: register reads and writes and raw bus cycles, with no knowledge
+//! of what the registers mean.
+//!
+//! [`LT7683`](crate::LT7683) is built on top of this layer and exposes it as its `ll` field, for
+//! features the high-level API does not cover.
+
+use crate::LT7683Interface;
+use crate::registers::Register;
+#[cfg(feature = "shadow")]
+use crate::shadow::RegisterShadow;
+
+pub struct Lt7683Ll<I> {
+    pub interface: I,
+    #[cfg(feature = "shadow")]
+    shadow: RegisterShadow,
+}
+
+impl<I: LT7683Interface> Lt7683Ll<I> {
+    pub fn new(interface: I) -> Self {
+        Self {
+            interface,
+            #[cfg(feature = "shadow")]
+            shadow: RegisterShadow::new(),
+        }
+    }
+
+    /// Give back the bus interface.
+    pub fn release(self) -> I {
+        self.interface
+    }
+
+    pub fn write_register(&mut self, register: Register, data: u8) -> Result<(), I::Error> {
+        self.interface.write_command(register)?;
+        self.interface.write_data(data)?;
+        #[cfg(feature = "shadow")]
+        self.shadow.record(register, data);
+        Ok(())
+    }
+
+    pub fn read_register(&mut self, register: Register) -> Result<u8, I::Error> {
+        self.interface.write_command(register)?;
+        self.interface.read_data()
+    }
+
+    /// Update only the bits of `register` selected by `mask`. With the `shadow` feature the
+    /// current value is taken from the shadow when known, otherwise it is read from the chip.
+    /// Write-only builds leave a register the shadow has no value for untouched rather than
+    /// guess its other bits; write it in full with [`write_register`](Self::write_register)
+    /// first.
+    pub fn modify_register(&mut self, register: Register, mask: u8, value: u8) -> Result<(), I::Error> {
+        #[cfg(feature = "shadow")]
+        let shadowed = self.shadow.get(register as u8);
+        #[cfg(not(feature = "shadow"))]
+        let shadowed = None;
+        let current = match shadowed {
+            Some(current) => current,
+            None if cfg!(feature = "write-only") => return Ok(()),
+            None => self.read_register(register)?,
+        };
+        self.write_register(register, (current & !mask) | (value & mask))
+    }
+
+    /// Select `register` for the data cycles that follow, e.g. the memory data port.
+    pub fn write_command(&mut self, register: Register) -> Result<(), I::Error> {
+        self.interface.write_command(register)
+    }
+
+    /// Data write cycle to the selected register. Not recorded in the shadow.
+    pub fn write_data(&mut self, data: u8) -> Result<(), I::Error> {
+        self.interface.write_data(data)
+    }
+
+    /// Data write cycles of all of `data` to the selected register. Not recorded in the shadow.
+    pub fn write_data_burst(&mut self, data: &[u8]) -> Result<(), I::Error> {
+        self.interface.write_data_burst(data)
+    }
+
+    /// Data read cycle from the selected register.
+    pub fn read_data(&mut self) -> Result<u8, I::Error> {
+        self.interface.read_data()
+    }
+
+    /// Status read cycle.
+    pub fn read_status(&mut self) -> Result<u8, I::Error> {
+        self.interface.read_status()
+    }
+
+    /// Values written to the control registers so far.
+    /// The shadow survives resets, so it keeps describing the configured state.
+    #[cfg(feature = "shadow")]
+    pub fn shadow(&self) -> &RegisterShadow {
+        &self.shadow
+    }
+
+    /// Write every recorded register value back to the chip, in address order.
+    /// Operations such as SDRAM initialization or power mode changes are not replayed.
+    #[cfg(feature = "shadow")]
+    pub fn restore_registers(&mut self) -> Result<(), I::Error> {
+        for (register, value) in self.shadow.entries() {
+            self.interface.write_command(register)?;
+            self.interface.write_data(value)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Reduced refresh for always-on status screens on battery products: a lower pixel clock and,
+//! optionally, memory clock, with the backlight dimmed, switched on and off in one call.
+
+use crate::{LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+/// Settings of [`LT7683::low_power_display_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LowPowerConfig {
+    /// Panel refresh rate, in frames per second. Panels flicker below their minimum rate,
+    /// which is often around 30 Hz.
+    pub refresh_hz: u32,
+    /// Backlight level, 0-255. A backlight already dimmer than this is left as it is.
+    pub backlight_level: u8,
+    /// Memory clock, or `None` to keep it. It has to stay high enough for the panel refresh
+    /// and drawing; the SDRAM refresh interval is reprogrammed to still meet the 64 ms the
+    /// datasheet requires.
+    pub memory_clock_hz: Option<u32>,
+}
+
+impl LowPowerConfig {
+    pub const fn new() -> Self {
+        Self { refresh_hz: 30, backlight_level: 64, memory_clock_hz: None }
+    }
+}
+
+impl Default for LowPowerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What low power mode changed, to go back to on leaving it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LowPowerRestore {
+    pixel_clock_hz: u32,
+    memory_clock_hz: u32,
+    backlight_level: u8,
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Switch to the reduced refresh of `config.low_power`, or back to the clocks and
+    /// backlight level from before. The display keeps running throughout; a configured
+    /// backlight fades like it does after init.
+    pub fn low_power_display_mode<D: DelayNs>(&mut self, enable: bool, delay: &mut D) -> Result<(), I::Error> {
+        match (enable, self.low_power) {
+            (true, None) => {
+                let restore = LowPowerRestore {
+                    pixel_clock_hz: self.config.pixel_clock_hz,
+                    memory_clock_hz: self.config.memory_clock_hz,
+                    backlight_level: self.backlight_level,
+                };
+                let mode = self.config.low_power;
+                // Lower the pixel clock first, so the memory never falls behind the panel
+                self.set_refresh_rate(mode.refresh_hz, delay)?;
+                if let Some(memory_clock_hz) = mode.memory_clock_hz {
+                    self.set_memory_clock(memory_clock_hz, delay)?;
+                }
+                self.fade_backlight_to(mode.backlight_level.min(restore.backlight_level), delay)?;
+                self.low_power = Some(restore);
+            }
+            (false, Some(restore)) => {
+                if restore.memory_clock_hz != self.config.memory_clock_hz {
+                    self.set_memory_clock(restore.memory_clock_hz, delay)?;
+                }
+                self.set_pixel_clock(restore.pixel_clock_hz, delay)?;
+                self.fade_backlight_to(restore.backlight_level, delay)?;
+                self.low_power = None;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn is_low_power_display_mode(&self) -> bool {
+        self.low_power.is_some()
+    }
+
+    fn fade_backlight_to<D: DelayNs>(&mut self, level: u8, delay: &mut D) -> Result<(), I::Error> {
+        match self.config.backlight {
+            Some(backlight) => self.fade_backlight(level, backlight.fade_ms as u32, delay),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Text scrolling through a window too narrow to show it at once.
+
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, TextStyle, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Text rendered once into display memory and scrolled through a window on the canvas by BTE
+/// copies, so each step costs a single block transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marquee {
+    window: Rect,
+    /// Off-screen strip holding the text twice, a window width apart, so the copy source never
+    /// runs past its end.
+    address: u32,
+    strip_width: u16,
+    height: u16,
+    /// Distance after which the strip repeats: text width plus the gap.
+    period: u16,
+    offset: u16,
+    /// Pixels scrolled per tick.
+    pub step: u16,
+}
+
+impl Marquee {
+    pub fn window(&self) -> Rect {
+        self.window
+    }
+
+    /// Scroll the text `step` pixels to the left, wrapping around after it has left the window.
+    pub fn tick<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        self.offset = ((self.offset as u32 + self.step as u32) % self.period as u32) as u16;
+        self.show(display)
+    }
+
+    fn show<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        let source = (self.address, self.strip_width);
+        display.bte_copy(source, self.offset, 0, self.window.x, self.window.y, self.window.width, self.height, None)
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Render `text` into display memory at `address` and show its start in `window`; call
+    /// [`Marquee::tick`] to scroll it. The text is measured assuming a fixed-width font, and
+    /// a transparent background is rendered black.
+    ///
+    /// The off-screen strip takes (text width + 2 * window width) by one character cell height
+    /// of pixels from `address`, which must not overlap the canvas or other images, e.g. a
+    /// region of a [`MemoryMap`](crate::MemoryMap).
+    pub fn create_marquee(&mut self, text: &str, style: TextStyle, window: Rect, address: u32, step: u16) -> Result<Marquee, CheckedError<I::Error>> {
+        let char_width = style.char_width();
+        let text_width = (text.chars().count() as u16).saturating_mul(char_width);
+        let period = text_width.saturating_add(window.width).max(1);
+        let strip_width = period.saturating_add(window.width).next_multiple_of(4);
+        let height = style.cell_height();
+        let background = style.bg_color.unwrap_or(0x000000);
+        let style = TextStyle { bg_color: Some(background), ..style };
+        // The repeat only has to cover one window width
+        let repeat_chars = window.width.div_ceil(char_width) as usize;
+        let repeat = match text.char_indices().nth(repeat_chars) {
+            Some((end, _)) => &text[..end],
+            None => text,
+        };
+        self.draw_offscreen(address, strip_width, height, |display| {
+            display.clear_area(Rect::new(0, 0, strip_width, height), background)?;
+            display.write_spans(&[(style, text)], 0, 0)?;
+            Ok(display.write_spans(&[(style, repeat)], period, 0)?)
+        })?;
+        let marquee = Marquee {
+            window,
+            address,
+            strip_width,
+            height: height.min(window.height),
+            period,
+            offset: 0,
+            step,
+        };
+        marquee.show(self)?;
+        Ok(marquee)
+    }
+}
@@ -0,0 +1,217 @@
+//! Register value arithmetic, kept separate from bus access so it can be tested on the host.
+
+/// Split a 16-bit value into its (low, high) register bytes.
+pub(crate) const fn split_u16(value: u16) -> (u8, u8) {
+    (value as u8, (value >> 8) as u8)
+}
+
+/// Encode a horizontal period in pixels as the (period / 8 - 1, period % 8) pair used by the
+/// horizontal timing registers. Periods shorter than 8 pixels encode as the minimum.
+pub(crate) const fn horizontal_period(pixels: u16) -> (u8, u8) {
+    let coarse = (pixels / 8).saturating_sub(1);
+    let coarse = if coarse > u8::MAX as u16 { u8::MAX } else { coarse as u8 };
+    (coarse, (pixels % 8) as u8)
+}
+
+/// Pixels described by a `horizontal_period` register pair.
+#[cfg(test)]
+pub(crate) const fn horizontal_pixels(coarse: u8, fine: u8) -> u16 {
+    (coarse as u16 + 1) * 8 + fine as u16
+}
+
+/// Encode a vertical period in lines as the (lines - 1) value used by the vertical timing
+/// registers. Zero encodes as the minimum.
+pub(crate) const fn vertical_period(lines: u16) -> u16 {
+    lines.saturating_sub(1)
+}
+
+/// Bytes of display memory taken by an image of `width` by `height` pixels, with the width
+/// rounded up to a multiple of 4 pixels as the canvas and image width registers require.
+/// Saturates rather than wrapping for images larger than the address space.
+pub(crate) const fn image_bytes(width: u16, height: u16, bytes_per_pixel: u8) -> u32 {
+    let row_bytes = (width as u32).next_multiple_of(4) * bytes_per_pixel as u32;
+    row_bytes.saturating_mul(height as u32)
+}
+
+/// SDRAM refresh interval in memory clock cycles: 8192 rows refreshed every 64 ms.
+pub(crate) const fn sdram_refresh_interval(memory_clock_hz: u32) -> u16 {
+    let cycles = memory_clock_hz / (8192 * 1000 / 64);
+    if cycles > u16::MAX as u32 { u16::MAX } else { cycles as u16 }
+}
+
+/// Pixel position of the reading `raw` on an axis of `pixels` pixels whose edges read `first`
+/// and `last`, rounded and clamped to the axis. `first` may be the larger reading.
+pub(crate) const fn scale_reading(raw: u16, first: u16, last: u16, pixels: u16) -> u16 {
+    if pixels == 0 || first == last {
+        return 0;
+    }
+    let (mut offset, mut span) = (raw as i64 - first as i64, last as i64 - first as i64);
+    if span < 0 {
+        (offset, span) = (-offset, -span);
+    }
+    let position = (offset * (pixels as i64 - 1) + span / 2).div_euclid(span);
+    if position < 0 {
+        0
+    } else if position >= pixels as i64 {
+        pixels - 1
+    } else {
+        position as u16
+    }
+}
+
+/// Feed `byte` into a running CRC-32 (IEEE 802.3, reflected). Start from `!0` and invert the
+/// final value.
+pub(crate) const fn crc32_update(crc: u32, byte: u8) -> u32 {
+    let mut crc = crc ^ byte as u32;
+    let mut bit = 0;
+    while bit < 8 {
+        crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        bit += 1;
+    }
+    crc
+}
+
+/// Divider settings of one of the three PLLs:
+/// output = input * (n + 1) / ((m + 1) * 2^k), with the VCO (before the 2^k output divider)
+/// kept within its operating range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PllConfig {
+    /// Output divider exponent, 0-3.
+    pub k: u8,
+    /// Input divider, 0 (divide by 1) or 1 (divide by 2).
+    pub m: u8,
+    /// Feedback multiplier minus one, 1-63.
+    pub n: u8,
+}
+
+impl PllConfig {
+    pub const VCO_MIN_HZ: u32 = 100_000_000;
+    pub const VCO_MAX_HZ: u32 = 600_000_000;
+
+    /// Closest achievable output to `target_hz`, preferring the highest VCO frequency (lowest
+    /// jitter) on ties. `None` if no setting keeps the VCO in range.
+    pub fn compute(input_hz: u32, target_hz: u32) -> Option<Self> {
+        let mut best: Option<(Self, u64)> = None;
+        for k in (0..=3).rev() {
+            for m in 0..=1 {
+                for n in (1..=63).rev() {
+                    let config = Self { k, m, n };
+                    let vco = config.vco_hz(input_hz);
+                    if !(Self::VCO_MIN_HZ as u64..=Self::VCO_MAX_HZ as u64).contains(&vco) {
+                        continue;
+                    }
+                    let error = config.output_hz(input_hz).abs_diff(target_hz as u64);
+                    if best.is_none_or(|(_, best_error)| error < best_error) {
+                        best = Some((config, error));
+                    }
+                }
+            }
+        }
+        best.map(|(config, _)| config)
+    }
+
+    pub const fn vco_hz(&self, input_hz: u32) -> u64 {
+        input_hz as u64 * (self.n as u64 + 1) / (self.m as u64 + 1)
+    }
+
+    pub const fn output_hz(&self, input_hz: u32) -> u64 {
+        self.vco_hz(input_hz) >> self.k
+    }
+
+    /// Value of the PLL control register 1 (output and input dividers).
+    pub const fn control1(&self) -> u8 {
+        self.k << 1 | self.m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn split_u16_round_trips(value: u16) {
+            let (low, high) = split_u16(value);
+            prop_assert_eq!(u16::from_le_bytes([low, high]), value);
+        }
+
+        #[test]
+        fn horizontal_period_round_trips(pixels in 8u16..=2048) {
+            let (coarse, fine) = horizontal_period(pixels);
+            prop_assert!(fine < 8);
+            prop_assert_eq!(horizontal_pixels(coarse, fine), pixels);
+        }
+
+        #[test]
+        fn short_horizontal_periods_do_not_underflow(pixels in 0u16..8) {
+            prop_assert_eq!(horizontal_period(pixels), (0, pixels as u8));
+        }
+
+        #[test]
+        fn vertical_period_round_trips(lines in 1u16..) {
+            prop_assert_eq!(vertical_period(lines) + 1, lines);
+        }
+
+        #[test]
+        fn pll_stays_in_range(input_hz in 4_000_000u32..=40_000_000, target_hz in 10_000_000u32..=150_000_000) {
+            let config = PllConfig::compute(input_hz, target_hz).unwrap();
+            prop_assert!(config.k <= 3 && config.m <= 1 && (1..=63).contains(&config.n));
+            let vco = config.vco_hz(input_hz);
+            prop_assert!((PllConfig::VCO_MIN_HZ as u64..=PllConfig::VCO_MAX_HZ as u64).contains(&vco));
+        }
+
+        #[test]
+        fn pll_hits_reachable_targets_exactly(input_hz in 4_000_000u32..=40_000_000, k in 0u8..=3, n in 1u8..=63) {
+            let reachable = PllConfig { k, m: 0, n };
+            let vco = reachable.vco_hz(input_hz);
+            prop_assume!((PllConfig::VCO_MIN_HZ as u64..=PllConfig::VCO_MAX_HZ as u64).contains(&vco));
+            let target = reachable.output_hz(input_hz);
+            prop_assume!(target == target as u32 as u64);
+            let config = PllConfig::compute(input_hz, target as u32).unwrap();
+            prop_assert_eq!(config.output_hz(input_hz), target);
+        }
+
+        #[test]
+        fn image_bytes_rounds_rows_to_whole_words(width: u16, height: u16, bytes_per_pixel in 1u8..=3) {
+            let stride = width as u64 + (4 - width as u64 % 4) % 4;
+            prop_assert!(stride.is_multiple_of(4) && stride >= width as u64 && stride < width as u64 + 4);
+            let bytes = stride * height as u64 * bytes_per_pixel as u64;
+            prop_assert_eq!(image_bytes(width, height, bytes_per_pixel) as u64, bytes.min(u32::MAX as u64));
+        }
+
+        #[test]
+        fn sdram_refresh_interval_is_at_most_7_8_us(memory_clock_hz in 10_000_000u32..=200_000_000) {
+            let cycles = sdram_refresh_interval(memory_clock_hz) as u64;
+            prop_assert!(cycles * 128_000 <= memory_clock_hz as u64);
+        }
+
+        #[test]
+        fn readings_stay_on_the_axis(raw: u16, first: u16, last: u16, pixels in 1u16..) {
+            prop_assert!(scale_reading(raw, first, last, pixels) < pixels);
+        }
+
+        #[test]
+        fn edge_readings_hit_edge_pixels(first: u16, last: u16, pixels in 1u16..) {
+            prop_assume!(first != last);
+            prop_assert_eq!(scale_reading(first, first, last, pixels), 0);
+            prop_assert_eq!(scale_reading(last, first, last, pixels), pixels - 1);
+        }
+    }
+
+    #[test]
+    fn crc32_matches_check_value() {
+        let crc = b"123456789".iter().fold(!0, |crc, &byte| crc32_update(crc, byte));
+        assert_eq!(!crc, 0xCBF4_3926);
+    }
+
+    #[test]
+    fn default_clocks_match_known_good_settings() {
+        // Settings used before the dividers were computed
+        assert_eq!(PllConfig::compute(10_000_000, 50_000_000), Some(PllConfig { k: 3, m: 0, n: 39 }));
+        assert_eq!(PllConfig::compute(10_000_000, 100_000_000), Some(PllConfig { k: 2, m: 0, n: 39 }));
+        assert_eq!(sdram_refresh_interval(100_000_000), 0x030D);
+    }
+}
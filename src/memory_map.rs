@@ -0,0 +1,161 @@
+//! Layout of display memory. Everything apart from the framebuffer (PIP images, off-screen
+//! canvases, sprite atlases, user fonts, caches) lives wherever the application puts it; a
+//! [`MemoryMap`] places these regions one after another and checks that none of them overlap,
+//! at compile time when built in a `const`:
+//!
+//! ```ignore
+//! const MAP: MemoryMap = MemoryMap::new(&CONFIG)
+//!     .with_image(RegionKind::Pip(PipWindow::Pip1), 320, 240, ColorDepth::Bpp16)
+//!     .with(RegionKind::Cgram(0), FontSize::Size16x32.user_font_bytes());
+//! let font = display.upload_icon_font(MAP.address(RegionKind::Cgram(0)))?;
+//! ```
+
+use crate::math::image_bytes;
+use crate::{ColorDepth, DisplayConfig, PipWindow, SDRAM_BYTES};
+
+/// Regions start on multiples of this, as image addresses must.
+const REGION_ALIGN: u32 = 4;
+/// Regions a map can hold.
+const MAX_REGIONS: usize = 16;
+
+/// What a region of display memory is used for. Numbered kinds tell several regions of the
+/// same use apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    /// The canvas and main image, at [`DisplayConfig::framebuffer_address`].
+    Framebuffer,
+    /// A second full canvas, e.g. for drawing a screen before showing it.
+    BackBuffer,
+    Pip(PipWindow),
+    Canvas(u8),
+    Sprites(u8),
+    /// Glyphs of a [`UserFont`](crate::UserFont).
+    Cgram(u8),
+    GlyphCache(u8),
+    Other(u8),
+}
+
+impl RegionKind {
+    /// Value identifying the kind, comparable in const fns.
+    const fn key(self) -> u16 {
+        match self {
+            RegionKind::Framebuffer => 0x000,
+            RegionKind::BackBuffer => 0x001,
+            RegionKind::Pip(PipWindow::Pip1) => 0x002,
+            RegionKind::Pip(PipWindow::Pip2) => 0x003,
+            RegionKind::Canvas(index) => 0x100 | index as u16,
+            RegionKind::Sprites(index) => 0x200 | index as u16,
+            RegionKind::Cgram(index) => 0x300 | index as u16,
+            RegionKind::GlyphCache(index) => 0x400 | index as u16,
+            RegionKind::Other(index) => 0x500 | index as u16,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub kind: RegionKind,
+    pub address: u32,
+    pub bytes: u32,
+}
+
+impl Region {
+    /// Address just past the region.
+    pub const fn end(&self) -> u32 {
+        self.address + self.bytes
+    }
+
+    pub const fn overlaps(&self, other: &Region) -> bool {
+        self.address < other.end() && other.address < self.end()
+    }
+}
+
+/// Regions of display memory, checked not to overlap each other or run past the end of the
+/// 16 MiB SDRAM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryMap {
+    regions: [Option<Region>; MAX_REGIONS],
+    len: usize,
+}
+
+impl MemoryMap {
+    /// Map holding the framebuffer of `config`.
+    pub const fn new(config: &DisplayConfig) -> Self {
+        let map = Self { regions: [None; MAX_REGIONS], len: 0 };
+        map.with_at(RegionKind::Framebuffer, config.framebuffer_address, config.framebuffer_bytes())
+    }
+
+    /// Add `bytes` for `kind` at the first aligned address after all regions so far. Panics
+    /// if the region does not fit, `kind` is already mapped or the map is full.
+    pub const fn with(self, kind: RegionKind, bytes: u32) -> Self {
+        let address = self.end().next_multiple_of(REGION_ALIGN);
+        self.with_at(kind, address, bytes)
+    }
+
+    /// Add an image of `width` by `height` pixels, with the width rounded up to a multiple of
+    /// 4 pixels as images must be.
+    pub const fn with_image(self, kind: RegionKind, width: u16, height: u16, color_depth: ColorDepth) -> Self {
+        self.with(kind, image_bytes(width, height, color_depth.bytes_per_pixel()))
+    }
+
+    /// Add a region at a fixed `address`, e.g. one that assets or earlier firmware expect.
+    /// Panics like [`with`](Self::with), and if the region overlaps another.
+    pub const fn with_at(mut self, kind: RegionKind, address: u32, bytes: u32) -> Self {
+        assert!(self.len < MAX_REGIONS, "memory map is full");
+        assert!(address.is_multiple_of(REGION_ALIGN), "region address must be a multiple of 4");
+        assert!(address as u64 + bytes as u64 <= SDRAM_BYTES as u64, "region does not fit in display memory");
+        assert!(self.region(kind).is_none(), "region kind is already mapped");
+        let region = Region { kind, address, bytes };
+        let mut index = 0;
+        while index < self.len {
+            if let Some(other) = self.regions[index] {
+                assert!(!region.overlaps(&other), "region overlaps another");
+            }
+            index += 1;
+        }
+        self.regions[self.len] = Some(region);
+        self.len += 1;
+        self
+    }
+
+    pub const fn region(&self, kind: RegionKind) -> Option<Region> {
+        let mut index = 0;
+        while index < self.len {
+            if let Some(region) = self.regions[index] && region.kind.key() == kind.key() {
+                return Some(region);
+            }
+            index += 1;
+        }
+        None
+    }
+
+    /// Start address of the region for `kind`. Panics if there is none.
+    pub const fn address(&self, kind: RegionKind) -> u32 {
+        match self.region(kind) {
+            Some(region) => region.address,
+            None => panic!("region kind is not mapped"),
+        }
+    }
+
+    /// Address just past the highest region.
+    pub const fn end(&self) -> u32 {
+        let mut end = 0;
+        let mut index = 0;
+        while index < self.len {
+            if let Some(region) = self.regions[index] && region.end() > end {
+                end = region.end();
+            }
+            index += 1;
+        }
+        end
+    }
+
+    /// Display memory left after the highest region.
+    pub const fn free_bytes(&self) -> u32 {
+        SDRAM_BYTES - self.end()
+    }
+
+    pub fn regions(&self) -> impl Iterator<Item = Region> + '_ {
+        self.regions[..self.len].iter().flatten().copied()
+    }
+}
@@ -0,0 +1,51 @@
+//! 8080 bus through a memory-mapped external memory controller (STM32 FSMC/FMC, EBI and
+//! similar), where the RS line is driven by an address bit so command and data cycles are
+//! accesses to two different addresses.
+//!
+//! The crate does no unsafe pointer access itself; the two addresses are reached through
+//! [`MmioPort`] implementations supplied by the user, typically thin wrappers around a volatile
+//! register type.
+
+use crate::LT7683Interface;
+use crate::registers::Register;
+use core::convert::Infallible;
+
+/// One byte-wide memory-mapped address, accessed with volatile reads and writes.
+pub trait MmioPort {
+    fn write(&mut self, value: u8);
+    fn read(&mut self) -> u8;
+}
+
+/// Interface over the command (RS low) and data (RS high) addresses of the bus.
+pub struct MmioInterface<C, D> {
+    pub command: C,
+    pub data: D,
+}
+
+impl<C: MmioPort, D: MmioPort> MmioInterface<C, D> {
+    pub fn new(command: C, data: D) -> Self {
+        Self { command, data }
+    }
+}
+
+impl<C: MmioPort, D: MmioPort> LT7683Interface for MmioInterface<C, D> {
+    type Error = Infallible;
+
+    fn write_command(&mut self, register: Register) -> Result<(), Self::Error> {
+        self.command.write(register as u8);
+        Ok(())
+    }
+
+    fn write_data(&mut self, data: u8) -> Result<(), Self::Error> {
+        self.data.write(data);
+        Ok(())
+    }
+
+    fn read_data(&mut self) -> Result<u8, Self::Error> {
+        Ok(self.data.read())
+    }
+
+    fn read_status(&mut self) -> Result<u8, Self::Error> {
+        Ok(self.command.read())
+    }
+}
@@ -0,0 +1,66 @@
+//! Numeric readouts formatted on the stack, without going through `core::fmt`, for values
+//! redrawn many times a second.
+
+use crate::{LT7683, LT7683Interface, Rect, TextAlign, TextStyle, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Longest field [`NumberBuffer`] formats.
+const NUMBER_LEN: usize = 24;
+/// Most digits after the decimal point, as far as an `i32` reaches.
+const MAX_DECIMALS: u8 = 9;
+
+/// Integer or fixed-point value formatted as decimal text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberBuffer {
+    bytes: [u8; NUMBER_LEN],
+    start: usize,
+}
+
+impl NumberBuffer {
+    /// Format `value` with the last `decimals` digits (at most 9) after a decimal point, so
+    /// 1234 with 2 decimals reads `12.34`, padded with leading spaces to `width` characters
+    /// (at most 24).
+    pub fn format(value: i32, decimals: u8, width: u8) -> Self {
+        let decimals = decimals.min(MAX_DECIMALS) as usize;
+        let mut bytes = [b' '; NUMBER_LEN];
+        let mut start = NUMBER_LEN;
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = 0;
+        // Digits from the last, with at least one before the decimal point
+        while digits <= decimals || magnitude > 0 {
+            if digits == decimals && decimals > 0 {
+                start -= 1;
+                bytes[start] = b'.';
+            }
+            start -= 1;
+            bytes[start] = b'0' + (magnitude % 10) as u8;
+            magnitude /= 10;
+            digits += 1;
+        }
+        if value < 0 {
+            start -= 1;
+            bytes[start] = b'-';
+        }
+        let start = start.min(NUMBER_LEN.saturating_sub(width as usize));
+        Self { bytes, start }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only ASCII digits, signs, points and spaces are written
+        core::str::from_utf8(&self.bytes[self.start..]).unwrap_or_default()
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Write `value` as formatted by [`NumberBuffer::format`], right-aligned so it ends at `x`.
+    /// With a background color in `style`, the leading spaces up to `width` characters erase
+    /// whatever a longer value left behind. The charset and font size selected for other
+    /// writes are left unchanged.
+    pub fn draw_value(&mut self, x: u16, y: u16, value: i32, decimals: u8, width: u8, style: TextStyle) -> Result<(), I::Error> {
+        let number = NumberBuffer::format(value, decimals, width);
+        let text = number.as_str();
+        let text_width = self.text_width(text, &style);
+        let rect = Rect::new(x.saturating_sub(text_width), y, text_width, style.cell_height());
+        self.write_text_aligned(text, rect, TextAlign::Right, style)
+    }
+}
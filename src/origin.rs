@@ -0,0 +1,30 @@
+//! Logical coordinate origin, so composite widgets can be drawn in local coordinates and placed
+//! anywhere on the canvas.
+
+use crate::{LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Add `x`, `y` to the canvas coordinates of all following drawing, text, image and BTE
+    /// destination calls, until changed again. The clip rectangle and active window are given
+    /// in the same coordinates and move with the origin. Off-screen drawing is not offset.
+    pub fn set_origin(&mut self, x: u16, y: u16) {
+        if self.origin == (x, y) {
+            return;
+        }
+        self.origin = (x, y);
+        // Cached positions are relative to the old origin
+        self.active_window = None;
+        self.graphic_cursor = None;
+    }
+
+    pub fn origin(&self) -> (u16, u16) {
+        self.origin
+    }
+
+    /// Canvas position of the logical point `x`, `y`, after the origin and rotation.
+    pub(crate) fn to_physical(&self, x: u16, y: u16) -> (u16, u16) {
+        let (x, y) = (x.saturating_add(self.origin.0), y.saturating_add(self.origin.1));
+        self.rotation.rotate_point(x, y, self.config.canvas_width(), self.config.canvas_height())
+    }
+}
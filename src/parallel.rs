@@ -0,0 +1,361 @@
+//! Bit-banged 8080 parallel bus over individual GPIO pins, for HALs without a full-port
+//! abstraction.
+
+use crate::LT7683Interface;
+use crate::registers::Register;
+use embedded_hal::delay::DelayNs;
+use core::cell::RefCell;
+use core::convert::Infallible;
+use core::fmt;
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParallelBusError {
+    /// Setting or reading a pin failed.
+    Pin,
+    /// A read was requested but no RD pin was given.
+    ReadNotSupported,
+}
+
+impl fmt::Display for ParallelBusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParallelBusError::Pin => f.write_str("setting or reading a bus pin failed"),
+            ParallelBusError::ReadNotSupported => f.write_str("read requested on a bus without an RD pin"),
+        }
+    }
+}
+
+impl core::error::Error for ParallelBusError {}
+
+/// Strobe timing of the bus cycles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusTiming {
+    /// Data and RS setup time before the strobe falls.
+    pub setup_ns: u32,
+    /// WR low time.
+    pub write_pulse_ns: u32,
+    /// RD low time before the data pins are sampled.
+    pub read_pulse_ns: u32,
+    /// Time after the strobe rises before the next cycle may start.
+    pub hold_ns: u32,
+}
+
+impl BusTiming {
+    /// Timing for a bus cycle at `hz`, split evenly between strobe low and recovery. Reads keep
+    /// the default access time, which is set by the chip rather than the bus.
+    pub const fn from_bus_frequency(hz: u32) -> Self {
+        let half_period_ns = 500_000_000 / hz;
+        Self {
+            setup_ns: 0,
+            write_pulse_ns: half_period_ns,
+            read_pulse_ns: Self::DEFAULT.read_pulse_ns,
+            hold_ns: half_period_ns,
+        }
+    }
+
+    /// No delays at all, for GPIO that toggles slower than the chip's minimum timing anyway.
+    /// Delay calls are then skipped entirely.
+    pub const NONE: Self = Self {
+        setup_ns: 0,
+        write_pulse_ns: 0,
+        read_pulse_ns: 0,
+        hold_ns: 0,
+    };
+
+    const DEFAULT: Self = Self {
+        setup_ns: 0,
+        write_pulse_ns: 10,
+        read_pulse_ns: 150,
+        hold_ns: 150,
+    };
+}
+
+impl Default for BusTiming {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Delay that does nothing, for a [`ParallelBus`] that needs no delays at all; the strobe
+/// timing then compiles away.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    #[inline(always)]
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Pin that does nothing, for a [`ParallelBus`] whose chip select is driven elsewhere, e.g. by
+/// a [`SharedInterface`](crate::SharedInterface) per chip, or tied low on the board.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoPin;
+
+impl ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoPin {
+    #[inline(always)]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// 8080 bus built from eight data pins, D0 first, and the CS, RS (A0), WR and RD strobes: a
+/// [`ParallelPort`] the chip has to itself, with its CS and RS pins.
+///
+/// Reads need the data pins to be readable while released, e.g. open-drain outputs with
+/// pull-ups. Without an RD pin (tied high on the board) the bus is write only.
+pub struct ParallelBus<P, CS, RS, WR, RD, D> {
+    pub port: ParallelPort<P, WR, RD, D>,
+    pub cs: CS,
+    pub rs: RS,
+}
+
+impl<P, CS, RS, WR, RD, D> ParallelBus<P, CS, RS, WR, RD, D>
+where
+    P: OutputPin + InputPin,
+    CS: OutputPin,
+    RS: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    D: DelayNs,
+{
+    pub fn new(data: [P; 8], cs: CS, rs: RS, wr: WR, rd: Option<RD>, delay: D) -> Self {
+        Self { port: ParallelPort::new(data, wr, rd, delay), cs, rs }
+    }
+}
+
+/// The part of an 8080 bus shared by every device on it: the data pins, D0 first, and the WR
+/// and RD strobes. Each device drives its own CS and RS through a [`ParallelBusDevice`], like
+/// `embedded-hal-bus` does for SPI, so the chip can share the port with other 8080 peripherals.
+/// Several LT7683 chips alone on a bus can also use a [`ParallelBus`] with a
+/// [`SharedInterface`](crate::SharedInterface) per chip.
+pub struct ParallelPort<P, WR, RD, D> {
+    pub data: [P; 8],
+    pub wr: WR,
+    pub rd: Option<RD>,
+    pub delay: D,
+    pub timing: BusTiming,
+}
+
+impl<P, WR, RD, D> ParallelPort<P, WR, RD, D>
+where
+    P: OutputPin + InputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    D: DelayNs,
+{
+    pub fn new(data: [P; 8], wr: WR, rd: Option<RD>, delay: D) -> Self {
+        Self { data, wr, rd, delay, timing: BusTiming::default() }
+    }
+
+    fn strobes(&mut self) -> Strobes<'_, P, WR, RD, D> {
+        Strobes { data: &mut self.data, wr: &mut self.wr, rd: self.rd.as_mut(), delay: &mut self.delay, timing: self.timing }
+    }
+
+    /// Write `bytes` to the device selected by `cs`, with RS at `rs_high`, keeping it selected
+    /// for all of them.
+    fn write<CS: OutputPin, RS: OutputPin>(&mut self, cs: &mut CS, rs: &mut RS, rs_high: bool, bytes: &[u8]) -> Result<(), ParallelBusError> {
+        set(rs, rs_high)?;
+        set(cs, false)?;
+        self.strobes().write(bytes)?;
+        set(cs, true)
+    }
+
+    /// Read one byte from the device selected by `cs`, with RS at `rs_high`.
+    fn read<CS: OutputPin, RS: OutputPin>(&mut self, cs: &mut CS, rs: &mut RS, rs_high: bool) -> Result<u8, ParallelBusError> {
+        if self.rd.is_none() {
+            return Err(ParallelBusError::ReadNotSupported);
+        }
+        set(rs, rs_high)?;
+        set(cs, false)?;
+        let byte = self.strobes().read()?;
+        set(cs, true)?;
+        Ok(byte)
+    }
+}
+
+/// One device on a shared [`ParallelPort`], with its own CS and RS pins. The port is borrowed
+/// for each transfer, so only one device drives it at a time; starting a transfer while
+/// another is in progress, e.g. from an interrupt handler, panics.
+///
+/// ```ignore
+/// let port = RefCell::new(ParallelPort::new(data, wr, Some(rd), delay));
+/// let mut display = LT7683::new(ParallelBusDevice::new(&port, cs_display, rs_display), res, config);
+/// let mut sensor = ParallelBusDevice::new(&port, cs_sensor, rs_sensor);
+/// sensor.write(false, &[0x2C])?;
+/// ```
+pub struct ParallelBusDevice<'a, T, CS, RS> {
+    port: &'a RefCell<T>,
+    pub cs: CS,
+    pub rs: RS,
+}
+
+impl<'a, P, WR, RD, D, CS, RS> ParallelBusDevice<'a, ParallelPort<P, WR, RD, D>, CS, RS>
+where
+    P: OutputPin + InputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    D: DelayNs,
+    CS: OutputPin,
+    RS: OutputPin,
+{
+    pub fn new(port: &'a RefCell<ParallelPort<P, WR, RD, D>>, cs: CS, rs: RS) -> Self {
+        Self { port, cs, rs }
+    }
+
+    /// Write `bytes` with RS at `rs_high`, keeping the device selected for all of them.
+    pub fn write(&mut self, rs_high: bool, bytes: &[u8]) -> Result<(), ParallelBusError> {
+        self.port.borrow_mut().write(&mut self.cs, &mut self.rs, rs_high, bytes)
+    }
+
+    /// Read one byte with RS at `rs_high`.
+    pub fn read(&mut self, rs_high: bool) -> Result<u8, ParallelBusError> {
+        self.port.borrow_mut().read(&mut self.cs, &mut self.rs, rs_high)
+    }
+}
+
+/// Data pins and strobes of a bus, borrowed for the cycles of one transfer once CS and RS are
+/// set.
+struct Strobes<'p, P, WR, RD, D> {
+    data: &'p mut [P; 8],
+    wr: &'p mut WR,
+    rd: Option<&'p mut RD>,
+    delay: &'p mut D,
+    timing: BusTiming,
+}
+
+impl<P, WR, RD, D> Strobes<'_, P, WR, RD, D>
+where
+    P: OutputPin + InputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    D: DelayNs,
+{
+    /// Wait `ns` nanoseconds; zero skips the delay call, which may itself take longer.
+    fn pause(&mut self, ns: u32) {
+        if ns != 0 {
+            self.delay.delay_ns(ns);
+        }
+    }
+
+    fn set_data(&mut self, byte: u8) -> Result<(), ParallelBusError> {
+        for (bit, pin) in self.data.iter_mut().enumerate() {
+            let result = if byte & (1 << bit) != 0 { pin.set_high() } else { pin.set_low() };
+            result.map_err(|_| ParallelBusError::Pin)?;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), ParallelBusError> {
+        for &byte in bytes {
+            self.set_data(byte)?;
+            self.pause(self.timing.setup_ns);
+            set(self.wr, false)?;
+            self.pause(self.timing.write_pulse_ns);
+            set(self.wr, true)?;
+            self.pause(self.timing.hold_ns);
+        }
+        Ok(())
+    }
+
+    fn read(&mut self) -> Result<u8, ParallelBusError> {
+        // Release the data lines so the chip can drive them
+        self.set_data(0xFF)?;
+        self.pause(self.timing.setup_ns);
+        if let Some(rd) = &mut self.rd {
+            set(*rd, false)?;
+        }
+        self.pause(self.timing.read_pulse_ns);
+        let mut byte = 0;
+        for (bit, pin) in self.data.iter_mut().enumerate() {
+            if pin.is_high().map_err(|_| ParallelBusError::Pin)? {
+                byte |= 1 << bit;
+            }
+        }
+        if let Some(rd) = &mut self.rd {
+            set(*rd, true)?;
+        }
+        self.pause(self.timing.hold_ns);
+        Ok(byte)
+    }
+}
+
+fn set<P: OutputPin>(pin: &mut P, high: bool) -> Result<(), ParallelBusError> {
+    let result = if high { pin.set_high() } else { pin.set_low() };
+    result.map_err(|_| ParallelBusError::Pin)
+}
+
+impl<P, CS, RS, WR, RD, D> LT7683Interface for ParallelBus<P, CS, RS, WR, RD, D>
+where
+    P: OutputPin + InputPin,
+    CS: OutputPin,
+    RS: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    D: DelayNs,
+{
+    type Error = ParallelBusError;
+
+    fn write_command(&mut self, register: Register) -> Result<(), Self::Error> {
+        self.port.write(&mut self.cs, &mut self.rs, false, &[register as u8])
+    }
+
+    fn write_data(&mut self, data: u8) -> Result<(), Self::Error> {
+        self.port.write(&mut self.cs, &mut self.rs, true, &[data])
+    }
+
+    /// Keeps CS low for the whole burst.
+    fn write_data_burst(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.port.write(&mut self.cs, &mut self.rs, true, data)
+    }
+
+    fn read_data(&mut self) -> Result<u8, Self::Error> {
+        self.port.read(&mut self.cs, &mut self.rs, true)
+    }
+
+    fn read_status(&mut self) -> Result<u8, Self::Error> {
+        self.port.read(&mut self.cs, &mut self.rs, false)
+    }
+}
+
+impl<P, WR, RD, D, CS, RS> LT7683Interface for ParallelBusDevice<'_, ParallelPort<P, WR, RD, D>, CS, RS>
+where
+    P: OutputPin + InputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+    D: DelayNs,
+    CS: OutputPin,
+    RS: OutputPin,
+{
+    type Error = ParallelBusError;
+
+    fn write_command(&mut self, register: Register) -> Result<(), Self::Error> {
+        self.write(false, &[register as u8])
+    }
+
+    fn write_data(&mut self, data: u8) -> Result<(), Self::Error> {
+        self.write(true, &[data])
+    }
+
+    /// Keeps CS low for the whole burst.
+    fn write_data_burst(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.write(true, data)
+    }
+
+    fn read_data(&mut self) -> Result<u8, Self::Error> {
+        self.read(true)
+    }
+
+    fn read_status(&mut self) -> Result<u8, Self::Error> {
+        self.read(false)
+    }
+}
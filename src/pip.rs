@@ -0,0 +1,192 @@
+//! Picture-in-picture windows: up to two images from display memory shown over the main window,
+//! positioned independently of it.
+
+use crate::registers::Register;
+use crate::{ColorDepth, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipWindow {
+    Pip1,
+    Pip2,
+}
+
+impl PipWindow {
+    /// MPWCTR enable bit.
+    const fn enable_bit(self) -> u8 {
+        match self {
+            PipWindow::Pip1 => 0x80,
+            PipWindow::Pip2 => 0x40,
+        }
+    }
+
+    /// PIPCDEP color depth field position.
+    const fn depth_shift(self) -> u8 {
+        match self {
+            PipWindow::Pip1 => 2,
+            PipWindow::Pip2 => 0,
+        }
+    }
+}
+
+/// Source image and placement of a PIP window. Horizontal positions and the width should be
+/// multiples of 4 pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PipConfig {
+    /// Start address of the source image in display memory.
+    pub image_address: u32,
+    /// Width of the source image in pixels.
+    pub image_width: u16,
+    pub color_depth: ColorDepth,
+    /// Upper-left corner of the shown part within the source image.
+    pub image_x: u16,
+    pub image_y: u16,
+    /// Upper-left corner on screen, in the display's rotated coordinates.
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+/// Handle to a configured PIP window, remembering its position on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pip {
+    window: PipWindow,
+    x: u16,
+    y: u16,
+    /// Size on the panel, needed to place the window under a rotation.
+    width: u16,
+    height: u16,
+}
+
+impl Pip {
+    pub fn window(&self) -> PipWindow {
+        self.window
+    }
+
+    pub fn position(&self) -> (u16, u16) {
+        (self.x, self.y)
+    }
+
+    pub fn show<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>, visible: bool) -> Result<(), I::Error> {
+        let bit = self.window.enable_bit();
+        display.modify_register(Register::Mpwctr, bit, if visible { bit } else { 0 })
+    }
+
+    /// Move the window to `x`, `y` on screen at once. The position is in the display's
+    /// rotated coordinates; the image itself is shown as stored.
+    pub fn set_position<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, x: u16, y: u16) -> Result<(), I::Error> {
+        let (panel_x, panel_y) = display.pip_to_panel(x, y, self.width, self.height);
+        display.pip_registers(self.window)?.set_panel_position(panel_x, panel_y)?;
+        self.x = x;
+        self.y = y;
+        Ok(())
+    }
+
+    /// Slide the window to `x`, `y` in `steps` equal steps, updating the position right after
+    /// every `frame_delay`-th vertical sync.
+    pub fn move_to<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &mut self,
+        display: &mut LT7683<I, RESET, W>,
+        x: u16,
+        y: u16,
+        steps: u16,
+        frame_delay: u16,
+    ) -> Result<(), I::Error> {
+        let (from_x, from_y) = (self.x as i32, self.y as i32);
+        let steps = steps.max(1) as i32;
+        for step in 1..=steps {
+            for _ in 0..frame_delay.max(1) {
+                display.wait_vsync()?;
+            }
+            let step_x = from_x + (x as i32 - from_x) * step / steps;
+            let step_y = from_y + (y as i32 - from_y) * step / steps;
+            self.set_position(display, step_x as u16, step_y as u16)?;
+        }
+        Ok(())
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Configure a PIP window and return a handle to it. The window stays hidden until shown
+    /// with [`Pip::show`].
+    pub fn configure_pip(&mut self, window: PipWindow, config: &PipConfig) -> Result<Pip, I::Error> {
+        let shift = window.depth_shift();
+        self.modify_register(Register::Pipcdep, 0x03 << shift, (config.color_depth as u8) << shift)?;
+        let mut registers = self.pip_registers(window)?;
+        registers.set_image(config.image_address, config.image_width)?;
+        registers.set_image_offset(config.image_x, config.image_y)?;
+        registers.set_size(config.width, config.height)?;
+        let mut pip = Pip { window, x: 0, y: 0, width: config.width, height: config.height };
+        pip.set_position(self, config.x, config.y)?;
+        Ok(pip)
+    }
+
+    /// Panel position of a PIP window `width` by `height` panel pixels whose upper-left corner
+    /// in rotated screen coordinates is `x`, `y`. The origin only applies to the canvas.
+    fn pip_to_panel(&self, x: u16, y: u16, width: u16, height: u16) -> (u16, u16) {
+        let (width, height) = if self.rotation.is_quarter_turn() { (height, width) } else { (width, height) };
+        let area = self.rotation.rotate_rect(Rect::new(x, y, width, height), self.config.width, self.config.height);
+        (area.x, area.y)
+    }
+
+    /// Point the PIP registers, which both windows share, at `window` and return a guard to
+    /// program them with. The guard borrows the display, so the selection cannot change
+    /// before it is dropped.
+    pub fn pip_registers(&mut self, window: PipWindow) -> Result<PipRegisters<'_, I, RESET, W>, I::Error> {
+        let select = match window {
+            PipWindow::Pip1 => 0x00,
+            PipWindow::Pip2 => 0x10,
+        };
+        self.modify_register(Register::Mpwctr, 0x10, select)?;
+        Ok(PipRegisters { display: self, window })
+    }
+}
+
+/// The PIP registers while they are selected for one window, from
+/// [`LT7683::pip_registers`]. Values are in panel coordinates, without rotation.
+pub struct PipRegisters<'a, I: LT7683Interface, RESET, W> {
+    display: &'a mut LT7683<I, RESET, W>,
+    window: PipWindow,
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> PipRegisters<'_, I, RESET, W> {
+    pub fn window(&self) -> PipWindow {
+        self.window
+    }
+
+    /// Start address and width in pixels of the source image.
+    pub fn set_image(&mut self, address: u32, width: u16) -> Result<(), I::Error> {
+        self.write_u32([Register::Pisa1, Register::Pisa2, Register::Pisa3, Register::Pisa4], address)?;
+        self.write_u16([Register::Piw1, Register::Piw2], width)
+    }
+
+    /// Upper-left corner of the shown part within the source image.
+    pub fn set_image_offset(&mut self, x: u16, y: u16) -> Result<(), I::Error> {
+        self.write_u16([Register::Pwiulx1, Register::Pwiulx2], x)?;
+        self.write_u16([Register::Pwiuly1, Register::Pwiuly2], y)
+    }
+
+    pub fn set_size(&mut self, width: u16, height: u16) -> Result<(), I::Error> {
+        self.write_u16([Register::Pww1, Register::Pww2], width)?;
+        self.write_u16([Register::Pwh1, Register::Pwh2], height)
+    }
+
+    /// Upper-left corner of the window on the panel.
+    pub fn set_panel_position(&mut self, x: u16, y: u16) -> Result<(), I::Error> {
+        self.write_u16([Register::Pwdulx1, Register::Pwdulx2], x)?;
+        self.write_u16([Register::Pwduly1, Register::Pwduly2], y)
+    }
+
+    fn write_u16(&mut self, registers: [Register; 2], value: u16) -> Result<(), I::Error> {
+        self.display.write_register(registers[0], value as u8)?;
+        self.display.write_register(registers[1], (value >> 8) as u8)
+    }
+
+    fn write_u32(&mut self, registers: [Register; 4], value: u32) -> Result<(), I::Error> {
+        for (index, register) in registers.into_iter().enumerate() {
+            self.display.write_register(register, (value >> (8 * index)) as u8)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+//! The chip's two PWM outputs, commonly wired to the backlight (PWM0) and a buzzer (PWM1).
+
+use crate::registers::Register;
+use crate::{LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+/// Timer input clock after the shared prescaler.
+const TIMER_CLOCK_HZ: u32 = 10_000_000;
+/// Backlight PWM frequency, above the audible range.
+const BACKLIGHT_PWM_HZ: u32 = 20_000;
+/// Time between brightness updates while fading.
+const FADE_STEP_MS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PwmChannel {
+    Pwm0,
+    Pwm1,
+}
+
+/// Backlight driven by the chip's PWM0 output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BacklightConfig {
+    /// Brightness after init, 0-255.
+    pub level: u8,
+    /// Duration of the fade in after init and wakeup, and of the fade out before power saving.
+    pub fade_ms: u16,
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Output `frequency_hz` on `channel` with `duty` out of 255 high.
+    pub fn set_pwm(&mut self, channel: PwmChannel, frequency_hz: u32, duty: u8) -> Result<(), I::Error> {
+        let prescaler = (self.config.core_clock_hz / TIMER_CLOCK_HZ).clamp(1, 256) - 1;
+        self.write_register(Register::Psclr, prescaler as u8)?;
+        // Smallest clock divider (1, 2, 4 or 8) that keeps the period within the 16-bit counter
+        let frequency_hz = frequency_hz.max(1);
+        let divider = (0..=3u8)
+            .find(|&divider| (TIMER_CLOCK_HZ >> divider) / frequency_hz <= 0x1_0000)
+            .unwrap_or(3);
+        let count = ((TIMER_CLOCK_HZ >> divider) / frequency_hz).clamp(1, 0x1_0000) - 1;
+        let compare = (count + 1) * duty as u32 / 255;
+        let (registers, mux_shift, start_bits) = match channel {
+            PwmChannel::Pwm0 => ([Register::Tcmpb0l, Register::Tcmpb0h, Register::Tcntb0l, Register::Tcntb0h], 0, 0x03),
+            PwmChannel::Pwm1 => ([Register::Tcmpb1l, Register::Tcmpb1h, Register::Tcntb1l, Register::Tcntb1h], 2, 0x30),
+        };
+        // Clock divider and PWM output function of the pin
+        let mux = divider << (mux_shift + 4) | 0x02 << mux_shift;
+        self.modify_register(Register::Pmuxr, 0x33 << mux_shift, mux)?;
+        self.write_register(registers[0], compare as u8)?;
+        self.write_register(registers[1], (compare >> 8) as u8)?;
+        self.write_register(registers[2], count as u8)?;
+        self.write_register(registers[3], (count >> 8) as u8)?;
+        // Start with auto reload
+        self.modify_register(Register::Pcfgr, start_bits, start_bits)
+    }
+
+    pub fn stop_pwm(&mut self, channel: PwmChannel) -> Result<(), I::Error> {
+        let start_bits = match channel {
+            PwmChannel::Pwm0 => 0x03,
+            PwmChannel::Pwm1 => 0x30,
+        };
+        self.modify_register(Register::Pcfgr, start_bits, 0x00)
+    }
+
+    /// Sound a buzzer on PWM1 at `frequency_hz` for `duration_ms`, blocking until done.
+    pub fn beep<D: DelayNs>(&mut self, frequency_hz: u32, duration_ms: u32, delay: &mut D) -> Result<(), I::Error> {
+        self.set_pwm(PwmChannel::Pwm1, frequency_hz, 128)?;
+        delay.delay_ms(duration_ms);
+        self.stop_pwm(PwmChannel::Pwm1)
+    }
+
+    /// Set the PWM0 backlight brightness, 0-255.
+    pub fn set_backlight(&mut self, level: u8) -> Result<(), I::Error> {
+        self.set_pwm(PwmChannel::Pwm0, BACKLIGHT_PWM_HZ, level)?;
+        self.backlight_level = level;
+        Ok(())
+    }
+
+    /// Change the backlight brightness gradually over `duration_ms`.
+    pub fn fade_backlight<D: DelayNs>(&mut self, level: u8, duration_ms: u32, delay: &mut D) -> Result<(), I::Error> {
+        let from = self.backlight_level as i32;
+        let steps = (duration_ms / FADE_STEP_MS).max(1) as i32;
+        for step in 1..=steps {
+            self.set_backlight((from + (level as i32 - from) * step / steps) as u8)?;
+            if step < steps {
+                delay.delay_ms(FADE_STEP_MS);
+            }
+        }
+        Ok(())
+    }
+}
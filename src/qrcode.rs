@@ -0,0 +1,46 @@
+//! QR code rendering. Enabled with the `qrcode` feature.
+//!
+//! The driver does not encode data itself; the symbol comes from a closure reporting whether a
+//! module is dark. That fits both precomputed bit matrices and no_std generators, e.g. with
+//! `qrcodegen-no-heap`: `display.draw_qr_code(x, y, 200, qr.size() as u16, |mx, my| qr.get_module(mx as i32, my as i32), 0x000000, 0xFFFFFF)`.
+
+use crate::{CheckedError, LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Light modules required around the symbol by the QR specification.
+const QUIET_ZONE: u16 = 4;
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Draw a `modules` x `modules` QR symbol into the `size` x `size` square at (x, y).
+    /// The symbol is scaled by the largest whole factor that fits together with the quiet zone and
+    /// centered in the square. Runs of dark modules in a row are drawn with one BTE fill each.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_qr_code<F: Fn(u16, u16) -> bool>(
+        &mut self, x: u16, y: u16, size: u16, modules: u16,
+        is_dark: F, dark_color: u32, light_color: u32
+    ) -> Result<(), CheckedError<I::Error>> {
+        if modules == 0 {
+            return Ok(());
+        }
+        let scale = (size / (modules + 2 * QUIET_ZONE)).max(1);
+        let symbol = modules * scale;
+        let offset = size.saturating_sub(symbol) / 2;
+        let (x0, y0) = (x + offset, y + offset);
+        self.bte_solid_fill(x, y, size.max(symbol), size.max(symbol), light_color)?;
+        for my in 0..modules {
+            let mut mx = 0;
+            while mx < modules {
+                if !is_dark(mx, my) {
+                    mx += 1;
+                    continue;
+                }
+                let run_start = mx;
+                while mx < modules && is_dark(mx, my) {
+                    mx += 1;
+                }
+                self.bte_solid_fill(x0 + run_start * scale, y0 + my * scale, (mx - run_start) * scale, scale, dark_color)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! Raw register access, for chip features the high-level API does not cover yet.
+//!
+//! Registers are written through [`Lt7683Ll`], reachable as the `ll` field of
+//! [`LT7683`](crate::LT7683). The driver caches some drawing state; after writing color, active
+//! window, cursor or line coordinate registers directly, call
+//! [`LT7683::invalidate_cache`](crate::LT7683::invalidate_cache).
+//!
+//! ```ignore
+//! use lt7683::raw::{self, Register};
+//!
+//! // Drive the chip's GPIO A pins, which the driver does not use itself
+//! display.ll.write_register(Register::Gpioad, 0x00)?;
+//! display.ll.write_register(Register::Gpioa, 0x01)?;
+//! let busy = display.ll.read_status()? & raw::status::CORE_BUSY != 0;
+//! ```
+
+pub use crate::LT7683Interface;
+pub use crate::ll::Lt7683Ll;
+pub use crate::registers::Register;
+
+/// Bits of the status register, as returned by `read_status`.
+pub mod status {
+    /// Host memory write FIFO is full.
+    pub const WRITE_FIFO_FULL: u8 = 0x80;
+    /// Host memory write FIFO is empty.
+    pub const WRITE_FIFO_EMPTY: u8 = 0x40;
+    /// Host memory read FIFO is full.
+    pub const READ_FIFO_FULL: u8 = 0x20;
+    /// Host memory read FIFO is empty.
+    pub const READ_FIFO_EMPTY: u8 = 0x10;
+    /// Drawing engine (geometry, text or BTE) is busy.
+    pub const CORE_BUSY: u8 = 0x08;
+    /// SDRAM is initialized and ready.
+    pub const SDRAM_READY: u8 = 0x04;
+    /// Chip is in a power saving mode.
+    pub const POWER_SAVING: u8 = 0x02;
+    /// An interrupt is pending.
+    pub const INTERRUPT: u8 = 0x01;
+}
+
+/// Interrupt sources, as bits of the INTEN, INTF and MINTFR registers. Flags in INTF are cleared
+/// by writing 1.
+pub mod interrupt {
+    /// Wakeup from a power saving mode.
+    pub const WAKEUP: u8 = 0x80;
+    /// PWM timer 1 period.
+    pub const PWM1: u8 = 0x10;
+    /// PWM timer 0 period.
+    pub const PWM0: u8 = 0x08;
+    /// External interrupt input.
+    pub const EXTERNAL: u8 = 0x04;
+    /// Drawing, BTE or serial flash DMA finished.
+    pub const TASK_DONE: u8 = 0x02;
+    /// Vertical sync.
+    pub const VSYNC: u8 = 0x01;
+}
+
+/// Bits of the chip configuration register (CCR).
+pub mod ccr {
+    /// Restart the PLLs with their new settings when written; reads back set once they are
+    /// locked.
+    pub const PLL_READY: u8 = 0x80;
+}
+
+/// Bits of the power management register (PMU). The low two bits select the mode.
+pub mod pmu {
+    /// Enter the selected power saving mode; cleared to wake up.
+    pub const ENTER: u8 = 0x80;
+}
+
+/// Bits of the SDRAM control register (SDRCR).
+pub mod sdrcr {
+    /// Let the SDRAM enter power saving together with the chip.
+    pub const POWER_SAVING: u8 = 0x04;
+    /// With `POWER_SAVING`, power down instead of self-refresh. Memory contents are lost.
+    pub const POWER_DOWN: u8 = 0x02;
+    /// Start SDRAM initialization.
+    pub const INITIALIZE: u8 = 0x01;
+}
+
+/// Bits of the input control register (ICR).
+pub mod icr {
+    /// Text mode; clear for graphic mode.
+    pub const TEXT_MODE: u8 = 0x04;
+}
+
+/// Bits of BTE control register 0.
+pub mod bte_ctrl0 {
+    /// Start a block transfer when written; reads back set while it is running.
+    pub const ENABLE: u8 = 0x10;
+}
+
+/// Bits of the serial flash DMA control register.
+pub mod dma_ctrl {
+    /// Start a DMA transfer when written; reads back set while it is running.
+    pub const START: u8 = 0x01;
+}
+
+/// Bits of the serial flash/ROM control register (SFL_CTRL). The low four bits select the flash
+/// read command.
+pub mod sfl_ctrl {
+    /// Use the flash on chip select 1 instead of 0.
+    pub const CHIP_SELECT_1: u8 = 0x80;
+    /// DMA access; clear for font ROM access by the text engine.
+    pub const DMA_MODE: u8 = 0x40;
+    /// 32-bit flash addresses; clear for 24-bit.
+    pub const ADDRESS_32_BIT: u8 = 0x20;
+}
+
+/// Bits of the SPI master control register (SPIMCR2). The low two bits select the SPI mode.
+pub mod spimcr2 {
+    /// Drive chip select 1 instead of 0.
+    pub const CHIP_SELECT_1: u8 = 0x20;
+    /// Hold the selected chip select active (low).
+    pub const SELECT_ACTIVE: u8 = 0x10;
+}
+
+/// Bits of the SPI master status register (SPIMSR).
+pub mod spimsr {
+    /// Nothing left to send.
+    pub const TX_FIFO_EMPTY: u8 = 0x80;
+    /// Nothing received to read.
+    pub const RX_FIFO_EMPTY: u8 = 0x20;
+}
@@ -1,4 +1,5 @@
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Register {
     // Configuration registers:
     /// Software reset register.
@@ -27,7 +28,12 @@ pub enum Register {
     Cpllc2 = 0x0A,
 
     // Interrupt control registers:
-    // TODO:
+    /// Interrupt Enable Register.
+    Inten = 0x0B,
+    /// Interrupt Event Flag Register.
+    Intf = 0x0C,
+    /// Mask Interrupt Flag Register.
+    Mintfr = 0x0D,
 
     // LCD display control registers:
     /// Main/PIP Window Control Register.
@@ -236,7 +242,30 @@ pub enum Register {
     Fgcb = 0xD4,
 
     // PWM control registers:
-    // TODO: page 165 https://www.buydisplay.com/download/ic/LT7683.pdf
+    /// PWM Prescaler Register.
+    Psclr = 0x84,
+    /// PWM Clock Mux Register.
+    Pmuxr = 0x85,
+    /// PWM Configuration Register.
+    Pcfgr = 0x86,
+    /// Timer 0 Dead Zone Length Register.
+    DzLength = 0x87,
+    /// Timer 0 Compare Buffer Register 1.
+    Tcmpb0l = 0x88,
+    /// Timer 0 Compare Buffer Register 2.
+    Tcmpb0h = 0x89,
+    /// Timer 0 Count Buffer Register 1.
+    Tcntb0l = 0x8A,
+    /// Timer 0 Count Buffer Register 2.
+    Tcntb0h = 0x8B,
+    /// Timer 1 Compare Buffer Register 1.
+    Tcmpb1l = 0x8C,
+    /// Timer 1 Compare Buffer Register 2.
+    Tcmpb1h = 0x8D,
+    /// Timer 1 Count Buffer Register 1.
+    Tcntb1l = 0x8E,
+    /// Timer 1 Count Buffer Register 2.
+    Tcntb1h = 0x8F,
 
     // Bit block transfer engine (BTE) control registers:
     /// BTE Control Register 0.
@@ -313,15 +342,60 @@ pub enum Register {
     BteHig0 = 0xB3,
     /// BTE Window Height 1.
     BteHig1 = 0xB4,
+    /// Alpha Blending (window alpha level of BTE memory copy with opacity).
+    Apbctrl = 0xB5,
 
     // Serial Flash & SPI Master Control Registers:
-    // TODO: page 176 https://www.buydisplay.com/download/ic/LT7683.pdf
+    /// Serial Flash DMA Control Register.
+    DmaCtrl = 0xB6,
+    /// Serial Flash/ROM Control Register.
+    SflCtrl = 0xB7,
+    /// SPI Master Tx/Rx FIFO Data Register.
+    Spidr = 0xB8,
+    /// SPI Master Control Register.
+    Spimcr2 = 0xB9,
+    /// SPI Master Status Register.
+    Spimsr = 0xBA,
+    /// SPI Clock Period.
+    SpiDivsor = 0xBB,
+    /// Serial Flash DMA Source Starting Address 0.
+    DmaSstr0 = 0xBC,
+    /// Serial Flash DMA Source Starting Address 1.
+    DmaSstr1 = 0xBD,
+    /// Serial Flash DMA Source Starting Address 2.
+    DmaSstr2 = 0xBE,
+    /// Serial Flash DMA Source Starting Address 3.
+    DmaSstr3 = 0xBF,
+    /// DMA Destination Upper-Left Corner X-Coordinate 0.
+    DmaDx0 = 0xC0,
+    /// DMA Destination Upper-Left Corner X-Coordinate 1.
+    DmaDx1 = 0xC1,
+    /// DMA Destination Upper-Left Corner Y-Coordinate 0.
+    DmaDy0 = 0xC2,
+    /// DMA Destination Upper-Left Corner Y-Coordinate 1.
+    DmaDy1 = 0xC3,
+    /// DMA Block Width 0.
+    DmaDwr0 = 0xC6,
+    /// DMA Block Width 1.
+    DmaDwr1 = 0xC7,
+    /// DMA Block Height 0.
+    DmaDhr0 = 0xC8,
+    /// DMA Block Height 1.
+    DmaDhr1 = 0xC9,
+    /// DMA Source Picture Width 0.
+    DmaSwr0 = 0xCA,
+    /// DMA Source Picture Width 1.
+    DmaSwr1 = 0xCB,
 
     // Text engine registers:
     /// Character Control Register 0.
     Ccr0 = 0xCC,
     /// Character Control Register 1.
     Ccr1 = 0xCD,
+    /// GT Character ROM Select.
+    GtfntSel = 0xCE,
+    /// GT Character ROM Control Register.
+    GtfntCr = 0xCF,
     /// Character Line gap Setting Register.
     Fldr = 0xD0,
     /// Character to Character Space Setting Register.
@@ -334,6 +408,12 @@ pub enum Register {
     Bgcb = 0xD7,
     /// CGRAM Start Address 0.
     CgramStr0 = 0xDB,
+    /// CGRAM Start Address 1.
+    CgramStr1 = 0xDC,
+    /// CGRAM Start Address 2.
+    CgramStr2 = 0xDD,
+    /// CGRAM Start Address 3.
+    CgramStr3 = 0xDE,
 
     // Power Management Control Register:
     /// Power Management Register
@@ -367,3 +447,221 @@ pub enum Register {
     /// GPIO-D.
     Gpiod = 0xF6,
 }
+
+impl Register {
+    /// Every documented register, in address order.
+    pub const ALL: [Register; 212] = [
+        Register::Srr,
+        Register::Ccr,
+        Register::Macr,
+        Register::Icr,
+        Register::Mrwdp,
+        Register::Ppllc1,
+        Register::Ppllc2,
+        Register::Mpllc1,
+        Register::Mpllc2,
+        Register::Cpllc1,
+        Register::Cpllc2,
+        Register::Inten,
+        Register::Intf,
+        Register::Mintfr,
+        Register::Mpwctr,
+        Register::Pipcdep,
+        Register::Dpcr,
+        Register::Pcsr,
+        Register::Hdwr,
+        Register::Hdwftr,
+        Register::Hndr,
+        Register::Hndftr,
+        Register::Hstr,
+        Register::Hpwr,
+        Register::Vdhr1,
+        Register::Vdhr2,
+        Register::Vndr1,
+        Register::Vndr2,
+        Register::Vstr,
+        Register::Vpwr,
+        Register::Misa1,
+        Register::Misa2,
+        Register::Misa3,
+        Register::Misa4,
+        Register::Miw1,
+        Register::Miw2,
+        Register::Mwulx1,
+        Register::Mwulx2,
+        Register::Mwuly1,
+        Register::Mwuly2,
+        Register::Pwdulx1,
+        Register::Pwdulx2,
+        Register::Pwduly1,
+        Register::Pwduly2,
+        Register::Pisa1,
+        Register::Pisa2,
+        Register::Pisa3,
+        Register::Pisa4,
+        Register::Piw1,
+        Register::Piw2,
+        Register::Pwiulx1,
+        Register::Pwiulx2,
+        Register::Pwiuly1,
+        Register::Pwiuly2,
+        Register::Pww1,
+        Register::Pww2,
+        Register::Pwh1,
+        Register::Pwh2,
+        Register::Gtccr,
+        Register::Btcr,
+        Register::Curhs,
+        Register::Curvs,
+        Register::Gchp1,
+        Register::Gchp2,
+        Register::Gcvp1,
+        Register::Gcvp2,
+        Register::Gcc0,
+        Register::Gcc1,
+        Register::Cvssa1,
+        Register::Cvssa2,
+        Register::Cvssa3,
+        Register::Cvssa4,
+        Register::CvsImwth1,
+        Register::CvsImwth2,
+        Register::AwulX1,
+        Register::AwulX2,
+        Register::AwulY1,
+        Register::AwulY2,
+        Register::AwWth1,
+        Register::AwWth2,
+        Register::AwHt1,
+        Register::AwHt2,
+        Register::AwColor,
+        Register::Curh1,
+        Register::Curh2,
+        Register::Curv1,
+        Register::Curv2,
+        Register::FCurx1,
+        Register::FCurx2,
+        Register::FCury1,
+        Register::FCury2,
+        Register::Dcr0,
+        Register::Dlhsr1,
+        Register::Dlhsr2,
+        Register::Dlvsr1,
+        Register::Dlvsr2,
+        Register::Dlher1,
+        Register::Dlher2,
+        Register::Dlver1,
+        Register::Dlver2,
+        Register::Dtph1,
+        Register::Dtph2,
+        Register::Dtpv1,
+        Register::Dtpv2,
+        Register::Dcr1,
+        Register::EllA1,
+        Register::EllA2,
+        Register::EllB1,
+        Register::EllB2,
+        Register::Dehr1,
+        Register::Dehr2,
+        Register::Devr1,
+        Register::Devr2,
+        Register::Fgcr,
+        Register::Fgcg,
+        Register::Fgcb,
+        Register::Psclr,
+        Register::Pmuxr,
+        Register::Pcfgr,
+        Register::DzLength,
+        Register::Tcmpb0l,
+        Register::Tcmpb0h,
+        Register::Tcntb0l,
+        Register::Tcntb0h,
+        Register::Tcmpb1l,
+        Register::Tcmpb1h,
+        Register::Tcntb1l,
+        Register::Tcntb1h,
+        Register::BteCtrl0,
+        Register::BteCtrl1,
+        Register::BteColr,
+        Register::S0Str0,
+        Register::S0Str1,
+        Register::S0Str2,
+        Register::S0Str3,
+        Register::S0Wth0,
+        Register::S0Wth1,
+        Register::S0X0,
+        Register::S0X1,
+        Register::S0Y0,
+        Register::S0Y1,
+        Register::S1Str0,
+        Register::S1Str1,
+        Register::S1Str2,
+        Register::S1Str3,
+        Register::S1Wth0,
+        Register::S1Wth1,
+        Register::S1X0,
+        Register::S1X1,
+        Register::S1Y0,
+        Register::S1Y1,
+        Register::DtStr0,
+        Register::DtStr1,
+        Register::DtStr2,
+        Register::DtStr3,
+        Register::DtWth0,
+        Register::DtWth1,
+        Register::DtX0,
+        Register::DtX1,
+        Register::DtY0,
+        Register::DtY1,
+        Register::BteWth0,
+        Register::BteWth1,
+        Register::BteHig0,
+        Register::BteHig1,
+        Register::Apbctrl,
+        Register::DmaCtrl,
+        Register::SflCtrl,
+        Register::Spidr,
+        Register::Spimcr2,
+        Register::Spimsr,
+        Register::SpiDivsor,
+        Register::DmaSstr0,
+        Register::DmaSstr1,
+        Register::DmaSstr2,
+        Register::DmaSstr3,
+        Register::DmaDx0,
+        Register::DmaDx1,
+        Register::DmaDy0,
+        Register::DmaDy1,
+        Register::DmaDwr0,
+        Register::DmaDwr1,
+        Register::DmaDhr0,
+        Register::DmaDhr1,
+        Register::DmaSwr0,
+        Register::DmaSwr1,
+        Register::Ccr0,
+        Register::Ccr1,
+        Register::GtfntSel,
+        Register::GtfntCr,
+        Register::Fldr,
+        Register::F2fssr,
+        Register::Bgcr,
+        Register::Bgcg,
+        Register::Bgcb,
+        Register::CgramStr0,
+        Register::CgramStr1,
+        Register::CgramStr2,
+        Register::CgramStr3,
+        Register::Pmu,
+        Register::Sdrar,
+        Register::Sdrmd,
+        Register::SdrRef1,
+        Register::SdrRef2,
+        Register::Sdrcr,
+        Register::Gpioad,
+        Register::Gpioa,
+        Register::Gpiob,
+        Register::Gpiocd,
+        Register::Gpioc,
+        Register::Gpiodd,
+        Register::Gpiod,
+    ];
+}
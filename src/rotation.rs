@@ -0,0 +1,205 @@
+//! Mounting orientation. With a rotation set, drawing, text positions, windows, the graphic
+//! cursor and the PIP position are given in logical coordinates and mapped to the panel by the
+//! driver, so application code is the same for any orientation.
+//!
+//! Shapes, fills, memory port images, mono bitmaps and windows follow the rotation completely.
+//! What the chip renders or copies on its own keeps the panel orientation: text engine glyphs
+//! stay upright on the panel, and BTE and flash copies take their source as it is stored, so
+//! off-screen images for a rotated display are stored rotated.
+
+use crate::{LT7683, LT7683Interface, MemoryDirection, Quadrant, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Clockwise rotation of the logical coordinate system relative to the canvas, for panels
+/// mounted in portrait or upside down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    /// Whether logical width and height are swapped relative to the canvas.
+    pub const fn is_quarter_turn(self) -> bool {
+        matches!(self, Rotation::Deg90 | Rotation::Deg270)
+    }
+
+    /// Position of the logical point `x`, `y` on a canvas of `width` by `height` pixels.
+    /// Points outside the logical area are clamped to the canvas edge.
+    pub(crate) const fn rotate_point(self, x: u16, y: u16, width: u16, height: u16) -> (u16, u16) {
+        match self {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (width.saturating_sub(1).saturating_sub(y), x),
+            Rotation::Deg180 => (width.saturating_sub(1).saturating_sub(x), height.saturating_sub(1).saturating_sub(y)),
+            Rotation::Deg270 => (y, height.saturating_sub(1).saturating_sub(x)),
+        }
+    }
+
+    /// Inverse of [`rotate_point`](Self::rotate_point).
+    pub(crate) const fn unrotate_point(self, x: u16, y: u16, width: u16, height: u16) -> (u16, u16) {
+        match self {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (y, width.saturating_sub(1).saturating_sub(x)),
+            Rotation::Deg180 => (width.saturating_sub(1).saturating_sub(x), height.saturating_sub(1).saturating_sub(y)),
+            Rotation::Deg270 => (height.saturating_sub(1).saturating_sub(y), x),
+        }
+    }
+
+    /// Canvas area covered by the logical `rect`.
+    pub(crate) fn rotate_rect(self, rect: Rect, width: u16, height: u16) -> Rect {
+        if rect.is_empty() || self == Rotation::Deg0 {
+            return rect;
+        }
+        let (ax, ay) = self.rotate_point(rect.x, rect.y, width, height);
+        // Last pixel, clamped to the coordinate range for areas reaching past it
+        let right = (rect.x as u32 + rect.width as u32 - 1).min(u16::MAX as u32) as u16;
+        let bottom = (rect.y as u32 + rect.height as u32 - 1).min(u16::MAX as u32) as u16;
+        let (bx, by) = self.rotate_point(right, bottom, width, height);
+        let (left, top) = (ax.min(bx), ay.min(by));
+        let span = |low: u16, high: u16| (high as u32 - low as u32 + 1).min(u16::MAX as u32) as u16;
+        Rect::new(left, top, span(left, ax.max(bx)), span(top, ay.max(by)))
+    }
+
+    /// Canvas quadrant showing the logical `quadrant`.
+    pub(crate) const fn rotate_quadrant(self, quadrant: Quadrant) -> Quadrant {
+        const CLOCKWISE: [Quadrant; 4] = [Quadrant::UpperRight, Quadrant::BottomRight, Quadrant::BottomLeft, Quadrant::UpperLeft];
+        let index = match quadrant {
+            Quadrant::UpperRight => 0,
+            Quadrant::BottomRight => 1,
+            Quadrant::BottomLeft => 2,
+            Quadrant::UpperLeft => 3,
+        };
+        CLOCKWISE[(index + self as usize) % 4]
+    }
+
+    /// Canvas memory write direction and whether rows have to be sent bottom row first, so an
+    /// image sent row by row from its logical upper-left corner lands upright.
+    pub(crate) const fn write_layout(self) -> (MemoryDirection, bool) {
+        match self {
+            Rotation::Deg0 => (MemoryDirection::LeftRightTopDown, false),
+            Rotation::Deg90 => (MemoryDirection::TopDownLeftRight, true),
+            Rotation::Deg180 => (MemoryDirection::RightLeftTopDown, true),
+            Rotation::Deg270 => (MemoryDirection::BottomUpLeftRight, false),
+        }
+    }
+}
+
+impl<I: LT7683Interface, RESET, W> LT7683<I, RESET, W> {
+    /// Canvas size in logical coordinates, with width and height swapped when rotated by 90 or
+    /// 270 degrees.
+    pub fn logical_size(&self) -> (u16, u16) {
+        let (width, height) = (self.config.canvas_width(), self.config.canvas_height());
+        if self.rotation.is_quarter_turn() { (height, width) } else { (width, height) }
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Rotate all following drawing relative to the canvas. Coordinates given to the driver,
+    /// including the origin and clip rectangle, are logical from then on; content already on
+    /// the canvas is left as it is.
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        if self.rotation == rotation {
+            return;
+        }
+        self.rotation = rotation;
+        // Cached positions are logical and map elsewhere now
+        self.active_window = None;
+        self.graphic_cursor = None;
+    }
+
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Canvas area covered by the logical `rect`.
+    pub(crate) fn rect_to_physical(&self, rect: Rect) -> Rect {
+        let (x, y) = (rect.x.saturating_add(self.origin.0), rect.y.saturating_add(self.origin.1));
+        let moved = Rect::new(x, y, rect.width, rect.height);
+        self.rotation.rotate_rect(moved, self.config.canvas_width(), self.config.canvas_height())
+    }
+
+    /// Logical position of the canvas point `x`, `y`.
+    pub(crate) fn to_logical(&self, x: u16, y: u16) -> (u16, u16) {
+        let (x, y) = self.rotation.unrotate_point(x, y, self.config.canvas_width(), self.config.canvas_height());
+        (x.saturating_sub(self.origin.0), y.saturating_sub(self.origin.1))
+    }
+
+    /// Run `draw` in canvas coordinates, without origin, rotation or clip rectangle.
+    pub(crate) fn with_physical_coordinates<E, F>(&mut self, draw: F) -> Result<(), E>
+    where
+        F: FnOnce(&mut Self) -> Result<(), E>,
+    {
+        let (clip, origin, rotation) = (self.clip.take(), self.origin, self.rotation);
+        self.set_origin(0, 0);
+        self.set_rotation(Rotation::Deg0);
+        let result = draw(self);
+        self.clip = clip;
+        self.set_rotation(rotation);
+        self.set_origin(origin.0, origin.1);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use proptest::prelude::*;
+
+    const ROTATIONS: [Rotation; 4] = [Rotation::Deg0, Rotation::Deg90, Rotation::Deg180, Rotation::Deg270];
+
+    /// Logical size of a `width` by `height` canvas.
+    fn logical(rotation: Rotation, width: u16, height: u16) -> (u16, u16) {
+        if rotation.is_quarter_turn() { (height, width) } else { (width, height) }
+    }
+
+    proptest! {
+        #[test]
+        fn points_round_trip(rotation in 0usize..4, width in 1u16..2048, height in 1u16..2048, x: u16, y: u16) {
+            let rotation = ROTATIONS[rotation];
+            let (logical_width, logical_height) = logical(rotation, width, height);
+            let (x, y) = (x % logical_width, y % logical_height);
+            let (canvas_x, canvas_y) = rotation.rotate_point(x, y, width, height);
+            prop_assert!(canvas_x < width && canvas_y < height);
+            prop_assert_eq!(rotation.unrotate_point(canvas_x, canvas_y, width, height), (x, y));
+        }
+
+        #[test]
+        fn rects_round_trip(rotation in 0usize..4, width in 1u16..2048, height in 1u16..2048, corners: [u16; 4]) {
+            let rotation = ROTATIONS[rotation];
+            let (logical_width, logical_height) = logical(rotation, width, height);
+            let (x1, x2) = (corners[0] % logical_width, corners[1] % logical_width);
+            let (y1, y2) = (corners[2] % logical_height, corners[3] % logical_height);
+            let rect = Rect::new(x1.min(x2), y1.min(y2), x1.abs_diff(x2) + 1, y1.abs_diff(y2) + 1);
+            let canvas = rotation.rotate_rect(rect, width, height);
+            prop_assert!(canvas.x as u32 + canvas.width as u32 <= width as u32);
+            prop_assert!(canvas.y as u32 + canvas.height as u32 <= height as u32);
+            // The corners of the canvas area map back onto those of the logical one
+            let (right, bottom) = canvas.last_pixel();
+            let (ax, ay) = rotation.unrotate_point(canvas.x, canvas.y, width, height);
+            let (bx, by) = rotation.unrotate_point(right, bottom, width, height);
+            let (last_x, last_y) = rect.last_pixel();
+            prop_assert_eq!((ax.min(bx), ay.min(by), ax.max(bx), ay.max(by)), (rect.x, rect.y, last_x, last_y));
+        }
+    }
+
+    #[test]
+    fn rects_at_the_far_edge_stay_on_the_canvas() {
+        // 800x480 canvas; the rect fills the logical lower-right corner
+        let expected = [
+            Rect::new(790, 475, 10, 5),
+            Rect::new(0, 470, 5, 10),
+            Rect::new(0, 0, 10, 5),
+            Rect::new(795, 0, 5, 10),
+        ];
+        for (rotation, expected) in ROTATIONS.into_iter().zip(expected) {
+            let (width, height) = logical(rotation, 800, 480);
+            let rect = Rect::new(width - 10, height - 5, 10, 5);
+            assert_eq!(rotation.rotate_rect(rect, 800, 480), expected, "{rotation:?}");
+        }
+    }
+}
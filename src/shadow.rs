@@ -0,0 +1,71 @@
+//! Shadow copy of written control registers. Enabled with the `shadow` feature.
+//!
+//! The shadow lets read-modify-write updates work without bus reads, which matters when the
+//! read path is not wired, and records the complete configured state for dumping or restoring.
+
+use crate::registers::Register;
+
+/// Registers that start operations or move data rather than hold configuration. They are never
+/// recorded, so restoring the shadow cannot re-trigger them.
+const TRIGGER_REGISTERS: [Register; 10] = [
+    Register::Srr,
+    Register::Intf,
+    Register::Mrwdp,
+    Register::Dcr0,
+    Register::Dcr1,
+    Register::BteCtrl0,
+    Register::DmaCtrl,
+    Register::Spidr,
+    Register::Pmu,
+    Register::Sdrcr,
+];
+
+/// Last value written to each control register.
+#[derive(Clone)]
+pub struct RegisterShadow {
+    values: [u8; 256],
+    /// Register recorded at each address, `None` when nothing was written yet.
+    recorded: [Option<Register>; 256],
+}
+
+impl Default for RegisterShadow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegisterShadow {
+    pub const fn new() -> Self {
+        Self { values: [0; 256], recorded: [None; 256] }
+    }
+
+    /// Last value written to the register at `address`, if any.
+    pub fn get(&self, address: u8) -> Option<u8> {
+        self.recorded[address as usize].map(|_| self.values[address as usize])
+    }
+
+    /// Forget all recorded values.
+    pub fn clear(&mut self) {
+        self.recorded = [None; 256];
+    }
+
+    /// Recorded (address, value) pairs in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.entries().map(|(register, value)| (register as u8, value))
+    }
+
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (Register, u8)> + '_ {
+        self.recorded
+            .iter()
+            .zip(self.values.iter())
+            .filter_map(|(register, &value)| register.map(|register| (register, value)))
+    }
+
+    pub(crate) fn record(&mut self, register: Register, value: u8) {
+        if TRIGGER_REGISTERS.contains(&register) {
+            return;
+        }
+        self.values[register as usize] = value;
+        self.recorded[register as usize] = Some(register);
+    }
+}
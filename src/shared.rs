@@ -0,0 +1,68 @@
+//! Several chips on one bus, each selected by its own chip select pin, e.g. the two screens of a
+//! dual-display instrument cluster on one parallel port.
+//!
+//! The bus is built with a [`NoPin`](crate::NoPin) chip select and kept in a `RefCell`; every
+//! display gets a [`SharedInterface`] with its own CS pin:
+//!
+//! ```ignore
+//! let bus = RefCell::new(ParallelBus::new(data, NoPin, rs, wr, Some(rd), delay));
+//! let mut left = LT7683::new(SharedInterface::new(&bus, cs_left), res_left, DisplayConfig::new());
+//! let mut right = LT7683::new(SharedInterface::new(&bus, cs_right), res_right, DisplayConfig::new());
+//! ```
+//!
+//! Use it when every device on the bus is an LT7683, over any [`LT7683Interface`], as it only
+//! adds a chip select to each transfer. To share a parallel port with other 8080 peripherals,
+//! which need their own RS pin and transfers of their own, use
+//! [`ParallelBusDevice`](crate::ParallelBusDevice) instead.
+
+use crate::LT7683Interface;
+use crate::registers::Register;
+use core::cell::RefCell;
+use embedded_hal::digital::OutputPin;
+
+/// Interface to one chip on a shared bus. The bus is borrowed and the chip selected for each
+/// bus cycle or burst, so only one chip drives the bus at a time. Using the bus while another
+/// device is in the middle of a transfer, e.g. from an interrupt handler, panics.
+pub struct SharedInterface<'a, B, CS> {
+    bus: &'a RefCell<B>,
+    pub cs: CS,
+}
+
+impl<'a, B: LT7683Interface, CS: OutputPin> SharedInterface<'a, B, CS> {
+    pub fn new(bus: &'a RefCell<B>, cs: CS) -> Self {
+        Self { bus, cs }
+    }
+
+    fn with_bus<R>(&mut self, f: impl FnOnce(&mut B) -> Result<R, B::Error>) -> Result<R, B::Error> {
+        let mut bus = self.bus.borrow_mut();
+        let _ = self.cs.set_low();
+        let result = f(&mut bus);
+        let _ = self.cs.set_high();
+        result
+    }
+}
+
+impl<B: LT7683Interface, CS: OutputPin> LT7683Interface for SharedInterface<'_, B, CS> {
+    type Error = B::Error;
+
+    fn write_command(&mut self, register: Register) -> Result<(), Self::Error> {
+        self.with_bus(|bus| bus.write_command(register))
+    }
+
+    fn write_data(&mut self, data: u8) -> Result<(), Self::Error> {
+        self.with_bus(|bus| bus.write_data(data))
+    }
+
+    /// Keeps the chip selected for the whole burst.
+    fn write_data_burst(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        self.with_bus(|bus| bus.write_data_burst(data))
+    }
+
+    fn read_data(&mut self) -> Result<u8, Self::Error> {
+        self.with_bus(|bus| bus.read_data())
+    }
+
+    fn read_status(&mut self) -> Result<u8, Self::Error> {
+        self.with_bus(|bus| bus.read_status())
+    }
+}
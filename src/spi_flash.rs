@@ -0,0 +1,139 @@
+//! Commands sent straight to a serial flash through the chip's SPI master, for asset update
+//! flows: checking that an erase or program has finished and guarding production assets with
+//! the flash's block protection.
+
+use crate::raw::{spimcr2, spimsr};
+use crate::registers::Register;
+use crate::{FlashReadMode, FlashTarget, LT7683, LT7683Interface, WaitStrategy};
+use core::fmt;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+const WRITE_STATUS: u8 = 0x01;
+const READ_STATUS: u8 = 0x05;
+const WRITE_ENABLE: u8 = 0x06;
+/// Block protect bits BP0-BP2, which cover the whole array on common 25-series parts.
+const PROTECT_ALL: u8 = 0x1C;
+/// Longest a status register write takes on common parts.
+const WRITE_STATUS_TIMEOUT_MS: u32 = 50;
+/// Status reads after which a byte that has not come back is given up on. A byte takes 8
+/// serial clocks, far less than one register read over the host bus.
+const EXCHANGE_POLLS: u32 = 1000;
+
+/// Error of the commands sent to a serial flash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlashError<E> {
+    /// The interface failed.
+    Bus(E),
+    /// The SPI master did not finish shifting a byte, e.g. because its clock is stopped.
+    Timeout,
+}
+
+impl<E> From<E> for FlashError<E> {
+    fn from(error: E) -> Self {
+        FlashError::Bus(error)
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for FlashError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlashError::Bus(error) => write!(f, "interface error: {error:?}"),
+            FlashError::Timeout => f.write_str("SPI master did not complete the transfer"),
+        }
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for FlashError<E> {}
+
+/// Status register 1 of a serial flash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlashStatus(pub u8);
+
+impl FlashStatus {
+    /// An erase, program or status write is still running (WIP).
+    pub const fn is_busy(self) -> bool {
+        self.0 & 0x01 != 0
+    }
+
+    /// Writes are enabled (WEL).
+    pub const fn is_write_enabled(self) -> bool {
+        self.0 & 0x02 != 0
+    }
+
+    /// Any block protect bit is set.
+    pub const fn is_protected(self) -> bool {
+        self.0 & PROTECT_ALL != 0
+    }
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy> LT7683<I, RESET, W> {
+    /// Send `command` to the device on `target`, then read `response.len()` bytes, with its
+    /// chip select held active throughout.
+    pub fn flash_command(&mut self, target: FlashTarget, command: &[u8], response: &mut [u8]) -> Result<(), FlashError<I::Error>> {
+        self.select_flash(target, 0x00, FlashReadMode::Normal)?;
+        let chip_select = match target {
+            FlashTarget::Cs0 => 0x00,
+            FlashTarget::Cs1 => spimcr2::CHIP_SELECT_1,
+        };
+        // SPI mode 0
+        self.write_register(Register::Spimcr2, chip_select | spimcr2::SELECT_ACTIVE)?;
+        let result = self.flash_exchange(command, response);
+        self.write_register(Register::Spimcr2, chip_select)?;
+        result
+    }
+
+    fn flash_exchange(&mut self, command: &[u8], response: &mut [u8]) -> Result<(), FlashError<I::Error>> {
+        for &byte in command {
+            self.spi_exchange(byte)?;
+        }
+        for byte in response {
+            *byte = self.spi_exchange(0x00)?;
+        }
+        Ok(())
+    }
+
+    /// Clock out `byte` and return the byte clocked in meanwhile.
+    fn spi_exchange(&mut self, byte: u8) -> Result<u8, FlashError<I::Error>> {
+        self.write_register(Register::Spidr, byte)?;
+        for _ in 0..EXCHANGE_POLLS {
+            if self.read_register(Register::Spimsr)? & spimsr::RX_FIFO_EMPTY == 0 {
+                return Ok(self.read_register(Register::Spidr)?);
+            }
+        }
+        Err(FlashError::Timeout)
+    }
+
+    pub fn read_flash_status(&mut self, target: FlashTarget) -> Result<FlashStatus, FlashError<I::Error>> {
+        let mut status = [0];
+        self.flash_command(target, &[READ_STATUS], &mut status)?;
+        Ok(FlashStatus(status[0]))
+    }
+
+    /// Poll the flash on `target` once per millisecond until it is no longer busy, for up to
+    /// `timeout_ms`, and return whether it finished.
+    pub fn wait_flash_ready<D: DelayNs>(&mut self, target: FlashTarget, timeout_ms: u32, delay: &mut D) -> Result<bool, FlashError<I::Error>> {
+        for _ in 0..=timeout_ms {
+            if !self.read_flash_status(target)?.is_busy() {
+                return Ok(true);
+            }
+            delay.delay_ms(1);
+        }
+        Ok(false)
+    }
+
+    /// Set or clear the block protect bits of the flash on `target`, and return whether the
+    /// flash reports the requested state afterwards. The other status bits (status register
+    /// protect, top/bottom and sector protect, quad enable) are written back unchanged. A
+    /// flash whose status register is locked by its WP pin keeps its protection.
+    pub fn set_flash_write_protect<D: DelayNs>(&mut self, target: FlashTarget, protect: bool, delay: &mut D) -> Result<bool, FlashError<I::Error>> {
+        let status = self.read_flash_status(target)?.0;
+        let bits = if protect { PROTECT_ALL } else { 0x00 };
+        self.flash_command(target, &[WRITE_ENABLE], &mut [])?;
+        self.flash_command(target, &[WRITE_STATUS, (status & !PROTECT_ALL) | bits], &mut [])?;
+        if !self.wait_flash_ready(target, WRITE_STATUS_TIMEOUT_MS, delay)? {
+            return Ok(false);
+        }
+        Ok(self.read_flash_status(target)?.is_protected() == protect)
+    }
+}
@@ -0,0 +1,83 @@
+//! Icon sets and tiles packed into one image in display memory, drawn cell by cell with BTE
+//! copies.
+
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Image in display memory divided into equally sized cells, numbered left to right, then top
+/// to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpriteAtlas {
+    address: u32,
+    /// Row length of the stored image, rounded up as images must be.
+    stride: u16,
+    cell_width: u16,
+    cell_height: u16,
+    columns: u16,
+    count: u16,
+    /// Color left out when drawing cells, for sprites that are not rectangular.
+    pub chroma_key: Option<u32>,
+}
+
+impl SpriteAtlas {
+    /// Upload `image` (raw pixels in memory port byte order, row by row, `image_width` pixels
+    /// per row) to `address` in display memory, which must not overlap the canvas or other
+    /// images, and divide it into `cell_width` by `cell_height` cells.
+    pub fn load<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        display: &mut LT7683<I, RESET, W>,
+        address: u32,
+        image: &[u8],
+        image_width: u16,
+        cell_width: u16,
+        cell_height: u16,
+    ) -> Result<Self, CheckedError<I::Error>> {
+        let row_bytes = image_width as usize * display.config.color_depth.bytes_per_pixel() as usize;
+        let image_height = image.len().checked_div(row_bytes).unwrap_or(0).min(u16::MAX as usize) as u16;
+        let stride = image_width.next_multiple_of(4);
+        display.draw_offscreen(address, stride, image_height, |display| {
+            display.write_image(Rect::new(0, 0, image_width, image_height), image)
+        })?;
+        let columns = image_width.checked_div(cell_width).unwrap_or(0);
+        let rows = image_height.checked_div(cell_height).unwrap_or(0);
+        Ok(Self {
+            address,
+            stride,
+            cell_width,
+            cell_height,
+            columns,
+            count: columns * rows,
+            chroma_key: None,
+        })
+    }
+
+    /// Number of whole cells in the image.
+    pub fn len(&self) -> u16 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn cell_size(&self) -> (u16, u16) {
+        (self.cell_width, self.cell_height)
+    }
+
+    /// Draw cell `index` with its upper-left corner at `x`, `y`. Indices past the last cell
+    /// draw nothing.
+    pub fn draw_cell<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &self,
+        display: &mut LT7683<I, RESET, W>,
+        index: u16,
+        x: u16,
+        y: u16,
+    ) -> Result<(), CheckedError<I::Error>> {
+        if index >= self.count {
+            return Ok(());
+        }
+        let cell_x = index % self.columns * self.cell_width;
+        let cell_y = index / self.columns * self.cell_height;
+        let source = (self.address, self.stride);
+        display.bte_copy(source, cell_x, cell_y, x, y, self.cell_width, self.cell_height, self.chroma_key)
+    }
+}
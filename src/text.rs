@@ -0,0 +1,429 @@
+//! Text engine character sources and encodings.
+
+use crate::FlashTarget;
+
+/// Character set the text engine renders with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    /// Internal CGROM, ISO/IEC 8859-1 (Western European).
+    #[default]
+    Iso8859_1,
+    /// Internal CGROM, ISO/IEC 8859-2 (Central European).
+    Iso8859_2,
+    /// Internal CGROM, ISO/IEC 8859-4 (Baltic).
+    Iso8859_4,
+    /// Internal CGROM, ISO/IEC 8859-5 (Cyrillic).
+    Iso8859_5,
+    /// External Genitop font ROM on the serial flash interface.
+    External(ExternalFont),
+    /// User-defined glyphs in display memory, e.g. loaded with
+    /// [`load_flash_font`](crate::LT7683::load_flash_font). Character codes are Latin-1.
+    User(UserFont),
+}
+
+/// Byte sent for characters the selected character set cannot represent.
+const REPLACEMENT: u8 = b'?';
+
+impl Charset {
+    /// Character source select (bits 7-6) and internal CGROM select (bits 1-0) of CCR0.
+    pub(crate) fn ccr0_bits(self) -> u8 {
+        match self {
+            Charset::Iso8859_1 => 0x00,
+            Charset::Iso8859_2 => 0x01,
+            Charset::Iso8859_4 => 0x02,
+            Charset::Iso8859_5 => 0x03,
+            Charset::External(_) => 0x40,
+            Charset::User(_) => 0x80,
+        }
+    }
+
+    /// Cell size text is written in when `selected` is the selected font size; user fonts
+    /// have a fixed size.
+    pub(crate) const fn font_size(self, selected: FontSize) -> FontSize {
+        match self {
+            Charset::User(font) => font.font_size,
+            _ => selected,
+        }
+    }
+
+    /// Encode `c` for the text engine into `buf`, returning the bytes to send.
+    /// Characters that cannot be represented are replaced with `?`.
+    pub(crate) fn encode(self, c: char, buf: &mut [u8; 2]) -> &[u8] {
+        let code = c as u32;
+        let byte = match self {
+            Charset::Iso8859_1 => latin1(code),
+            Charset::Iso8859_2 => iso8859(code, &ISO8859_2_HIGH),
+            Charset::Iso8859_4 => iso8859(code, &ISO8859_4_HIGH),
+            Charset::Iso8859_5 => iso8859(code, &ISO8859_5_HIGH),
+            Charset::User(font) => latin1(code).filter(|&code| font.contains(code)),
+            Charset::External(font) => match font.encoding {
+                RomEncoding::Unicode => {
+                    let code = if code <= 0xFFFF { code as u16 } else { REPLACEMENT as u16 };
+                    *buf = code.to_be_bytes();
+                    return &buf[..];
+                }
+                RomEncoding::Iso8859_1 => latin1(code),
+                RomEncoding::Iso8859_2 => iso8859(code, &ISO8859_2_HIGH),
+                RomEncoding::Iso8859_4 => iso8859(code, &ISO8859_4_HIGH),
+                RomEncoding::Iso8859_5 => iso8859(code, &ISO8859_5_HIGH),
+                // Multi-byte and remaining single-byte encodings share ASCII; anything else has
+                // to be pre-encoded and written with `write_text_encoded`.
+                _ if code < 0x80 => Some(code as u8),
+                _ => None,
+            },
+        };
+        buf[0] = byte.unwrap_or(REPLACEMENT);
+        &buf[..1]
+    }
+}
+
+fn latin1(code: u32) -> Option<u8> {
+    u8::try_from(code).ok()
+}
+
+/// Map a code point to an ISO/IEC 8859 byte, given the code points of bytes 0xA0-0xFF.
+/// The lower half is identical to Unicode in all parts.
+fn iso8859(code: u32, high: &[u16; 96]) -> Option<u8> {
+    if code < 0xA0 {
+        return Some(code as u8);
+    }
+    high.iter().position(|&c| c as u32 == code).map(|i| 0xA0 + i as u8)
+}
+
+const ISO8859_2_HIGH: [u16; 96] = [
+    0x00A0, 0x0104, 0x02D8, 0x0141, 0x00A4, 0x013D, 0x015A, 0x00A7,
+    0x00A8, 0x0160, 0x015E, 0x0164, 0x0179, 0x00AD, 0x017D, 0x017B,
+    0x00B0, 0x0105, 0x02DB, 0x0142, 0x00B4, 0x013E, 0x015B, 0x02C7,
+    0x00B8, 0x0161, 0x015F, 0x0165, 0x017A, 0x02DD, 0x017E, 0x017C,
+    0x0154, 0x00C1, 0x00C2, 0x0102, 0x00C4, 0x0139, 0x0106, 0x00C7,
+    0x010C, 0x00C9, 0x0118, 0x00CB, 0x011A, 0x00CD, 0x00CE, 0x010E,
+    0x0110, 0x0143, 0x0147, 0x00D3, 0x00D4, 0x0150, 0x00D6, 0x00D7,
+    0x0158, 0x016E, 0x00DA, 0x0170, 0x00DC, 0x00DD, 0x0162, 0x00DF,
+    0x0155, 0x00E1, 0x00E2, 0x0103, 0x00E4, 0x013A, 0x0107, 0x00E7,
+    0x010D, 0x00E9, 0x0119, 0x00EB, 0x011B, 0x00ED, 0x00EE, 0x010F,
+    0x0111, 0x0144, 0x0148, 0x00F3, 0x00F4, 0x0151, 0x00F6, 0x00F7,
+    0x0159, 0x016F, 0x00FA, 0x0171, 0x00FC, 0x00FD, 0x0163, 0x02D9,
+];
+
+const ISO8859_4_HIGH: [u16; 96] = [
+    0x00A0, 0x0104, 0x0138, 0x0156, 0x00A4, 0x0128, 0x013B, 0x00A7,
+    0x00A8, 0x0160, 0x0112, 0x0122, 0x0166, 0x00AD, 0x017D, 0x00AF,
+    0x00B0, 0x0105, 0x02DB, 0x0157, 0x00B4, 0x0129, 0x013C, 0x02C7,
+    0x00B8, 0x0161, 0x0113, 0x0123, 0x0167, 0x014A, 0x017E, 0x014B,
+    0x0100, 0x00C1, 0x00C2, 0x00C3, 0x00C4, 0x00C5, 0x00C6, 0x012E,
+    0x010C, 0x00C9, 0x0118, 0x00CB, 0x0116, 0x00CD, 0x00CE, 0x012A,
+    0x0110, 0x0145, 0x014C, 0x0136, 0x00D4, 0x00D5, 0x00D6, 0x00D7,
+    0x00D8, 0x0172, 0x00DA, 0x00DB, 0x00DC, 0x0168, 0x016A, 0x00DF,
+    0x0101, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x012F,
+    0x010D, 0x00E9, 0x0119, 0x00EB, 0x0117, 0x00ED, 0x00EE, 0x012B,
+    0x0111, 0x0146, 0x014D, 0x0137, 0x00F4, 0x00F5, 0x00F6, 0x00F7,
+    0x00F8, 0x0173, 0x00FA, 0x00FB, 0x00FC, 0x0169, 0x016B, 0x02D9,
+];
+
+const ISO8859_5_HIGH: [u16; 96] = [
+    0x00A0, 0x0401, 0x0402, 0x0403, 0x0404, 0x0405, 0x0406, 0x0407,
+    0x0408, 0x0409, 0x040A, 0x040B, 0x040C, 0x00AD, 0x040E, 0x040F,
+    0x0410, 0x0411, 0x0412, 0x0413, 0x0414, 0x0415, 0x0416, 0x0417,
+    0x0418, 0x0419, 0x041A, 0x041B, 0x041C, 0x041D, 0x041E, 0x041F,
+    0x0420, 0x0421, 0x0422, 0x0423, 0x0424, 0x0425, 0x0426, 0x0427,
+    0x0428, 0x0429, 0x042A, 0x042B, 0x042C, 0x042D, 0x042E, 0x042F,
+    0x0430, 0x0431, 0x0432, 0x0433, 0x0434, 0x0435, 0x0436, 0x0437,
+    0x0438, 0x0439, 0x043A, 0x043B, 0x043C, 0x043D, 0x043E, 0x043F,
+    0x0440, 0x0441, 0x0442, 0x0443, 0x0444, 0x0445, 0x0446, 0x0447,
+    0x0448, 0x0449, 0x044A, 0x044B, 0x044C, 0x044D, 0x044E, 0x044F,
+    0x2116, 0x0451, 0x0452, 0x0453, 0x0454, 0x0455, 0x0456, 0x0457,
+    0x0458, 0x0459, 0x045A, 0x045B, 0x045C, 0x00A7, 0x045E, 0x045F,
+];
+
+/// Font ROM configuration for [`Charset::External`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExternalFont {
+    pub rom: FontRom,
+    /// Chip select the ROM is wired to.
+    pub chip_select: FlashTarget,
+    pub encoding: RomEncoding,
+    pub width: FontWidth,
+}
+
+/// User-defined glyphs in display memory (CGRAM) for [`Charset::User`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserFont {
+    /// Cell size of the glyphs; text is written in this size whatever font size is selected.
+    pub font_size: FontSize,
+    /// Display memory address of the glyph for code 0. Glyphs are indexed by character code.
+    pub address: u32,
+    /// Code of the first glyph loaded.
+    pub first_code: u8,
+    pub glyph_count: u16,
+}
+
+impl UserFont {
+    pub const fn contains(&self, code: u8) -> bool {
+        code >= self.first_code && ((code - self.first_code) as u16) < self.glyph_count
+    }
+}
+
+/// Supported Genitop font ROM parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontRom {
+    Gt21l16t1w = 0x00,
+    Gt30l16u2w = 0x01,
+    Gt30l24t3y = 0x02,
+    Gt30l24m1z = 0x03,
+    Gt30l32s4w = 0x04,
+    Gt20l24f6y = 0x05,
+    Gt21l24s1w = 0x06,
+}
+
+/// Encoding of the characters sent to the text engine when using an external font ROM.
+/// Which encodings are available depends on the ROM part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RomEncoding {
+    Gb2312 = 0x00,
+    /// GB12345 / GB18030.
+    Gb12345 = 0x01,
+    Big5 = 0x02,
+    Unicode = 0x03,
+    Ascii = 0x04,
+    UniJapanese = 0x05,
+    Jis0208 = 0x06,
+    /// Latin, Greek, Cyrillic, Arabic, Thai and Hebrew.
+    LatinGreekCyrillic = 0x07,
+    Iso8859_1 = 0x11,
+    Iso8859_2 = 0x12,
+    Iso8859_3 = 0x13,
+    Iso8859_4 = 0x14,
+    Iso8859_5 = 0x15,
+    Iso8859_7 = 0x16,
+    Iso8859_8 = 0x17,
+    Iso8859_9 = 0x18,
+    Iso8859_10 = 0x19,
+    Iso8859_11 = 0x1A,
+    Iso8859_13 = 0x1B,
+    Iso8859_14 = 0x1C,
+    Iso8859_15 = 0x1D,
+    Iso8859_16 = 0x1E,
+}
+
+/// Glyph width style of the external font ROM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontWidth {
+    Fixed = 0x00,
+    Arial = 0x01,
+    Roman = 0x02,
+    Bold = 0x03,
+}
+
+/// Printable ASCII characters, space to tilde, covered by [`FontWidths`].
+pub(crate) const WIDTH_TABLE_CHARS: usize = 95;
+
+/// Advance of each printable ASCII character of a proportional external font, unscaled, as
+/// read with [`read_font_widths`](crate::LT7683::read_font_widths).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FontWidths {
+    pub(crate) widths: [u8; WIDTH_TABLE_CHARS],
+}
+
+impl FontWidths {
+    /// Unscaled advance of `c`, `None` outside printable ASCII.
+    pub fn width(&self, c: char) -> Option<u16> {
+        let index = (c as u32).checked_sub(' ' as u32)? as usize;
+        self.widths.get(index).map(|&width| width as u16)
+    }
+}
+
+/// Where a font ROM keeps the widths of its proportional ASCII glyphs, from the ROM's
+/// datasheet: one byte per character from space on, `stride` bytes apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WidthTable {
+    /// Chip select of the font ROM.
+    pub chip_select: FlashTarget,
+    /// ROM address of the width of the space character.
+    pub address: u32,
+    /// Bytes from one character's width to the next, e.g. the glyph size for ROMs that store
+    /// the width in front of each glyph.
+    pub stride: u16,
+}
+
+/// Horizontal placement of text within an area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Character cell size of the text engine, before scaling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FontSize {
+    #[default]
+    Size8x16 = 0x00,
+    Size12x24 = 0x01,
+    Size16x32 = 0x02,
+}
+
+impl FontSize {
+    /// Width and height of a character cell in pixels, before scaling.
+    pub const fn cell_size(self) -> (u16, u16) {
+        match self {
+            FontSize::Size8x16 => (8, 16),
+            FontSize::Size12x24 => (12, 24),
+            FontSize::Size16x32 => (16, 32),
+        }
+    }
+
+    /// Bytes of one glyph bitmap, rows MSB first and padded to whole bytes.
+    pub const fn glyph_bytes(self) -> u16 {
+        let (width, height) = self.cell_size();
+        width.div_ceil(8) * height
+    }
+
+    /// Display memory taken by a user font of this size, whose glyphs are indexed by all 256
+    /// character codes.
+    pub const fn user_font_bytes(self) -> u32 {
+        self.glyph_bytes() as u32 * 256
+    }
+}
+
+/// Appearance of a run of text written with
+/// [`write_spans`](crate::LT7683::write_spans).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextStyle {
+    pub fg_color: u32,
+    /// Background of the character cells; `None` leaves the canvas visible.
+    pub bg_color: Option<u32>,
+    /// 1-4.
+    pub scale_x: u8,
+    /// 1-4.
+    pub scale_y: u8,
+    pub font_size: FontSize,
+    pub charset: Charset,
+}
+
+impl TextStyle {
+    /// Unscaled default font in `fg_color` on a transparent background.
+    pub const fn new(fg_color: u32) -> Self {
+        Self {
+            fg_color,
+            bg_color: None,
+            scale_x: 1,
+            scale_y: 1,
+            font_size: FontSize::Size8x16,
+            charset: Charset::Iso8859_1,
+        }
+    }
+
+    /// Advance between characters in pixels, after scaling. The internal character sets are
+    /// fixed width.
+    pub const fn char_width(&self) -> u16 {
+        self.charset.font_size(self.font_size).cell_size().0 * self.scale_x() as u16
+    }
+
+    /// Whether characters have their own advance, with an external font ROM in one of its
+    /// proportional styles.
+    pub const fn is_proportional(&self) -> bool {
+        matches!(self.charset, Charset::External(font) if !matches!(font.width, FontWidth::Fixed))
+    }
+
+    /// Horizontal scale clamped to 1-4, as the text engine applies it.
+    pub(crate) const fn scale_x(&self) -> u8 {
+        if self.scale_x == 0 { 1 } else if self.scale_x > 4 { 4 } else { self.scale_x }
+    }
+
+    /// Height of a character cell in pixels, after scaling.
+    pub const fn cell_height(&self) -> u16 {
+        let scale = if self.scale_y == 0 { 1 } else if self.scale_y > 4 { 4 } else { self.scale_y };
+        self.charset.font_size(self.font_size).cell_size().1 * scale as u16
+    }
+}
+
+/// Split off the first line of `text` that fits in `max_width` pixels, with each character
+/// `char_width` wide: up to a newline, else up to the last space that keeps it within
+/// `max_width`, else as much of a word too long for a line as fits. Returns the line and the
+/// rest with the break consumed.
+pub(crate) fn wrap_line(text: &str, max_width: u32, char_width: impl Fn(char) -> u32) -> (&str, &str) {
+    let mut last_space = None;
+    let mut width = 0;
+    for (index, c) in text.char_indices() {
+        if c == '\n' {
+            return (&text[..index], &text[index + 1..]);
+        }
+        width += char_width(c);
+        if width > max_width {
+            return match last_space {
+                _ if c == ' ' => (&text[..index], &text[index + 1..]),
+                Some(space) => (&text[..space], &text[space + 1..]),
+                None => (&text[..index], &text[index..]),
+            };
+        }
+        if c == ' ' {
+            last_space = Some(index);
+        }
+    }
+    (text, "")
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use proptest::prelude::*;
+
+    fn unit(_: char) -> u32 {
+        1
+    }
+
+    proptest! {
+        #[test]
+        fn lines_fit_and_the_rest_follows_them(text in "[a-c \n\u{e4}\u{65e5}]{0,40}", max_width in 1u32..20) {
+            let (line, rest) = wrap_line(&text, max_width, |c| c.len_utf8() as u32);
+            prop_assert!(line.chars().map(|c| c.len_utf8() as u32).sum::<u32>() <= max_width);
+            prop_assert!(text.starts_with(line) && text.ends_with(rest));
+            // At most the one space or newline the line was broken at is consumed
+            prop_assert!(text.len() - line.len() - rest.len() <= 1);
+        }
+    }
+
+    #[test]
+    fn lines_of_exactly_the_width_are_kept_whole() {
+        assert_eq!(wrap_line("ab cd", 5, unit), ("ab cd", ""));
+        assert_eq!(wrap_line("ab cd ef", 5, unit), ("ab cd", "ef"));
+        assert_eq!(wrap_line("abcde\nf", 5, unit), ("abcde", "f"));
+    }
+
+    #[test]
+    fn words_longer_than_the_line_are_split() {
+        assert_eq!(wrap_line("abcdefgh", 3, unit), ("abc", "defgh"));
+        // The word starts on a line of its own first
+        assert_eq!(wrap_line("ab abcdefgh", 4, unit), ("ab", "abcdefgh"));
+        assert_eq!(wrap_line("abc", 0, unit), ("", "abc"));
+    }
+
+    #[test]
+    fn multibyte_characters_are_not_cut() {
+        assert_eq!(wrap_line("\u{e4}\u{f6}\u{fc} \u{df}", 3, unit), ("\u{e4}\u{f6}\u{fc}", "\u{df}"));
+        assert_eq!(wrap_line("\u{65e5}\u{672c}\u{8a9e}", 5, |_| 2), ("\u{65e5}\u{672c}", "\u{8a9e}"));
+    }
+
+    #[test]
+    fn empty_text_gives_empty_line() {
+        assert_eq!(wrap_line("", 10, unit), ("", ""));
+        assert_eq!(wrap_line("\n", 10, unit), ("", ""));
+    }
+
+    #[test]
+    fn font_widths_cover_printable_ascii() {
+        let mut widths = FontWidths { widths: [0; WIDTH_TABLE_CHARS] };
+        for (index, width) in widths.widths.iter_mut().enumerate() {
+            *width = index as u8;
+        }
+        assert_eq!(widths.width(' '), Some(0));
+        assert_eq!(widths.width('A'), Some(33));
+        assert_eq!(widths.width('~'), Some(94));
+        assert_eq!(widths.width('\n'), None);
+        assert_eq!(widths.width('\u{7f}'), None);
+        assert_eq!(widths.width('\u{e4}'), None);
+    }
+}
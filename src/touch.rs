@@ -0,0 +1,52 @@
+//! Mapping touch controller readings to the coordinates drawing uses, so a touch lands on what
+//! was drawn under it whatever the rotation and origin.
+
+use crate::math::scale_reading;
+use crate::{LT7683, LT7683Interface};
+
+/// How a touch controller's raw readings cover the panel. A controller mounted mirrored has
+/// the readings of the opposite edges swapped, e.g. `raw_left` greater than `raw_right`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TouchCalibration {
+    /// Readings at the left and right panel edges, along the panel's horizontal axis.
+    pub raw_left: u16,
+    pub raw_right: u16,
+    /// Readings at the top and bottom panel edges.
+    pub raw_top: u16,
+    pub raw_bottom: u16,
+    /// The controller reports the panel's vertical axis as x and the horizontal axis as y.
+    pub swap_axes: bool,
+}
+
+impl TouchCalibration {
+    /// Calibration for a controller that already reports panel pixels, as most capacitive
+    /// controllers do.
+    pub const fn pixels(width: u16, height: u16) -> Self {
+        Self {
+            raw_left: 0,
+            raw_right: width.saturating_sub(1),
+            raw_top: 0,
+            raw_bottom: height.saturating_sub(1),
+            swap_axes: false,
+        }
+    }
+
+    /// Panel pixel under the reading `raw_x`, `raw_y` on a panel of `width` by `height`
+    /// pixels. Readings beyond the calibrated edges are clamped to the panel.
+    pub const fn to_panel(&self, raw_x: u16, raw_y: u16, width: u16, height: u16) -> (u16, u16) {
+        let (raw_x, raw_y) = if self.swap_axes { (raw_y, raw_x) } else { (raw_x, raw_y) };
+        (scale_reading(raw_x, self.raw_left, self.raw_right, width), scale_reading(raw_y, self.raw_top, self.raw_bottom, height))
+    }
+}
+
+impl<I: LT7683Interface, RESET, W> LT7683<I, RESET, W> {
+    /// Logical position of a touch, in the coordinates drawing currently takes: the reading
+    /// is mapped to the panel with `calibration`, then back through the rotation and origin.
+    /// Assumes the main window shows the canvas from its upper-left corner. Returns `None`
+    /// for touches left of or above the origin.
+    pub fn touch_to_logical(&self, raw_x: u16, raw_y: u16, calibration: &TouchCalibration) -> Option<(u16, u16)> {
+        let (x, y) = calibration.to_panel(raw_x, raw_y, self.config.width, self.config.height);
+        let (x, y) = self.rotation.unrotate_point(x, y, self.config.canvas_width(), self.config.canvas_height());
+        Some((x.checked_sub(self.origin.0)?, y.checked_sub(self.origin.1)?))
+    }
+}
@@ -0,0 +1,63 @@
+//! Bus transfer tracing. Enabled with the `trace` feature.
+//!
+//! Wrap any interface in [`TraceInterface`] to get a logic-analyzer-style log of every
+//! transfer, e.g. printed over RTT or collected into a buffer on the host:
+//! `LT7683::new(TraceInterface::new(spi_interface, |event| log(event)), res, config)`.
+
+use crate::LT7683Interface;
+use crate::registers::Register;
+
+/// One bus transfer, in the order it happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// Command cycle selecting the register at this address.
+    Command(u8),
+    DataWrite(u8),
+    DataRead(u8),
+    StatusRead(u8),
+}
+
+/// Interface wrapper reporting every transfer of `interface` to `sink`.
+pub struct TraceInterface<I, F> {
+    pub interface: I,
+    pub sink: F,
+}
+
+impl<I: LT7683Interface, F: FnMut(TraceEvent)> TraceInterface<I, F> {
+    pub fn new(interface: I, sink: F) -> Self {
+        Self { interface, sink }
+    }
+}
+
+impl<I: LT7683Interface, F: FnMut(TraceEvent)> LT7683Interface for TraceInterface<I, F> {
+    type Error = I::Error;
+
+    fn write_command(&mut self, register: Register) -> Result<(), Self::Error> {
+        (self.sink)(TraceEvent::Command(register as u8));
+        self.interface.write_command(register)
+    }
+
+    fn write_data(&mut self, data: u8) -> Result<(), Self::Error> {
+        (self.sink)(TraceEvent::DataWrite(data));
+        self.interface.write_data(data)
+    }
+
+    fn write_data_burst(&mut self, data: &[u8]) -> Result<(), Self::Error> {
+        for &byte in data {
+            (self.sink)(TraceEvent::DataWrite(byte));
+        }
+        self.interface.write_data_burst(data)
+    }
+
+    fn read_data(&mut self) -> Result<u8, Self::Error> {
+        let data = self.interface.read_data()?;
+        (self.sink)(TraceEvent::DataRead(data));
+        Ok(data)
+    }
+
+    fn read_status(&mut self) -> Result<u8, Self::Error> {
+        let status = self.interface.read_status()?;
+        (self.sink)(TraceEvent::StatusRead(status));
+        Ok(status)
+    }
+}
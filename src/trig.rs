@@ -0,0 +1,42 @@
+//! Integer trigonometry for round widgets. Angles are whole degrees, measured clockwise from
+//! 12 o'clock, which matches both dial and clock faces in screen coordinates (y grows down).
+
+const SHIFT: u32 = 14;
+
+/// sin(0..=90 degrees), scaled by 2^14.
+const SIN_TABLE: [i32; 91] = [
+    0, 286, 572, 857, 1143, 1428, 1713, 1997, 2280, 2563,
+    2845, 3126, 3406, 3686, 3964, 4240, 4516, 4790, 5063, 5334,
+    5604, 5872, 6138, 6402, 6664, 6924, 7182, 7438, 7692, 7943,
+    8192, 8438, 8682, 8923, 9162, 9397, 9630, 9860, 10087, 10311,
+    10531, 10749, 10963, 11174, 11381, 11585, 11786, 11982, 12176, 12365,
+    12551, 12733, 12911, 13085, 13255, 13421, 13583, 13741, 13894, 14044,
+    14189, 14330, 14466, 14598, 14726, 14849, 14968, 15082, 15191, 15296,
+    15396, 15491, 15582, 15668, 15749, 15826, 15897, 15964, 16026, 16083,
+    16135, 16182, 16225, 16262, 16294, 16322, 16344, 16362, 16374, 16382,
+    16384,
+];
+
+/// sin(deg), scaled by 2^14.
+pub(crate) fn sin(deg: i32) -> i32 {
+    let deg = deg.rem_euclid(360) as usize;
+    match deg {
+        0..=90 => SIN_TABLE[deg],
+        91..=180 => SIN_TABLE[180 - deg],
+        181..=270 => -SIN_TABLE[deg - 180],
+        _ => -SIN_TABLE[360 - deg],
+    }
+}
+
+/// cos(deg), scaled by 2^14.
+pub(crate) fn cos(deg: i32) -> i32 {
+    sin(deg + 90)
+}
+
+/// Point `radius` pixels away from the center in the direction of `deg`.
+pub(crate) fn polar(center_x: i32, center_y: i32, radius: i32, deg: i32) -> (i32, i32) {
+    let round = 1 << (SHIFT - 1);
+    let x = center_x + ((radius * sin(deg) + round) >> SHIFT);
+    let y = center_y - ((radius * cos(deg) + round) >> SHIFT);
+    (x, y)
+}
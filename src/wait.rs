@@ -0,0 +1,215 @@
+//! How the driver waits for the drawing and BTE engines to finish.
+//!
+//! The strategy is a type parameter of [`LT7683`](crate::LT7683), so the choice is made at
+//! compile time and costs nothing at run time. [`Polling`] is the default.
+//!
+//! Interfaces without a read path need [`FixedDelay`], together with the `write-only` feature.
+
+use crate::LT7683Interface;
+use crate::raw::{bte_ctrl0, dma_ctrl, interrupt, status};
+use crate::registers::Register;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
+
+/// Engine operation being waited for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitEvent {
+    /// Geometric drawing.
+    Draw,
+    /// Writing one character in text mode.
+    Text,
+    /// Block transfer.
+    Bte,
+    /// Serial flash DMA transfer.
+    Dma,
+    /// Start of the next vertical sync.
+    Vsync,
+}
+
+pub trait WaitStrategy {
+    /// Interrupt sources allowed to drive the INT pin, written to MINTFR during init. The
+    /// default leaves the pin unused.
+    fn interrupt_pin_sources(&self) -> u8 {
+        0
+    }
+
+    /// Return once the operation behind `event` has finished. `interface` may be used to poll
+    /// the chip.
+    fn wait<I: LT7683Interface>(&mut self, interface: &mut I, event: WaitEvent) -> Result<(), I::Error>;
+
+    /// Called between polls of waits the driver runs itself, e.g. for the memory write FIFO.
+    /// The default does nothing.
+    fn hint(&mut self) {}
+}
+
+/// Read the busy flags until they clear. Returns as soon as the engine is done, at the cost of
+/// continuous bus traffic while waiting.
+#[derive(Debug, Clone, Copy)]
+pub struct Polling {
+    /// Called between polls, e.g. to yield to an RTOS.
+    pub hint: fn(),
+}
+
+impl Polling {
+    pub fn new() -> Self {
+        Self::with_hint(|| {})
+    }
+
+    pub fn with_hint(hint: fn()) -> Self {
+        Self { hint }
+    }
+}
+
+impl Default for Polling {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WaitStrategy for Polling {
+    fn hint(&mut self) {
+        (self.hint)();
+    }
+
+    fn wait<I: LT7683Interface>(&mut self, interface: &mut I, event: WaitEvent) -> Result<(), I::Error> {
+        match event {
+            WaitEvent::Draw | WaitEvent::Text => {
+                while interface.read_status()? & status::CORE_BUSY != 0 {
+                    (self.hint)();
+                }
+            }
+            WaitEvent::Bte => loop {
+                interface.write_command(Register::BteCtrl0)?;
+                if interface.read_data()? & bte_ctrl0::ENABLE == 0 {
+                    break;
+                }
+                (self.hint)();
+            },
+            WaitEvent::Dma => loop {
+                interface.write_command(Register::DmaCtrl)?;
+                if interface.read_data()? & dma_ctrl::START == 0 {
+                    break;
+                }
+                (self.hint)();
+            },
+            WaitEvent::Vsync => {
+                clear_interrupt(interface, interrupt::VSYNC)?;
+                while read_interrupt_flags(interface)? & interrupt::VSYNC == 0 {
+                    (self.hint)();
+                }
+                clear_interrupt(interface, interrupt::VSYNC)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sleep for a fixed time without touching the bus. The times must cover the slowest operation
+/// the application performs. Vsync and flash DMA, whose duration depends on the flash, cannot be
+/// timed this way and are polled; write-only builds instead time flash DMA like a block transfer
+/// and wait out a whole frame for vsync.
+pub struct FixedDelay<D> {
+    pub delay: D,
+    /// Time allowed for a drawing engine operation or a character write.
+    pub draw_us: u32,
+    /// Time allowed for a block transfer.
+    pub bte_us: u32,
+}
+
+impl<D: DelayNs> FixedDelay<D> {
+    pub fn new(delay: D, draw_us: u32, bte_us: u32) -> Self {
+        Self { delay, draw_us, bte_us }
+    }
+}
+
+impl<D: DelayNs> WaitStrategy for FixedDelay<D> {
+    fn wait<I: LT7683Interface>(&mut self, interface: &mut I, event: WaitEvent) -> Result<(), I::Error> {
+        match event {
+            WaitEvent::Draw | WaitEvent::Text => self.delay.delay_us(self.draw_us),
+            WaitEvent::Bte => self.delay.delay_us(self.bte_us),
+            WaitEvent::Dma if cfg!(feature = "write-only") => self.delay.delay_us(self.bte_us),
+            WaitEvent::Vsync if cfg!(feature = "write-only") => self.delay.delay_us(WRITE_ONLY_FRAME_US),
+            WaitEvent::Dma | WaitEvent::Vsync => Polling::new().wait(interface, event)?,
+        }
+        Ok(())
+    }
+}
+
+/// Frame time at 50 Hz, long enough for a vertical sync to have passed on common panels.
+const WRITE_ONLY_FRAME_US: u32 = 20_000;
+
+/// Wait on the chip's INT output (active low) instead of reading the busy flags, so the bus is
+/// only used once the operation has finished. Character writes are too short to be worth an
+/// interrupt and are polled.
+pub struct InterruptPin<P> {
+    pub pin: P,
+    /// Called while the pin is high, e.g. `cortex_m::asm::wfi` when INT also raises an MCU
+    /// interrupt.
+    pub hint: fn(),
+}
+
+impl<P: InputPin> InterruptPin<P> {
+    pub fn new(pin: P) -> Self {
+        Self::with_hint(pin, || {})
+    }
+
+    pub fn with_hint(pin: P, hint: fn()) -> Self {
+        Self { pin, hint }
+    }
+
+    /// Wait for the pin to go low, then for `flag` among the pending interrupts. A pin that
+    /// cannot be read degrades to polling the flags.
+    fn wait_for_flag<I: LT7683Interface>(&mut self, interface: &mut I, flag: u8) -> Result<(), I::Error> {
+        loop {
+            while self.pin.is_high().unwrap_or(false) {
+                (self.hint)();
+            }
+            let flags = read_interrupt_flags(interface)?;
+            if flags & flag != 0 {
+                return clear_interrupt(interface, flag);
+            }
+        }
+    }
+}
+
+impl<P: InputPin> WaitStrategy for InterruptPin<P> {
+    fn interrupt_pin_sources(&self) -> u8 {
+        interrupt::TASK_DONE
+    }
+
+    fn hint(&mut self) {
+        (self.hint)();
+    }
+
+    fn wait<I: LT7683Interface>(&mut self, interface: &mut I, event: WaitEvent) -> Result<(), I::Error> {
+        match event {
+            WaitEvent::Draw | WaitEvent::Bte | WaitEvent::Dma => self.wait_for_flag(interface, interrupt::TASK_DONE),
+            WaitEvent::Text => {
+                Polling::with_hint(self.hint).wait(interface, event)?;
+                // Do not let a flag raised by the text engine end the next wait early
+                clear_interrupt(interface, interrupt::TASK_DONE)
+            }
+            WaitEvent::Vsync => {
+                clear_interrupt(interface, interrupt::VSYNC)?;
+                set_interrupt_pin_sources(interface, interrupt::TASK_DONE | interrupt::VSYNC)?;
+                self.wait_for_flag(interface, interrupt::VSYNC)?;
+                set_interrupt_pin_sources(interface, interrupt::TASK_DONE)
+            }
+        }
+    }
+}
+
+fn read_interrupt_flags<I: LT7683Interface>(interface: &mut I) -> Result<u8, I::Error> {
+    interface.write_command(Register::Intf)?;
+    interface.read_data()
+}
+
+fn clear_interrupt<I: LT7683Interface>(interface: &mut I, flags: u8) -> Result<(), I::Error> {
+    interface.write_command(Register::Intf)?;
+    interface.write_data(flags)
+}
+
+fn set_interrupt_pin_sources<I: LT7683Interface>(interface: &mut I, sources: u8) -> Result<(), I::Error> {
+    interface.write_command(Register::Mintfr)?;
+    interface.write_data(!sources)
+}
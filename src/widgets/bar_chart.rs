@@ -0,0 +1,105 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// `N` vertical bars growing up from the bottom of `rect`.
+///
+/// Updates only touch bars whose height changed, and of those only the strip between the old
+/// and the new top, filled in the bar color when growing and the background when shrinking.
+pub struct BarChart<const N: usize> {
+    pub rect: Rect,
+    /// Value at the bottom of the chart.
+    pub min: i32,
+    /// Value at the top of the chart.
+    pub max: i32,
+    pub bar_width: u16,
+    /// Space between bars.
+    pub gap: u16,
+    pub background_color: u32,
+    pub colors: [u32; N],
+    values: [i32; N],
+}
+
+impl<const N: usize> BarChart<N> {
+    /// Chart with bars spread evenly over `rect`, all in `color`, at `min`.
+    pub fn new(rect: Rect, min: i32, max: i32, color: u32) -> Self {
+        let gap = 2;
+        let bar_width = (rect.width.saturating_sub(gap * N.saturating_sub(1) as u16) / N.max(1) as u16).max(1);
+        Self { rect, min, max, bar_width, gap, background_color: 0x000000, colors: [color; N], values: [min; N] }
+    }
+
+    pub fn values(&self) -> &[i32; N] {
+        &self.values
+    }
+
+    /// Draw the complete chart: background and bars.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        display.clear_area(self.rect, self.background_color)?;
+        for (index, &value) in self.values.iter().enumerate() {
+            let height = self.bar_height(value);
+            display.clear_area(self.bar_rect(index, 0, height), self.colors[index])?;
+        }
+        Ok(())
+    }
+
+    /// Set bar `index` to `value`, redrawing the part of the bar that changed. Indices past
+    /// the last bar are ignored.
+    pub fn set<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, index: usize, value: i32) -> Result<(), CheckedError<I::Error>> {
+        let Some(&previous) = self.values.get(index) else {
+            return Ok(());
+        };
+        self.values[index] = value;
+        let (from, to) = (self.bar_height(previous), self.bar_height(value));
+        if from == to {
+            return Ok(());
+        }
+        let color = if to > from { self.colors[index] } else { self.background_color };
+        display.clear_area(self.bar_rect(index, from.min(to), from.abs_diff(to)), color)
+    }
+
+    /// Set the bars to `values`, in order, as [`set`](Self::set) does.
+    pub fn set_values<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, values: &[i32]) -> Result<(), CheckedError<I::Error>> {
+        for (index, &value) in values.iter().enumerate() {
+            self.set(display, index, value)?;
+        }
+        Ok(())
+    }
+
+    /// Show a histogram of `samples`: the range from `low` up to `high` is split into `N`
+    /// equal bins and each bar set to the number of samples in its bin. Samples outside the
+    /// range are not counted.
+    pub fn set_histogram<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &mut self,
+        display: &mut LT7683<I, RESET, W>,
+        samples: &[i32],
+        (low, high): (i32, i32),
+    ) -> Result<(), CheckedError<I::Error>> {
+        let mut counts = [0; N];
+        let range = high as i64 - low as i64;
+        if range > 0 && N > 0 {
+            for &sample in samples.iter().filter(|&&sample| (low..high).contains(&sample)) {
+                let bin = ((sample as i64 - low as i64) * N as i64 / range) as usize;
+                counts[bin] += 1;
+            }
+        }
+        self.set_values(display, &counts)
+    }
+
+    /// Height of the bar for `value` in pixels, clamped to the chart.
+    fn bar_height(&self, value: i32) -> u16 {
+        let range = self.max as i64 - self.min as i64;
+        if range <= 0 {
+            return 0;
+        }
+        let height = self.rect.height as i64;
+        ((value as i64 - self.min as i64) * height / range).clamp(0, height) as u16
+    }
+
+    /// Part of bar `index` from `bottom` to `bottom + height` pixels above the chart's bottom
+    /// edge, cut off at the chart's right edge.
+    fn bar_rect(&self, index: usize, bottom: u16, height: u16) -> Rect {
+        let offset = (index as u32 * (self.bar_width as u32 + self.gap as u32)).min(self.rect.width as u32) as u16;
+        let width = self.bar_width.min(self.rect.width - offset);
+        let y = self.rect.y + self.rect.height - bottom - height;
+        Rect::new(self.rect.x + offset, y, width, height)
+    }
+}
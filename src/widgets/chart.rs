@@ -0,0 +1,106 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Line chart of `S` data series over fixed axis ranges, with gridlines.
+///
+/// Points are appended one at a time and only the segment from a series' previous point to
+/// the new one is drawn, so a chart of slowly arriving sensor readings costs one hardware line
+/// per reading.
+pub struct Chart<const S: usize> {
+    pub rect: Rect,
+    pub x_min: i32,
+    pub x_max: i32,
+    pub y_min: i32,
+    pub y_max: i32,
+    /// Number of gridline intervals along each axis; 0 for no gridlines.
+    pub grid_x: u16,
+    pub grid_y: u16,
+    pub background_color: u32,
+    pub axis_color: u32,
+    pub grid_color: u32,
+    pub colors: [u32; S],
+    /// Last point of each series, in pixels.
+    last: [Option<(u16, u16)>; S],
+}
+
+impl<const S: usize> Chart<S> {
+    /// Chart in `rect` showing `x_min` to `x_max` horizontally and `y_min` to `y_max`
+    /// vertically, with series drawn in `colors`.
+    pub fn new(rect: Rect, (x_min, x_max): (i32, i32), (y_min, y_max): (i32, i32), colors: [u32; S]) -> Self {
+        Self {
+            rect,
+            x_min,
+            x_max,
+            y_min,
+            y_max,
+            grid_x: 10,
+            grid_y: 5,
+            background_color: 0x000000,
+            axis_color: 0xFFFFFF,
+            grid_color: 0x303030,
+            colors,
+            last: [None; S],
+        }
+    }
+
+    /// Draw the empty chart: background, gridlines and axes. The series start over, with the
+    /// next point of each drawn on its own.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        self.last = [None; S];
+        let Rect { x, y, width, height } = self.rect;
+        if self.rect.is_empty() {
+            return Ok(());
+        }
+        display.clear_area(self.rect, self.background_color)?;
+        for line in 1..self.grid_x {
+            display.draw_vline(x + (width as u32 * line as u32 / self.grid_x as u32) as u16, y, height, self.grid_color)?;
+        }
+        for line in 1..self.grid_y {
+            display.draw_hline(x, y + (height as u32 * line as u32 / self.grid_y as u32) as u16, width, self.grid_color)?;
+        }
+        // Axes along the left and bottom edges
+        display.draw_vline(x, y, height, self.axis_color)?;
+        Ok(display.draw_hline(x, y + height - 1, width, self.axis_color)?)
+    }
+
+    /// Add the point `x`, `y` to `series`, drawing the segment from its previous point. Values
+    /// outside the axis ranges are clamped to the chart edges; series past the `S`th are
+    /// ignored.
+    pub fn append<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, series: usize, x: i32, y: i32) -> Result<(), CheckedError<I::Error>> {
+        if series >= S || self.rect.is_empty() {
+            return Ok(());
+        }
+        let point = self.to_screen(x, y);
+        let color = self.colors[series];
+        match self.last[series].replace(point) {
+            Some(previous) => Ok(display.draw_line(previous.0, previous.1, point.0, point.1, color)?),
+            None => display.draw_pixel(point.0, point.1, color),
+        }
+    }
+
+    /// Add `points` to `series` in order, as [`append`](Self::append) does.
+    pub fn extend<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, series: usize, points: &[(i32, i32)]) -> Result<(), CheckedError<I::Error>> {
+        for &(x, y) in points {
+            self.append(display, series, x, y)?;
+        }
+        Ok(())
+    }
+
+    /// Pixel the value `x`, `y` is drawn at.
+    pub fn to_screen(&self, x: i32, y: i32) -> (u16, u16) {
+        let Rect { x: left, y: top, width, height } = self.rect;
+        let column = scale(x, self.x_min, self.x_max, width.saturating_sub(1));
+        let row = scale(y, self.y_min, self.y_max, height.saturating_sub(1));
+        (left + column, top + height.saturating_sub(1) - row)
+    }
+}
+
+/// `value` from `min` to `max` mapped to 0 to `pixels`, clamped.
+fn scale(value: i32, min: i32, max: i32, pixels: u16) -> u16 {
+    let range = max as i64 - min as i64;
+    if range == 0 {
+        return 0;
+    }
+    let offset = (value as i64 - min as i64) * pixels as i64 / range;
+    offset.clamp(0, pixels as i64) as u16
+}
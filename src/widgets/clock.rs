@@ -0,0 +1,119 @@
+use super::coord;
+use crate::trig;
+use crate::{CheckedError, LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Analog clock face with hour, minute and second hands.
+///
+/// The dial is drawn once. Setting the time erases only the hands that moved by drawing them
+/// again in the face color, then draws all hands on top, as erasing one may have cut into the
+/// others. Hands stay inside the ring of hour marks, so erasing them never touches the dial.
+pub struct Clock {
+    pub center_x: u16,
+    pub center_y: u16,
+    pub radius: u16,
+    pub tick_length: u16,
+    pub face_color: u32,
+    pub dial_color: u32,
+    pub hand_color: u32,
+    pub second_color: u32,
+    /// Whether the second hand is shown.
+    pub seconds: bool,
+    /// Angle of the hour, minute and second hand, once a time is set.
+    angles: Option<[i32; 3]>,
+}
+
+/// Hour, minute and second hand.
+#[derive(Clone, Copy)]
+enum Hand {
+    Hour,
+    Minute,
+    Second,
+}
+
+const HANDS: [Hand; 3] = [Hand::Hour, Hand::Minute, Hand::Second];
+
+impl Clock {
+    pub fn new(center_x: u16, center_y: u16, radius: u16) -> Self {
+        Self {
+            center_x,
+            center_y,
+            radius,
+            tick_length: radius / 8,
+            face_color: 0x000000,
+            dial_color: 0xFFFFFF,
+            hand_color: 0xFFFFFF,
+            second_color: 0xFF0000,
+            seconds: true,
+            angles: None,
+        }
+    }
+
+    /// Draw the complete clock: face, rim, hour marks and, once a time is set, the hands.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        display.draw_circle(self.center_x, self.center_y, self.radius, self.face_color, true)?;
+        display.draw_circle(self.center_x, self.center_y, self.radius, self.dial_color, false)?;
+        let (cx, cy) = (self.center_x as i32, self.center_y as i32);
+        let outer = self.radius as i32 - 2;
+        for hour in 0..12 {
+            // Quarter hours are marked twice as long
+            let length = if hour % 3 == 0 { self.tick_length as i32 * 2 } else { self.tick_length as i32 };
+            let (x1, y1) = trig::polar(cx, cy, outer - length, hour * 30);
+            let (x2, y2) = trig::polar(cx, cy, outer, hour * 30);
+            display.draw_line(coord(x1), coord(y1), coord(x2), coord(y2), self.dial_color)?;
+        }
+        self.draw_hands(display)
+    }
+
+    /// Show `hours`:`minutes`:`seconds`, in 24 or 12 hour form. Only hands that moved are
+    /// erased.
+    pub fn set_time<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, hours: u8, minutes: u8, seconds: u8) -> Result<(), CheckedError<I::Error>> {
+        let (hours, minutes, seconds) = (hours as i32 % 12, minutes as i32 % 60, seconds as i32 % 60);
+        let angles = [hours * 30 + minutes / 2, minutes * 6 + seconds / 10, seconds * 6];
+        if let Some(previous) = self.angles {
+            if previous == angles {
+                return Ok(());
+            }
+            for hand in HANDS {
+                let index = hand as usize;
+                if previous[index] != angles[index] {
+                    self.draw_hand(display, hand, previous[index], self.face_color)?;
+                }
+            }
+        }
+        self.angles = Some(angles);
+        self.draw_hands(display)
+    }
+
+    fn draw_hands<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        let Some(angles) = self.angles else {
+            return Ok(());
+        };
+        for hand in HANDS {
+            let color = if let Hand::Second = hand { self.second_color } else { self.hand_color };
+            self.draw_hand(display, hand, angles[hand as usize], color)?;
+        }
+        // Hub covers where the hands meet
+        let hub = (self.radius / 25).max(2);
+        Ok(display.draw_circle(self.center_x, self.center_y, hub, self.second_color, true)?)
+    }
+
+    fn draw_hand<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>, hand: Hand, angle: i32, color: u32) -> Result<(), CheckedError<I::Error>> {
+        let (cx, cy) = (self.center_x as i32, self.center_y as i32);
+        // Longest hand ends short of the longest hour marks
+        let reach = (self.radius as i32 - 2 * self.tick_length as i32 - 4).max(1);
+        let (length, half_base) = match hand {
+            Hand::Hour => (reach * 3 / 5, (self.radius as i32 / 25).max(2)),
+            Hand::Minute => (reach * 9 / 10, (self.radius as i32 / 40).max(1)),
+            Hand::Second if self.seconds => (reach, 0),
+            Hand::Second => return Ok(()),
+        };
+        let (tx, ty) = trig::polar(cx, cy, length, angle);
+        if half_base == 0 {
+            return Ok(display.draw_line(self.center_x, self.center_y, coord(tx), coord(ty), color)?);
+        }
+        let (lx, ly) = trig::polar(cx, cy, half_base, angle - 90);
+        let (rx, ry) = trig::polar(cx, cy, half_base, angle + 90);
+        Ok(display.draw_triangle(coord(tx), coord(ty), coord(lx), coord(ly), coord(rx), coord(ry), color, true)?)
+    }
+}
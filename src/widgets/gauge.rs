@@ -0,0 +1,101 @@
+use super::{coord, draw_arc};
+use crate::trig;
+use crate::{CheckedError, LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Circular dial with tick marks and a needle.
+///
+/// Angles are in degrees clockwise from 12 o'clock. The default dial sweeps 270 degrees with the
+/// gap at the bottom, like a car speedometer.
+pub struct Gauge {
+    pub center_x: u16,
+    pub center_y: u16,
+    pub radius: u16,
+    pub min: i32,
+    pub max: i32,
+    /// Angle of the needle at `min`.
+    pub start_angle: i32,
+    /// Clockwise sweep from `min` to `max`.
+    pub sweep: i32,
+    /// Number of intervals between major ticks.
+    pub ticks: u16,
+    pub tick_length: u16,
+    pub face_color: u32,
+    pub dial_color: u32,
+    pub needle_color: u32,
+    value: i32,
+}
+
+impl Gauge {
+    pub fn new(center_x: u16, center_y: u16, radius: u16, min: i32, max: i32) -> Self {
+        Self {
+            center_x,
+            center_y,
+            radius,
+            min,
+            max,
+            start_angle: -135,
+            sweep: 270,
+            ticks: 10,
+            tick_length: radius / 8,
+            face_color: 0x000000,
+            dial_color: 0xFFFFFF,
+            needle_color: 0xFF0000,
+            value: min,
+        }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Draw the complete gauge: face, dial arc, ticks and needle.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        display.draw_circle(self.center_x, self.center_y, self.radius, self.face_color, true)?;
+        draw_arc(display, self.center_x, self.center_y, self.radius, self.start_angle, self.sweep, self.dial_color)?;
+        let (cx, cy) = (self.center_x as i32, self.center_y as i32);
+        let outer = self.radius as i32;
+        let inner = outer - self.tick_length as i32;
+        for i in 0..=self.ticks as i32 {
+            let angle = self.start_angle + self.sweep * i / (self.ticks.max(1) as i32);
+            let (x1, y1) = trig::polar(cx, cy, inner, angle);
+            let (x2, y2) = trig::polar(cx, cy, outer, angle);
+            display.draw_line(coord(x1), coord(y1), coord(x2), coord(y2), self.dial_color)?;
+        }
+        self.draw_needle(display, self.value, self.needle_color)
+    }
+
+    /// Move the needle to `value`. Only the old needle is erased and the new one drawn; the rest
+    /// of the gauge is left untouched.
+    pub fn set_value<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, value: i32) -> Result<(), CheckedError<I::Error>> {
+        let value = value.clamp(self.min.min(self.max), self.max.max(self.min));
+        if self.needle_angle(value) != self.needle_angle(self.value) {
+            self.draw_needle(display, self.value, self.face_color)?;
+            self.draw_needle(display, value, self.needle_color)?;
+        }
+        self.value = value;
+        Ok(())
+    }
+
+    fn needle_angle(&self, value: i32) -> i32 {
+        let range = self.max - self.min;
+        if range == 0 {
+            return self.start_angle;
+        }
+        self.start_angle + ((value - self.min) as i64 * self.sweep as i64 / range as i64) as i32
+    }
+
+    fn draw_needle<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>, value: i32, color: u32) -> Result<(), CheckedError<I::Error>> {
+        let (cx, cy) = (self.center_x as i32, self.center_y as i32);
+        let angle = self.needle_angle(value);
+        // Needle stays inside the tick ring so erasing it never touches the ticks
+        let length = (self.radius as i32 - self.tick_length as i32 - 2).max(1);
+        let half_base = (self.radius as i32 / 20).max(2);
+        let (tx, ty) = trig::polar(cx, cy, length, angle);
+        let (lx, ly) = trig::polar(cx, cy, half_base, angle - 90);
+        let (rx, ry) = trig::polar(cx, cy, half_base, angle + 90);
+        display.draw_triangle(coord(tx), coord(ty), coord(lx), coord(ly), coord(rx), coord(ry), color, true)?;
+        // Hub covers the needle base
+        Ok(display.draw_circle(self.center_x, self.center_y, half_base as u16 + 1, self.needle_color, true)?)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, TextAlign, TextStyle, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Space left of the text in each row.
+const TEXT_INSET: u16 = 4;
+
+/// Scrollable menu of text rows with the selected row highlighted.
+///
+/// The items are passed to every drawing call, so they can live wherever the application keeps
+/// them. Moving the selection redraws only the rows it leaves and enters; when it leaves the
+/// visible rows, the list scrolls by moving the rows that stay visible with BTE copies and
+/// drawing only the rows that come into view.
+pub struct List {
+    pub rect: Rect,
+    /// Text of the rows; its background is ignored.
+    pub style: TextStyle,
+    pub row_height: u16,
+    pub background_color: u32,
+    pub highlight_color: u32,
+    /// Text color of the selected row.
+    pub highlight_text_color: u32,
+    selected: usize,
+    /// First visible item.
+    top: usize,
+}
+
+impl List {
+    pub fn new(rect: Rect, style: TextStyle) -> Self {
+        Self {
+            rect,
+            style,
+            row_height: style.cell_height() + 4,
+            background_color: 0x000000,
+            highlight_color: 0x0066FF,
+            highlight_text_color: 0xFFFFFF,
+            selected: 0,
+            top: 0,
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Number of rows that fit in `rect`.
+    pub fn visible_rows(&self) -> usize {
+        self.rect.height.checked_div(self.row_height).unwrap_or(0) as usize
+    }
+
+    /// Item shown at `x`, `y`, e.g. of a touch.
+    pub fn item_at(&self, x: u16, y: u16, items: &[&str]) -> Option<usize> {
+        if !self.rect.contains(x, y) {
+            return None;
+        }
+        let row = ((y - self.rect.y) / self.row_height.max(1)) as usize;
+        let index = self.top + row;
+        (row < self.visible_rows() && index < items.len()).then_some(index)
+    }
+
+    /// Draw the complete list: background and visible rows.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>, items: &[&str]) -> Result<(), CheckedError<I::Error>> {
+        display.clear_area(self.rect, self.background_color)?;
+        for row in 0..self.visible_rows() {
+            self.draw_row(display, items, row)?;
+        }
+        Ok(())
+    }
+
+    /// Select item `index`, clamped to the last item, scrolling it into view if needed.
+    pub fn select<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, items: &[&str], index: usize) -> Result<(), CheckedError<I::Error>> {
+        let index = index.min(items.len().saturating_sub(1));
+        let rows = self.visible_rows();
+        if index == self.selected || rows == 0 {
+            self.selected = index;
+            return Ok(());
+        }
+        let previous_row = self.selected.checked_sub(self.top).filter(|&row| row < rows);
+        self.selected = index;
+        let top = if index < self.top {
+            index
+        } else if index >= self.top + rows {
+            index + 1 - rows
+        } else {
+            self.top
+        };
+        let shift = top.abs_diff(self.top);
+        if shift >= rows {
+            self.top = top;
+            return self.draw(display, items);
+        }
+        // Unhighlight the row that was selected before it moves
+        if let Some(row) = previous_row {
+            self.draw_row(display, items, row)?;
+        }
+        if shift > 0 {
+            // Rows are moved one at a time, in an order where no row is overwritten before it
+            // has moved
+            let (x, width) = (self.rect.x, self.rect.width);
+            let row_y = |row: usize| self.rect.y + row as u16 * self.row_height;
+            let scrolled_down = top > self.top;
+            if scrolled_down {
+                for row in 0..rows - shift {
+                    display.bte_memory_copy(x, row_y(row + shift), x, row_y(row), width, self.row_height)?;
+                }
+            } else {
+                for row in (0..rows - shift).rev() {
+                    display.bte_memory_copy(x, row_y(row), x, row_y(row + shift), width, self.row_height)?;
+                }
+            }
+            self.top = top;
+            // The selected row is among those that come into view
+            let exposed = if scrolled_down { rows - shift..rows } else { 0..shift };
+            for row in exposed {
+                self.draw_row(display, items, row)?;
+            }
+            return Ok(());
+        }
+        self.draw_row(display, items, index - top)
+    }
+
+    /// Select the next item, if there is one.
+    pub fn select_next<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, items: &[&str]) -> Result<(), CheckedError<I::Error>> {
+        self.select(display, items, self.selected + 1)
+    }
+
+    /// Select the previous item, if there is one.
+    pub fn select_previous<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, items: &[&str]) -> Result<(), CheckedError<I::Error>> {
+        self.select(display, items, self.selected.saturating_sub(1))
+    }
+
+    /// Draw visible row `row`, highlighted if it shows the selected item. Rows past the visible
+    /// ones are skipped; rows past the last item are cleared.
+    fn draw_row<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>, items: &[&str], row: usize) -> Result<(), CheckedError<I::Error>> {
+        if row >= self.visible_rows() {
+            return Ok(());
+        }
+        let index = self.top + row;
+        let selected = index == self.selected;
+        let rect = Rect::new(self.rect.x, self.rect.y + row as u16 * self.row_height, self.rect.width, self.row_height);
+        display.clear_area(rect, if selected { self.highlight_color } else { self.background_color })?;
+        let Some(text) = items.get(index) else {
+            return Ok(());
+        };
+        let fg_color = if selected { self.highlight_text_color } else { self.style.fg_color };
+        let style = TextStyle { fg_color, bg_color: None, ..self.style };
+        let text_y = rect.y + self.row_height.saturating_sub(style.cell_height()) / 2;
+        let text_rect = Rect::new(rect.x + TEXT_INSET, text_y, rect.width.saturating_sub(TEXT_INSET), style.cell_height());
+        Ok(display.write_text_aligned(text, text_rect, TextAlign::Left, style)?)
+    }
+}
@@ -0,0 +1,114 @@
+//! Ready-made widgets drawn with the hardware drawing engine. Enabled with the `widgets` feature.
+//!
+//! Widgets only hold their own geometry and state; every drawing call borrows the display, so a
+//! widget can live next to the driver in the application's state.
+
+mod bar_chart;
+mod chart;
+mod clock;
+mod gauge;
+mod list;
+mod progress_bar;
+mod progress_ring;
+mod slider;
+mod strip_chart;
+mod text_field;
+mod toggle;
+mod ui;
+
+pub use bar_chart::BarChart;
+pub use chart::Chart;
+pub use clock::Clock;
+pub use gauge::Gauge;
+pub use list::List;
+pub use progress_bar::ProgressBar;
+pub use progress_ring::ProgressRing;
+pub use slider::Slider;
+pub use strip_chart::StripChart;
+pub use text_field::TextField;
+pub use toggle::Toggle;
+pub use ui::{Theme, Ui, UiFrame};
+
+use crate::trig;
+use crate::{CheckedError, LT7683, LT7683Interface, Quadrant, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Angle step used when an arc has to be approximated by line segments.
+const ARC_STEP: i32 = 5;
+
+/// Clamp a signed coordinate into the range the drawing registers accept.
+fn coord(value: i32) -> u16 {
+    value.clamp(0, u16::MAX as i32) as u16
+}
+
+/// Draw a circular arc of `sweep` degrees starting at `start` (clockwise from 12 o'clock).
+/// Whole quadrants are drawn with the hardware ellipse curve, the rest with short line segments.
+#[allow(clippy::too_many_arguments)]
+fn draw_arc<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+    display: &mut LT7683<I, RESET, W>,
+    center_x: u16, center_y: u16, radius: u16,
+    start: i32, sweep: i32, color: u32
+) -> Result<(), CheckedError<I::Error>> {
+    let (cx, cy, r) = (center_x as i32, center_y as i32, radius as i32);
+    let end = start + sweep;
+    let mut angle = start;
+    while angle < end {
+        let quadrant_start = angle.div_euclid(90) * 90;
+        let quadrant_end = quadrant_start + 90;
+        if angle == quadrant_start && quadrant_end <= end {
+            display.draw_ellipse_curve(center_x, center_y, radius, radius, quadrant(quadrant_start), color, false)?;
+            angle = quadrant_end;
+            continue;
+        }
+        let segment_end = quadrant_end.min(end);
+        let (mut x, mut y) = trig::polar(cx, cy, r, angle);
+        while angle < segment_end {
+            angle = (angle + ARC_STEP).min(segment_end);
+            let (nx, ny) = trig::polar(cx, cy, r, angle);
+            display.draw_line(coord(x), coord(y), coord(nx), coord(ny), color)?;
+            (x, y) = (nx, ny);
+        }
+    }
+    Ok(())
+}
+
+/// Fill a circular sector of `sweep` degrees starting at `start`, like [`draw_arc`]. Whole
+/// quadrants are filled ellipse curves, the rest a fan of triangles.
+#[allow(clippy::too_many_arguments)]
+fn fill_sector<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+    display: &mut LT7683<I, RESET, W>,
+    center_x: u16, center_y: u16, radius: u16,
+    start: i32, sweep: i32, color: u32
+) -> Result<(), CheckedError<I::Error>> {
+    let (cx, cy, r) = (center_x as i32, center_y as i32, radius as i32);
+    let end = start + sweep;
+    let mut angle = start;
+    while angle < end {
+        let quadrant_start = angle.div_euclid(90) * 90;
+        let quadrant_end = quadrant_start + 90;
+        if angle == quadrant_start && quadrant_end <= end {
+            display.draw_ellipse_curve(center_x, center_y, radius, radius, quadrant(quadrant_start), color, true)?;
+            angle = quadrant_end;
+            continue;
+        }
+        let segment_end = quadrant_end.min(end);
+        let (mut x, mut y) = trig::polar(cx, cy, r, angle);
+        while angle < segment_end {
+            angle = (angle + ARC_STEP).min(segment_end);
+            let (nx, ny) = trig::polar(cx, cy, r, angle);
+            display.draw_triangle(center_x, center_y, coord(x), coord(y), coord(nx), coord(ny), color, true)?;
+            (x, y) = (nx, ny);
+        }
+    }
+    Ok(())
+}
+
+/// Quadrant of the circle that starts at `angle`, a multiple of 90 degrees.
+fn quadrant(angle: i32) -> Quadrant {
+    match angle.rem_euclid(360) / 90 {
+        0 => Quadrant::UpperRight,
+        1 => Quadrant::BottomRight,
+        2 => Quadrant::BottomLeft,
+        _ => Quadrant::UpperLeft,
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Horizontal bar that fills from the left as a percentage grows.
+pub struct ProgressBar {
+    pub rect: Rect,
+    /// Width of the border, drawn inside `rect`.
+    pub border: u16,
+    pub border_color: u32,
+    pub track_color: u32,
+    pub fill_color: u32,
+    percent: u8,
+}
+
+impl ProgressBar {
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            border: 1,
+            border_color: 0xFFFFFF,
+            track_color: 0x404040,
+            fill_color: 0x00FF00,
+            percent: 0,
+        }
+    }
+
+    pub fn percent(&self) -> u8 {
+        self.percent
+    }
+
+    /// Draw the complete bar: border, filled part and track.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        display.draw_rect_border(self.rect, self.border, self.border_color)?;
+        let inner = self.inner();
+        let filled = self.fill_width(self.percent);
+        display.clear_area(Rect::new(inner.x, inner.y, filled, inner.height), self.fill_color)?;
+        display.clear_area(Rect::new(inner.x + filled, inner.y, inner.width - filled, inner.height), self.track_color)
+    }
+
+    /// Change the value to `percent`, clamped to 100. Only the strip between the old and the
+    /// new end of the filled part is redrawn.
+    pub fn set_percent<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, percent: u8) -> Result<(), CheckedError<I::Error>> {
+        let percent = percent.min(100);
+        let (from, to) = (self.fill_width(self.percent), self.fill_width(percent));
+        if from != to {
+            let inner = self.inner();
+            let color = if to > from { self.fill_color } else { self.track_color };
+            display.clear_area(Rect::new(inner.x + from.min(to), inner.y, from.abs_diff(to), inner.height), color)?;
+        }
+        self.percent = percent;
+        Ok(())
+    }
+
+    /// Area inside the border.
+    fn inner(&self) -> Rect {
+        let border = self.border.min(self.rect.width / 2).min(self.rect.height / 2);
+        Rect::new(self.rect.x + border, self.rect.y + border, self.rect.width - 2 * border, self.rect.height - 2 * border)
+    }
+
+    fn fill_width(&self, percent: u8) -> u16 {
+        (self.inner().width as u32 * percent as u32 / 100) as u16
+    }
+}
@@ -0,0 +1,71 @@
+use super::fill_sector;
+use crate::{CheckedError, LT7683, LT7683Interface, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Ring that fills clockwise from 12 o'clock as a percentage grows.
+///
+/// The filled part is drawn as hardware pie slices with the hole punched out afterwards, so
+/// anything drawn inside the hole is erased by every update; draw labels there after updating.
+pub struct ProgressRing {
+    pub center_x: u16,
+    pub center_y: u16,
+    pub radius: u16,
+    /// Width of the ring, from `radius` inwards.
+    pub thickness: u16,
+    pub track_color: u32,
+    pub fill_color: u32,
+    /// Color of the hole in the middle.
+    pub background_color: u32,
+    percent: u8,
+}
+
+impl ProgressRing {
+    pub fn new(center_x: u16, center_y: u16, radius: u16, thickness: u16) -> Self {
+        Self {
+            center_x,
+            center_y,
+            radius,
+            thickness,
+            track_color: 0x404040,
+            fill_color: 0x00FF00,
+            background_color: 0x000000,
+            percent: 0,
+        }
+    }
+
+    pub fn percent(&self) -> u8 {
+        self.percent
+    }
+
+    /// Draw the complete ring: track, filled part and hole.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        display.draw_circle(self.center_x, self.center_y, self.radius, self.track_color, true)?;
+        fill_sector(display, self.center_x, self.center_y, self.radius, 0, self.angle(self.percent), self.fill_color)?;
+        self.draw_hole(display)
+    }
+
+    /// Change the value to `percent`, clamped to 100. Only the arc between the old and the new
+    /// value is redrawn, in the fill color when growing and the track color when shrinking.
+    pub fn set_percent<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, percent: u8) -> Result<(), CheckedError<I::Error>> {
+        let percent = percent.min(100);
+        let (from, to) = (self.angle(self.percent), self.angle(percent));
+        if from != to {
+            let (start, color) = if to > from { (from, self.fill_color) } else { (to, self.track_color) };
+            fill_sector(display, self.center_x, self.center_y, self.radius, start, from.abs_diff(to) as i32, color)?;
+            self.draw_hole(display)?;
+        }
+        self.percent = percent;
+        Ok(())
+    }
+
+    fn angle(&self, percent: u8) -> i32 {
+        percent as i32 * 360 / 100
+    }
+
+    fn draw_hole<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        if self.thickness >= self.radius {
+            return Ok(());
+        }
+        Ok(display.draw_circle(self.center_x, self.center_y, self.radius - self.thickness, self.background_color, true)?)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Horizontal slider: a thin track, filled left of a rectangular knob as tall as `rect`.
+pub struct Slider {
+    pub rect: Rect,
+    pub min: i32,
+    pub max: i32,
+    pub knob_width: u16,
+    /// Height of the track, centered vertically in `rect`.
+    pub track_height: u16,
+    pub background_color: u32,
+    pub track_color: u32,
+    pub fill_color: u32,
+    pub knob_color: u32,
+    value: i32,
+}
+
+impl Slider {
+    pub fn new(rect: Rect, min: i32, max: i32) -> Self {
+        Self {
+            rect,
+            min,
+            max,
+            knob_width: (rect.height / 2).max(1),
+            track_height: (rect.height / 4).max(1),
+            background_color: 0x000000,
+            track_color: 0x404040,
+            fill_color: 0x0080FF,
+            knob_color: 0xFFFFFF,
+            value: min,
+        }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    /// Value under the horizontal position `x`, e.g. of a touch inside `rect`, with the knob
+    /// centered on it.
+    pub fn value_at(&self, x: u16) -> i32 {
+        let travel = self.travel() as i64;
+        if travel == 0 {
+            return self.min;
+        }
+        let offset = (x as i64 - self.rect.x as i64 - self.knob_width as i64 / 2).clamp(0, travel);
+        self.min + (offset * (self.max - self.min) as i64 / travel) as i32
+    }
+
+    /// Draw the complete slider: background, track and knob.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        self.draw_span(display, self.rect.x, self.rect.x + self.rect.width)?;
+        self.draw_knob(display)
+    }
+
+    /// Move the knob to `value`, clamped to the range. Only the columns between the old and
+    /// the new knob position, knobs included, are redrawn.
+    pub fn set_value<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, value: i32) -> Result<(), CheckedError<I::Error>> {
+        let value = value.clamp(self.min.min(self.max), self.max.max(self.min));
+        let (from, to) = (self.knob_x(self.value), self.knob_x(value));
+        self.value = value;
+        if from != to {
+            self.draw_span(display, from.min(to), from.max(to) + self.knob_width)?;
+            self.draw_knob(display)?;
+        }
+        Ok(())
+    }
+
+    /// Distance the knob moves over from `min` to `max`.
+    fn travel(&self) -> u16 {
+        self.rect.width.saturating_sub(self.knob_width)
+    }
+
+    fn knob_x(&self, value: i32) -> u16 {
+        let range = self.max as i64 - self.min as i64;
+        if range == 0 {
+            return self.rect.x;
+        }
+        self.rect.x + ((value as i64 - self.min as i64) * self.travel() as i64 / range) as u16
+    }
+
+    /// Draw background and track in the columns from `left` up to `right`, filled left of the
+    /// knob's center.
+    fn draw_span<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>, left: u16, right: u16) -> Result<(), CheckedError<I::Error>> {
+        display.clear_area(Rect::new(left, self.rect.y, right - left, self.rect.height), self.background_color)?;
+        let track_height = self.track_height.min(self.rect.height);
+        let track_y = self.rect.y + (self.rect.height - track_height) / 2;
+        let split = (self.knob_x(self.value) + self.knob_width / 2).clamp(left, right);
+        display.clear_area(Rect::new(left, track_y, split - left, track_height), self.fill_color)?;
+        display.clear_area(Rect::new(split, track_y, right - split, track_height), self.track_color)
+    }
+
+    fn draw_knob<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        let width = self.knob_width.min(self.rect.width);
+        display.clear_area(Rect::new(self.knob_x(self.value), self.rect.y, width, self.rect.height), self.knob_color)
+    }
+}
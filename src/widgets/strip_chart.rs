@@ -0,0 +1,101 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, Rotation, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Oscilloscope-style chart of `S` series scrolling right to left, one sample per `step`
+/// pixels.
+///
+/// Each new sample shifts the plot left with a BTE copy and draws only the column that comes
+/// into view: background, gridlines and one short line per series.
+pub struct StripChart<const S: usize> {
+    pub rect: Rect,
+    pub y_min: i32,
+    pub y_max: i32,
+    /// Pixels the plot moves per sample.
+    pub step: u16,
+    /// Number of gridline intervals across the height; 0 for no gridlines.
+    pub grid_y: u16,
+    pub background_color: u32,
+    pub grid_color: u32,
+    pub colors: [u32; S],
+    /// Row of each series' last sample.
+    last: [Option<u16>; S],
+}
+
+impl<const S: usize> StripChart<S> {
+    pub fn new(rect: Rect, (y_min, y_max): (i32, i32), colors: [u32; S]) -> Self {
+        Self {
+            rect,
+            y_min,
+            y_max,
+            step: 2,
+            grid_y: 4,
+            background_color: 0x000000,
+            grid_color: 0x303030,
+            colors,
+            last: [None; S],
+        }
+    }
+
+    /// Draw the empty chart: background and gridlines. The series start over.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        self.last = [None; S];
+        self.draw_columns(display, self.rect.x, self.rect.width)
+    }
+
+    /// Scroll the plot left by `step` pixels and draw `values`, one per series, at the right
+    /// edge. Values outside the range are clamped to the chart edges.
+    pub fn push<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, values: [i32; S]) -> Result<(), CheckedError<I::Error>> {
+        let Rect { x, y, width, height } = self.rect;
+        let step = self.step.clamp(1, width.max(1));
+        if self.rect.is_empty() {
+            return Ok(());
+        }
+        let kept = width - step;
+        if kept > 0 {
+            // The BTE copies left to right, top to bottom on the canvas, so one overlapping copy
+            // moves the plot only where that order reads each pixel before overwriting it
+            if matches!(display.rotation(), Rotation::Deg0 | Rotation::Deg90) {
+                display.bte_memory_copy(x + step, y, x, y, kept, height)?;
+            } else {
+                let mut left = 0;
+                while left < kept {
+                    let strip = step.min(kept - left);
+                    display.bte_memory_copy(x + left + step, y, x + left, y, strip, height)?;
+                    left += strip;
+                }
+            }
+        }
+        self.draw_columns(display, x + kept, step)?;
+        let right = x + width - 1;
+        for (series, &value) in values.iter().enumerate() {
+            let row = self.to_row(value);
+            let color = self.colors[series];
+            match self.last[series].replace(row) {
+                Some(previous) if kept > 0 => display.draw_line(right - step, previous, right, row, color)?,
+                _ => display.draw_pixel(right, row, color)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Row `value` is drawn at.
+    pub fn to_row(&self, value: i32) -> u16 {
+        let pixels = self.rect.height.saturating_sub(1);
+        let range = self.y_max as i64 - self.y_min as i64;
+        let offset = match range {
+            0 => 0,
+            _ => ((value as i64 - self.y_min as i64) * pixels as i64 / range).clamp(0, pixels as i64) as u16,
+        };
+        self.rect.y + pixels - offset
+    }
+
+    /// Clear the columns from `left`, `width` pixels wide, and draw the gridlines across them.
+    fn draw_columns<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>, left: u16, width: u16) -> Result<(), CheckedError<I::Error>> {
+        let Rect { y, height, .. } = self.rect;
+        display.clear_area(Rect::new(left, y, width, height), self.background_color)?;
+        for line in 1..self.grid_y {
+            display.draw_hline(left, y + (height as u32 * line as u32 / self.grid_y as u32) as u16, width, self.grid_color)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, TextAlign, TextStyle, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Space between the border and the text.
+const TEXT_INSET: u16 = 3;
+/// Width of the insertion bar.
+const CURSOR_WIDTH: u16 = 2;
+
+/// Single-line text input holding up to `N` bytes of UTF-8, with the hardware text cursor
+/// blinking at the insertion point while focused.
+///
+/// Text longer than the box scrolls to keep the insertion point visible. Edits redraw the
+/// text from the edited position to the end of the box; moving the insertion point within the
+/// visible text only moves the cursor. Characters are assumed to be one cell wide, as they are
+/// in the internal character sets.
+pub struct TextField<const N: usize> {
+    pub rect: Rect,
+    /// Text style; its background is ignored.
+    pub style: TextStyle,
+    pub background_color: u32,
+    pub border_color: u32,
+    /// Frames per cursor blink, minus one.
+    pub blink_period: u8,
+    bytes: [u8; N],
+    len: usize,
+    /// Insertion point, in characters.
+    cursor: usize,
+    /// First visible character.
+    scroll: usize,
+    focused: bool,
+}
+
+impl<const N: usize> TextField<N> {
+    pub fn new(rect: Rect, style: TextStyle) -> Self {
+        Self {
+            rect,
+            style,
+            background_color: 0x000000,
+            border_color: 0x808080,
+            blink_period: 30,
+            bytes: [0; N],
+            len: 0,
+            cursor: 0,
+            scroll: 0,
+            focused: false,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        // Only whole characters are ever stored
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+
+    /// Insertion point, in characters from the start.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Draw the complete field: border, background, visible text and, if focused, the cursor.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        display.draw_rect_border(self.rect, 1, self.border_color)?;
+        self.redraw_from(display, 0)
+    }
+
+    /// Replace the text, keeping as many whole characters of `text` as fit in `N` bytes, and
+    /// put the insertion point after it.
+    pub fn set_text<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, text: &str) -> Result<(), CheckedError<I::Error>> {
+        let end = text.char_indices().map(|(index, c)| index + c.len_utf8()).take_while(|&end| end <= N).last().unwrap_or(0);
+        self.bytes[..end].copy_from_slice(&text.as_bytes()[..end]);
+        self.len = end;
+        self.cursor = self.text().chars().count();
+        self.scroll_to_cursor();
+        self.redraw_from(display, 0)
+    }
+
+    /// Show or hide the blinking cursor. Only one field at a time can show it.
+    pub fn set_focus<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, focused: bool) -> Result<(), CheckedError<I::Error>> {
+        self.focused = focused;
+        if focused {
+            display.set_text_cursor_blink_period(self.blink_period)?;
+            display.enable_text_cursor(true)?;
+            self.place_cursor(display)
+        } else {
+            Ok(display.disable_text_cursor()?)
+        }
+    }
+
+    /// Insert `c` at the insertion point and move past it. Returns `false`, changing nothing,
+    /// if the field is full.
+    pub fn insert<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, c: char) -> Result<bool, CheckedError<I::Error>> {
+        let mut buf = [0; 4];
+        let encoded = c.encode_utf8(&mut buf).as_bytes();
+        if self.len + encoded.len() > N {
+            return Ok(false);
+        }
+        let at = self.byte_offset(self.cursor);
+        self.bytes.copy_within(at..self.len, at + encoded.len());
+        self.bytes[at..at + encoded.len()].copy_from_slice(encoded);
+        self.len += encoded.len();
+        let column = self.cursor - self.scroll;
+        self.cursor += 1;
+        self.edited(display, column)?;
+        Ok(true)
+    }
+
+    /// Remove the character before the insertion point.
+    pub fn backspace<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        if self.cursor == 0 {
+            return Ok(());
+        }
+        self.cursor -= 1;
+        self.remove_at_cursor();
+        let column = self.cursor.saturating_sub(self.scroll);
+        self.edited(display, column)
+    }
+
+    /// Remove the character after the insertion point.
+    pub fn delete<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        if self.cursor >= self.text().chars().count() {
+            return Ok(());
+        }
+        self.remove_at_cursor();
+        let column = self.cursor - self.scroll;
+        self.edited(display, column)
+    }
+
+    /// Move the insertion point to character `index`, clamped to the end of the text.
+    pub fn move_to<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, index: usize) -> Result<(), CheckedError<I::Error>> {
+        self.cursor = index.min(self.text().chars().count());
+        if self.scroll_to_cursor() {
+            return self.redraw_from(display, 0);
+        }
+        self.place_cursor(display)
+    }
+
+    pub fn move_left<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        self.move_to(display, self.cursor.saturating_sub(1))
+    }
+
+    pub fn move_right<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        self.move_to(display, self.cursor + 1)
+    }
+
+    /// Area inside the border and inset the text is written in.
+    fn text_area(&self) -> Rect {
+        let inset = (TEXT_INSET + 1).min(self.rect.width / 2).min(self.rect.height / 2);
+        Rect::new(self.rect.x + inset, self.rect.y + inset, self.rect.width - 2 * inset, self.rect.height - 2 * inset)
+    }
+
+    /// Characters that fit in the box, leaving room for the cursor after the last one.
+    fn columns(&self) -> usize {
+        let width = self.text_area().width.saturating_sub(CURSOR_WIDTH);
+        (width / self.style.char_width().max(1)) as usize
+    }
+
+    fn byte_offset(&self, index: usize) -> usize {
+        self.text().char_indices().nth(index).map_or(self.len, |(offset, _)| offset)
+    }
+
+    fn remove_at_cursor(&mut self) {
+        let at = self.byte_offset(self.cursor);
+        let next = self.byte_offset(self.cursor + 1);
+        self.bytes.copy_within(next..self.len, at);
+        self.len -= next - at;
+    }
+
+    /// Scroll so the insertion point is visible. Returns whether the text scrolled.
+    fn scroll_to_cursor(&mut self) -> bool {
+        let columns = self.columns();
+        let scroll = if self.cursor < self.scroll {
+            self.cursor
+        } else if self.cursor > self.scroll + columns {
+            self.cursor - columns
+        } else {
+            self.scroll
+        };
+        let scrolled = scroll != self.scroll;
+        self.scroll = scroll;
+        scrolled
+    }
+
+    /// Redraw after the text changed from visible column `column` on.
+    fn edited<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, column: usize) -> Result<(), CheckedError<I::Error>> {
+        let column = if self.scroll_to_cursor() { 0 } else { column };
+        self.redraw_from(display, column)
+    }
+
+    /// Clear the box from visible column `column` to its right edge, write the visible text
+    /// from there and place the cursor.
+    fn redraw_from<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>, column: usize) -> Result<(), CheckedError<I::Error>> {
+        let area = self.text_area();
+        let char_width = self.style.char_width();
+        let left = (column as u16).saturating_mul(char_width).min(area.width);
+        display.clear_area(Rect::new(area.x + left, area.y, area.width - left, area.height), self.background_color)?;
+        let start = self.byte_offset(self.scroll + column);
+        let end = self.byte_offset(self.scroll + self.columns());
+        if start < end {
+            let style = TextStyle { bg_color: None, ..self.style };
+            let text_y = area.y + area.height.saturating_sub(style.cell_height()) / 2;
+            let text_rect = Rect::new(area.x + left, text_y, area.width - left, style.cell_height());
+            display.write_text_aligned(&self.text()[start..end], text_rect, TextAlign::Left, style)?;
+        }
+        self.place_cursor(display)
+    }
+
+    fn place_cursor<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        if !self.focused {
+            return Ok(());
+        }
+        let area = self.text_area();
+        let height = self.style.cell_height();
+        let x = area.x + ((self.cursor - self.scroll) as u16).saturating_mul(self.style.char_width());
+        let y = area.y + area.height.saturating_sub(height) / 2;
+        Ok(display.place_text_cursor(x, y, CURSOR_WIDTH, height)?)
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// On/off switch: a rounded track with a round thumb at its left end when off and its right
+/// end when on.
+pub struct Toggle {
+    pub rect: Rect,
+    pub on_color: u32,
+    pub off_color: u32,
+    pub thumb_color: u32,
+    on: bool,
+}
+
+impl Toggle {
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            on_color: 0x00C000,
+            off_color: 0x404040,
+            thumb_color: 0xFFFFFF,
+            on: false,
+        }
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// Draw the track and the thumb in the current state.
+    pub fn draw<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&self, display: &mut LT7683<I, RESET, W>) -> Result<(), CheckedError<I::Error>> {
+        if self.rect.is_empty() {
+            return Ok(());
+        }
+        let Rect { x, y, width, height } = self.rect;
+        let radius = height / 2;
+        let color = if self.on { self.on_color } else { self.off_color };
+        display.draw_rounded_rectangle(x, y, x + width - 1, y + height - 1, radius, color, true)?;
+        // Thumb inset from the track edge by an eighth of the height
+        let inset = (height / 8).max(1);
+        let thumb_radius = radius.saturating_sub(inset);
+        let center_x = if self.on { x + width - radius } else { x + radius };
+        Ok(display.draw_circle(center_x, y + radius, thumb_radius, self.thumb_color, true)?)
+    }
+
+    /// Switch to `on`, redrawing only if the state changes.
+    pub fn set_on<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>, on: bool) -> Result<(), CheckedError<I::Error>> {
+        if self.on != on {
+            self.on = on;
+            self.draw(display)?;
+        }
+        Ok(())
+    }
+
+    /// Flip the state, e.g. when a touch lands inside `rect`. Returns the new state.
+    pub fn toggle<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(&mut self, display: &mut LT7683<I, RESET, W>) -> Result<bool, CheckedError<I::Error>> {
+        self.set_on(display, !self.on)?;
+        Ok(self.on)
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{CheckedError, LT7683, LT7683Interface, Rect, TextAlign, TextStyle, WaitStrategy};
+use embedded_hal::digital::OutputPin;
+
+/// Colors and spacing of a [`Ui`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Color under everything outside panels.
+    pub background: u32,
+    pub panel_color: u32,
+    pub border_color: u32,
+    /// Text of labels and buttons; its background is ignored.
+    pub text: TextStyle,
+    pub button_color: u32,
+    /// Button color while touched.
+    pub pressed_color: u32,
+    pub corner_radius: u16,
+    /// Space between a panel's border and its contents, and around button text.
+    pub padding: u16,
+    /// Space between widgets laid out one below the other.
+    pub spacing: u16,
+}
+
+impl Theme {
+    pub const fn new() -> Self {
+        Self {
+            background: 0x000000,
+            panel_color: 0x202020,
+            border_color: 0x666666,
+            text: TextStyle::new(0xFFFFFF),
+            button_color: 0x0066FF,
+            pressed_color: 0x003380,
+            corner_radius: 8,
+            padding: 8,
+            spacing: 6,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A widget as drawn in the last frame, to tell whether it has to be drawn again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Slot {
+    rect: Rect,
+    /// Color under the widget, to clear it with when it moves or goes away.
+    background: u32,
+    /// Hash of what the widget shows.
+    hash: u32,
+}
+
+/// Immediate-mode GUI: the application declares its buttons, labels and panels anew every
+/// frame and reacts to button clicks where it declares them, with no widget objects to keep
+/// in sync with its state:
+///
+/// ```ignore
+/// let mut frame = ui.frame(&mut display, screen, touch);
+/// frame.panel(Rect::new(20, 20, 200, 160))?;
+/// frame.label(if running { "Running" } else { "Stopped" })?;
+/// if frame.button("Start")? {
+///     running = true;
+/// }
+/// frame.end()?;
+/// ```
+///
+/// Widgets are laid out one below the other and told apart by the order they are declared in.
+/// The `N` widgets declared first are remembered between frames, and only those that changed,
+/// moved, or sit on a panel that was drawn again are drawn; any further widgets are drawn every
+/// frame.
+pub struct Ui<const N: usize> {
+    /// After changing the theme, [`invalidate`](Self::invalidate) the widgets drawn with it.
+    pub theme: Theme,
+    slots: [Option<Slot>; N],
+    last_touch: Option<(u16, u16)>,
+    /// Index of the button the current touch started on.
+    active: Option<usize>,
+    /// Draw every widget in the next frame.
+    repaint: bool,
+}
+
+impl<const N: usize> Ui<N> {
+    pub const fn new(theme: Theme) -> Self {
+        Self { theme, slots: [None; N], last_touch: None, active: None, repaint: true }
+    }
+
+    /// Draw every widget in the next frame, e.g. after drawing over the screen.
+    pub fn invalidate(&mut self) {
+        self.repaint = true;
+    }
+
+    /// Start a frame laying widgets out in `area`, with the current touch point, if any. The
+    /// area itself is not drawn.
+    pub fn frame<'a, I: LT7683Interface, RESET: OutputPin, W: WaitStrategy>(
+        &'a mut self,
+        display: &'a mut LT7683<I, RESET, W>,
+        area: Rect,
+        touch: Option<(u16, u16)>,
+    ) -> UiFrame<'a, I, RESET, W, N> {
+        let background = self.theme.background;
+        UiFrame { ui: self, display, touch, area, cursor_y: area.y, background, next: 0, damage: None }
+    }
+}
+
+/// One frame of a [`Ui`], ended with [`end`](Self::end).
+pub struct UiFrame<'a, I: LT7683Interface, RESET, W, const N: usize> {
+    ui: &'a mut Ui<N>,
+    display: &'a mut LT7683<I, RESET, W>,
+    touch: Option<(u16, u16)>,
+    /// Area the following widgets are laid out in.
+    area: Rect,
+    /// Top of the next widget.
+    cursor_y: u16,
+    /// Color under the following widgets.
+    background: u32,
+    /// Index of the next widget.
+    next: usize,
+    /// Bounding box of everything drawn or cleared so far.
+    damage: Option<Rect>,
+}
+
+impl<I: LT7683Interface, RESET: OutputPin, W: WaitStrategy, const N: usize> UiFrame<'_, I, RESET, W, N> {
+    /// Draw a panel filling `rect` and lay the following widgets out inside it.
+    pub fn panel(&mut self, rect: Rect) -> Result<(), CheckedError<I::Error>> {
+        let theme = self.ui.theme;
+        let hash = fnv1a(fnv1a(FNV_OFFSET, &[0]), &theme.panel_color.to_le_bytes());
+        if self.slot(rect, hash)? {
+            self.display.clear_area(rect, theme.panel_color)?;
+            self.display.draw_rect_border(rect, 1, theme.border_color)?;
+        }
+        let inset = theme.padding.saturating_add(1).min(rect.width / 2).min(rect.height / 2);
+        self.area = Rect::new(rect.x + inset, rect.y + inset, rect.width - 2 * inset, rect.height - 2 * inset);
+        self.cursor_y = self.area.y;
+        self.background = theme.panel_color;
+        Ok(())
+    }
+
+    /// A line of text.
+    pub fn label(&mut self, text: &str) -> Result<(), CheckedError<I::Error>> {
+        let style = TextStyle { bg_color: None, ..self.ui.theme.text };
+        let rect = self.allocate(style.cell_height());
+        let hash = fnv1a(fnv1a(FNV_OFFSET, &[1]), text.as_bytes());
+        if self.slot(rect, hash)? {
+            self.display.clear_area(rect, self.background)?;
+            self.display.write_text_aligned(text, rect, TextAlign::Left, style)?;
+        }
+        Ok(())
+    }
+
+    /// A button spanning the layout area. Returns whether it was clicked: touched and released
+    /// again without the touch leaving it.
+    pub fn button(&mut self, text: &str) -> Result<bool, CheckedError<I::Error>> {
+        let theme = self.ui.theme;
+        let style = TextStyle { bg_color: None, ..theme.text };
+        let rect = self.allocate(style.cell_height().saturating_add(2 * theme.padding));
+        let index = self.next;
+        let pressed = self.touch.is_some_and(|(x, y)| rect.contains(x, y));
+        if pressed && self.ui.last_touch.is_none() {
+            self.ui.active = Some(index);
+        }
+        let released = self.touch.is_none() && self.ui.last_touch.is_some_and(|(x, y)| rect.contains(x, y));
+        let clicked = released && self.ui.active == Some(index);
+        let hash = fnv1a(fnv1a(FNV_OFFSET, &[2, pressed as u8]), text.as_bytes());
+        if self.slot(rect, hash)? && !rect.is_empty() {
+            let color = if pressed { theme.pressed_color } else { theme.button_color };
+            let (right, bottom) = (rect.x + rect.width - 1, rect.y + rect.height - 1);
+            self.display.clear_area(rect, self.background)?;
+            self.display.draw_rounded_rectangle(rect.x, rect.y, right, bottom, theme.corner_radius, color, true)?;
+            let text_rect = Rect::new(rect.x, rect.y + theme.padding, rect.width, style.cell_height());
+            self.display.write_text_aligned(text, text_rect, TextAlign::Center, style)?;
+        }
+        Ok(clicked)
+    }
+
+    /// Leave `height` pixels empty before the next widget.
+    pub fn space(&mut self, height: u16) {
+        self.cursor_y = self.cursor_y.saturating_add(height);
+    }
+
+    /// Finish the frame, clearing widgets of the last frame that were not declared in this
+    /// one.
+    pub fn end(self) -> Result<(), CheckedError<I::Error>> {
+        let mut repaint = false;
+        for index in self.next..N {
+            let Some(stale) = self.ui.slots[index].take() else {
+                continue;
+            };
+            self.display.clear_area(stale.rect, stale.background)?;
+            // Clearing may have hit widgets drawn in this frame, which the next one restores
+            repaint |= self.ui.slots[..self.next].iter().flatten().any(|slot| slot.rect.intersection(&stale.rect).is_some());
+        }
+        self.ui.repaint = repaint;
+        self.ui.last_touch = self.touch;
+        if self.touch.is_none() {
+            self.ui.active = None;
+        }
+        Ok(())
+    }
+
+    /// Area for the next widget, `height` pixels high across the layout area.
+    fn allocate(&mut self, height: u16) -> Rect {
+        let bottom = self.area.y.saturating_add(self.area.height);
+        let height = height.min(bottom.saturating_sub(self.cursor_y));
+        let rect = Rect::new(self.area.x, self.cursor_y, self.area.width, height);
+        self.cursor_y = self.cursor_y.saturating_add(height).saturating_add(self.ui.theme.spacing);
+        rect
+    }
+
+    /// Record the next widget, at `rect` showing content hashed to `hash`, and return whether
+    /// it has to be drawn. Where it moved from is cleared.
+    fn slot(&mut self, rect: Rect, hash: u32) -> Result<bool, CheckedError<I::Error>> {
+        let index = self.next;
+        self.next += 1;
+        let slot = Slot { rect, background: self.background, hash };
+        let damaged = self.damage.is_some_and(|damage| damage.intersection(&rect).is_some());
+        let Some(stored) = self.ui.slots.get_mut(index) else {
+            self.add_damage(rect);
+            return Ok(true);
+        };
+        let previous = stored.replace(slot);
+        if let Some(previous) = previous && previous.rect != rect {
+            self.display.clear_area(previous.rect, previous.background)?;
+            self.add_damage(previous.rect);
+        }
+        let redraw = self.ui.repaint || damaged || previous != Some(slot);
+        if redraw {
+            self.add_damage(rect);
+        }
+        Ok(redraw)
+    }
+
+    fn add_damage(&mut self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        self.damage = Some(match self.damage {
+            Some(damage) => bounding_box(&damage, &rect),
+            None => rect,
+        });
+    }
+}
+
+const FNV_OFFSET: u32 = 0x811C_9DC5;
+
+fn fnv1a(hash: u32, bytes: &[u8]) -> u32 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ byte as u32).wrapping_mul(0x0100_0193))
+}
+
+/// Smallest rectangle covering both.
+fn bounding_box(a: &Rect, b: &Rect) -> Rect {
+    let (left, top) = (a.x.min(b.x), a.y.min(b.y));
+    let right = (a.x as u32 + a.width as u32).max(b.x as u32 + b.width as u32);
+    let bottom = (a.y as u32 + a.height as u32).max(b.y as u32 + b.height as u32);
+    Rect::new(left, top, (right - left as u32) as u16, (bottom - top as u32) as u16)
+}